@@ -163,6 +163,11 @@
               example = 8081;
               description = "TCP port to use for the websocket server.";
             };
+            staleAfter = mkOption {
+              type = types.int;
+              default = 300;
+              description = "Seconds without a successful fetch after which the crawler counts as stale and /readyz fails.";
+            };
             hafasBaseUrl = mkOption {
               type = types.str;
               default = "https://v6.vbb.transport.rest";
@@ -193,6 +198,8 @@
               environment = {
                 DATABASE_URL = "postgres://localhost/isre1late?host=/run/postgresql";
                 HAFAS_BASE_URL = cfg.hafasBaseUrl;
+                # The service has no system fonts, but the labels of rendered PNGs need one.
+                RENDER_FONTS_DIR = "${pkgs.dejavu_fonts}/share/fonts";
               };
 
               serviceConfig = {
@@ -201,7 +208,8 @@
                   ${server}/bin/isre1late-server \
                     --listen '::1' \
                     --port ${builtins.toString cfg.port} \
-                    --ws-port ${builtins.toString cfg.websocketPort}
+                    --ws-port ${builtins.toString cfg.websocketPort} \
+                    --stale-after ${builtins.toString cfg.staleAfter}
                 '';
                 Restart = "always";
                 RestartSec = "30s";
@@ -237,6 +245,12 @@
                     gzip_types application/json;
                   '';
                 };
+                "/api/sse/" = {
+                  proxyPass = "http://[::1]:${toString cfg.port}";
+                  extraConfig = ''
+                    proxy_buffering off;
+                  '';
+                };
                 "/api/ws".return = "301 /api/ws/";
                 "/api/ws/" = {
                   proxyPass = "http://[::1]:${toString cfg.websocketPort}";
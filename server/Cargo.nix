@@ -159,6 +159,72 @@ rec {
           "rustc-dep-of-std" = [ "core" ];
         };
       };
+      "ahash" = rec {
+        crateName = "ahash";
+        version = "0.8.12";
+        edition = "2018";
+        sha256 = "0xbsp9rlm5ki017c0w6ay8kjwinwm8knjncci95mii30rmwz25as";
+        authors = [
+          "Tom Kaitchuck <Tom.Kaitchuck@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "cfg-if";
+            packageId = "cfg-if 1.0.3";
+          }
+          {
+            name = "const-random";
+            packageId = "const-random";
+            optional = true;
+          }
+          {
+            name = "getrandom";
+            packageId = "getrandom 0.3.3";
+            optional = true;
+          }
+          {
+            name = "once_cell";
+            packageId = "once_cell";
+            usesDefaultFeatures = false;
+            target =
+              { target, features }: (!(("arm" == target."arch" or null) && ("none" == target."os" or null)));
+            features = [ "alloc" ];
+          }
+          {
+            name = "zerocopy";
+            packageId = "zerocopy";
+            usesDefaultFeatures = false;
+            features = [ "simd" ];
+          }
+        ];
+        buildDependencies = [
+          {
+            name = "version_check";
+            packageId = "version_check";
+          }
+        ];
+        features = {
+          "atomic-polyfill" = [
+            "dep:portable-atomic"
+            "once_cell/critical-section"
+          ];
+          "compile-time-rng" = [ "const-random" ];
+          "const-random" = [ "dep:const-random" ];
+          "default" = [
+            "std"
+            "runtime-rng"
+          ];
+          "getrandom" = [ "dep:getrandom" ];
+          "runtime-rng" = [ "getrandom" ];
+          "serde" = [ "dep:serde" ];
+        };
+        resolvedDefaultFeatures = [
+          "compile-time-rng"
+          "const-random"
+          "getrandom"
+          "runtime-rng"
+        ];
+      };
       "aho-corasick" = rec {
         crateName = "aho-corasick";
         version = "1.1.3";
@@ -190,6 +256,39 @@ rec {
           "std"
         ];
       };
+      "alloc-no-stdlib" = rec {
+        crateName = "alloc-no-stdlib";
+        version = "3.0.0";
+        edition = "2015";
+        crateBin = [ ];
+        sha256 = "0ync9wrxr8fg5wad8547wd7400f2l2mhxhlm83v6yjdz8zynryy2";
+        libName = "alloc_no_stdlib";
+        authors = [
+          "Daniel Reiter Horn <danielrh@dropbox.com>"
+        ];
+        features = {
+        };
+      };
+      "alloc-stdlib" = rec {
+        crateName = "alloc-stdlib";
+        version = "0.3.0";
+        edition = "2015";
+        crateBin = [ ];
+        sha256 = "1qc6flsgl4b6231ykik493y8g5418zrba6j1hshvz203g1jihp0b";
+        libName = "alloc_stdlib";
+        authors = [
+          "Daniel Reiter Horn <danielrh@dropbox.com>"
+        ];
+        dependencies = [
+          {
+            name = "alloc-no-stdlib";
+            packageId = "alloc-no-stdlib";
+          }
+        ];
+        features = {
+          "unsafe" = [ "alloc-no-stdlib/unsafe" ];
+        };
+      };
       "android-tzdata" = rec {
         crateName = "android-tzdata";
         version = "0.1.1";
@@ -351,6 +450,32 @@ rec {
           }
         ];
 
+      };
+      "anyhow" = rec {
+        crateName = "anyhow";
+        version = "1.0.104";
+        edition = "2021";
+        sha256 = "0w34jjcm02p5g9kvsjr1dvpw0zs2fi7igi6nr414fkm5gz85w2ik";
+        authors = [
+          "David Tolnay <dtolnay@gmail.com>"
+        ];
+        features = {
+          "default" = [ "std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
+      "arrayref" = rec {
+        crateName = "arrayref";
+        version = "0.3.9";
+        edition = "2015";
+        sha256 = "1jzyp0nvp10dmahaq9a2rnxqdd5wxgbvp8xaibps3zai8c9fi8kn";
+        authors = [
+          "David Roundy <roundyd@physics.oregonstate.edu>"
+        ];
+
       };
       "arrayvec" = rec {
         crateName = "arrayvec";
@@ -371,6 +496,107 @@ rec {
           "std"
         ];
       };
+      "async-compression" = rec {
+        crateName = "async-compression";
+        version = "0.4.50";
+        edition = "2018";
+        sha256 = "18gmagyzh9584n0j0smnq2kcx0a819141s6lpan92diynjcvs6gf";
+        libName = "async_compression";
+        authors = [
+          "Wim Looman <wim@nemo157.com>"
+          "Allen Bui <fairingrey@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "compression-codecs";
+            packageId = "compression-codecs";
+          }
+          {
+            name = "compression-core";
+            packageId = "compression-core";
+          }
+          {
+            name = "pin-project-lite";
+            packageId = "pin-project-lite";
+          }
+          {
+            name = "tokio";
+            packageId = "tokio";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+        ];
+        devDependencies = [
+          {
+            name = "tokio";
+            packageId = "tokio";
+            usesDefaultFeatures = false;
+            features = [
+              "io-util"
+              "macros"
+              "rt-multi-thread"
+              "io-std"
+            ];
+          }
+        ];
+        features = {
+          "all" = [
+            "all-implementations"
+            "all-algorithms"
+          ];
+          "all-algorithms" = [
+            "brotli"
+            "bzip2"
+            "deflate"
+            "deflate64"
+            "gzip"
+            "lz4"
+            "lzma"
+            "xz"
+            "xz-parallel"
+            "zlib"
+            "zstd"
+          ];
+          "all-implementations" = [
+            "futures-io"
+            "tokio"
+          ];
+          "brotli" = [ "compression-codecs/brotli" ];
+          "brotli-mbrotli" = [ "compression-codecs/brotli-mbrotli" ];
+          "bzip2" = [ "compression-codecs/bzip2" ];
+          "deflate" = [ "compression-codecs/deflate" ];
+          "deflate64" = [ "compression-codecs/deflate64" ];
+          "futures-io" = [ "dep:futures-io" ];
+          "gzip" = [ "compression-codecs/gzip" ];
+          "lz4" = [ "compression-codecs/lz4" ];
+          "lzma" = [ "compression-codecs/lzma" ];
+          "tokio" = [ "dep:tokio" ];
+          "xz" = [
+            "compression-codecs/xz"
+            "lzma"
+          ];
+          "xz-parallel" = [
+            "compression-codecs/xz-parallel"
+            "xz"
+          ];
+          "xz2" = [
+            "compression-codecs/xz2"
+            "xz"
+          ];
+          "zlib" = [ "compression-codecs/zlib" ];
+          "zstd" = [ "compression-codecs/zstd" ];
+          "zstdmt" = [
+            "compression-codecs/zstdmt"
+            "zstd"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "brotli"
+          "gzip"
+          "tokio"
+          "zstd"
+        ];
+      };
       "async-stream" = rec {
         crateName = "async-stream";
         version = "0.3.6";
@@ -417,7 +643,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "full"
               "visit-mut"
@@ -447,7 +673,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             usesDefaultFeatures = false;
             features = [
               "clone-impls"
@@ -601,7 +827,7 @@ rec {
           }
           {
             name = "miniz_oxide";
-            packageId = "miniz_oxide";
+            packageId = "miniz_oxide 0.8.9";
             usesDefaultFeatures = false;
             target =
               { target, features }:
@@ -746,6 +972,100 @@ rec {
         ];
 
       };
+      "brotli" = rec {
+        crateName = "brotli";
+        version = "9.0.0";
+        edition = "2015";
+        crateBin = [ ];
+        sha256 = "0ssc7lyjbsw7451vk3yh0gmrm66ikgj2yq9xc9rpiji3bjvm3f7q";
+        authors = [
+          "Daniel Reiter Horn <danielrh@dropbox.com>"
+          "The Brotli Authors"
+        ];
+        dependencies = [
+          {
+            name = "alloc-no-stdlib";
+            packageId = "alloc-no-stdlib";
+          }
+          {
+            name = "alloc-stdlib";
+            packageId = "alloc-stdlib";
+            optional = true;
+          }
+          {
+            name = "brotli-decompressor";
+            packageId = "brotli-decompressor";
+            usesDefaultFeatures = false;
+          }
+        ];
+        devDependencies = [
+          {
+            name = "alloc-no-stdlib";
+            packageId = "alloc-no-stdlib";
+            features = [ "unsafe" ];
+          }
+        ];
+        features = {
+          "alloc-stdlib" = [ "dep:alloc-stdlib" ];
+          "benchmark" = [ "brotli-decompressor/benchmark" ];
+          "default" = [ "std" ];
+          "disable-timer" = [ "brotli-decompressor/disable-timer" ];
+          "ffi-api" = [ "brotli-decompressor/ffi-api" ];
+          "seccomp" = [
+            "alloc-no-stdlib/unsafe"
+            "brotli-decompressor/seccomp"
+          ];
+          "sha2" = [ "dep:sha2" ];
+          "std" = [
+            "alloc-stdlib"
+            "brotli-decompressor/std"
+          ];
+          "validation" = [ "sha2" ];
+        };
+        resolvedDefaultFeatures = [
+          "alloc-stdlib"
+          "default"
+          "std"
+        ];
+      };
+      "brotli-decompressor" = rec {
+        crateName = "brotli-decompressor";
+        version = "6.0.1";
+        edition = "2015";
+        crateBin = [ ];
+        sha256 = "1msbjd00hwgbgg7pdf4cqx5b4my8y4ld98m5dys3rf6s9nyxj74l";
+        libName = "brotli_decompressor";
+        authors = [
+          "Daniel Reiter Horn <danielrh@dropbox.com>"
+          "The Brotli Authors"
+        ];
+        dependencies = [
+          {
+            name = "alloc-no-stdlib";
+            packageId = "alloc-no-stdlib";
+          }
+          {
+            name = "alloc-stdlib";
+            packageId = "alloc-stdlib";
+            optional = true;
+          }
+        ];
+        features = {
+          "alloc-stdlib" = [ "dep:alloc-stdlib" ];
+          "default" = [ "std" ];
+          "no-stdlib-ffi-binding" = [ "ffi-api" ];
+          "seccomp" = [ "alloc-no-stdlib/unsafe" ];
+          "std" = [ "alloc-stdlib" ];
+          "unsafe" = [
+            "alloc-no-stdlib/unsafe"
+            "alloc-stdlib/unsafe"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "alloc-stdlib"
+          "std"
+        ];
+      };
       "bumpalo" = rec {
         crateName = "bumpalo";
         version = "3.19.0";
@@ -817,9 +1137,9 @@ rec {
       };
       "bytemuck" = rec {
         crateName = "bytemuck";
-        version = "1.23.2";
+        version = "1.25.2";
         edition = "2018";
-        sha256 = "0xs637lsr9p73ackjkmbjw80dp1dfdw0ydhdk0gzjcnzpkpfm59r";
+        sha256 = "15rp2m7j7kq22s76cbjwmrkd5r8lvacnm0mnrj013cnzka22x0wm";
         authors = [
           "Lokathor <zefria@gmail.com>"
         ];
@@ -847,7 +1167,13 @@ rec {
             "zeroable_unwind_fn"
           ];
           "must_cast_extra" = [ "must_cast" ];
+          "nightly_portable_simd" = [ "rustversion" ];
+          "rustversion" = [ "dep:rustversion" ];
         };
+        resolvedDefaultFeatures = [
+          "aarch64_simd"
+          "extern_crate_alloc"
+        ];
       };
       "byteorder" = rec {
         crateName = "byteorder";
@@ -1208,7 +1534,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "full" ];
           }
         ];
@@ -1287,6 +1613,117 @@ rec {
         features = {
         };
       };
+      "compression-codecs" = rec {
+        crateName = "compression-codecs";
+        version = "0.4.45";
+        edition = "2018";
+        sha256 = "0jk12ybh2wl2lg14g4x4qkl0npf4vyw34djx0wbm7bd01d39iz4q";
+        libName = "compression_codecs";
+        authors = [
+          "Wim Looman <wim@nemo157.com>"
+          "Allen Bui <fairingrey@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "brotli";
+            packageId = "brotli";
+            optional = true;
+          }
+          {
+            name = "compression-core";
+            packageId = "compression-core";
+          }
+          {
+            name = "flate2";
+            packageId = "flate2";
+            optional = true;
+          }
+          {
+            name = "memchr";
+            packageId = "memchr";
+            optional = true;
+          }
+          {
+            name = "zstd";
+            packageId = "zstd 0.14.2";
+            rename = "libzstd";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "zstd-safe";
+            packageId = "zstd-safe 8.1.0";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "all-algorithms" = [
+            "brotli"
+            "bzip2"
+            "deflate"
+            "gzip"
+            "lz4"
+            "lzma"
+            "xz-parallel"
+            "xz"
+            "zlib"
+            "zstd"
+            "deflate64"
+          ];
+          "brotli" = [ "dep:brotli" ];
+          "brotli-mbrotli" = [ "dep:mbrotli" ];
+          "bzip2" = [ "dep:bzip2" ];
+          "deflate" = [ "flate2" ];
+          "deflate64" = [ "dep:deflate64" ];
+          "flate2" = [ "dep:flate2" ];
+          "gzip" = [
+            "flate2"
+            "memchr"
+          ];
+          "libzstd" = [ "dep:libzstd" ];
+          "lz4" = [ "dep:lz4" ];
+          "lzma" = [ "dep:liblzma" ];
+          "memchr" = [ "dep:memchr" ];
+          "xz" = [ "lzma" ];
+          "xz-parallel" = [
+            "xz"
+            "liblzma/parallel"
+          ];
+          "xz2" = [ "xz" ];
+          "zlib" = [ "flate2" ];
+          "zstd" = [
+            "libzstd"
+            "zstd-safe"
+          ];
+          "zstd-safe" = [ "dep:zstd-safe" ];
+          "zstdmt" = [
+            "zstd"
+            "zstd-safe/zstdmt"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "brotli"
+          "flate2"
+          "gzip"
+          "libzstd"
+          "memchr"
+          "zstd"
+          "zstd-safe"
+        ];
+      };
+      "compression-core" = rec {
+        crateName = "compression-core";
+        version = "0.4.33";
+        edition = "2018";
+        sha256 = "056ll4gqcx1kfkg0837j2nni7n8i3m3ns3rc20nw7b7nm57cr33f";
+        libName = "compression_core";
+        authors = [
+          "Wim Looman <wim@nemo157.com>"
+          "Allen Bui <fairingrey@gmail.com>"
+        ];
+
+      };
       "console" = rec {
         crateName = "console";
         version = "0.16.0";
@@ -1346,36 +1783,85 @@ rec {
           "unicode-width"
         ];
       };
-      "cookie" = rec {
-        crateName = "cookie";
-        version = "0.18.1";
+      "const-random" = rec {
+        crateName = "const-random";
+        version = "0.1.18";
         edition = "2018";
-        sha256 = "0iy749flficrlvgr3hjmf3igr738lk81n5akzf4ym4cs6cxg7pjd";
+        sha256 = "0n8kqz3y82ks8znvz1mxn3a9hadca3amzf33gmi6dc3lzs103q47";
+        libName = "const_random";
         authors = [
-          "Sergio Benitez <sb@sergio.bz>"
-          "Alex Crichton <alex@alexcrichton.com>"
+          "Tom Kaitchuck <Tom.Kaitchuck@gmail.com>"
         ];
         dependencies = [
           {
-            name = "percent-encoding";
-            packageId = "percent-encoding";
-            optional = true;
-          }
-          {
-            name = "time";
-            packageId = "time";
-            usesDefaultFeatures = false;
-            features = [
-              "std"
-              "parsing"
-              "formatting"
-              "macros"
-            ];
+            name = "const-random-macro";
+            packageId = "const-random-macro";
           }
         ];
-        buildDependencies = [
-          {
-            name = "version_check";
+
+      };
+      "const-random-macro" = rec {
+        crateName = "const-random-macro";
+        version = "0.1.16";
+        edition = "2018";
+        sha256 = "03iram4ijjjq9j5a7hbnmdngj8935wbsd0f5bm8yw2hblbr3kn7r";
+        procMacro = true;
+        libName = "const_random_macro";
+        authors = [
+          "Tom Kaitchuck <Tom.Kaitchuck@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "getrandom";
+            packageId = "getrandom 0.2.16";
+          }
+          {
+            name = "once_cell";
+            packageId = "once_cell";
+            usesDefaultFeatures = false;
+            features = [
+              "race"
+              "alloc"
+            ];
+          }
+          {
+            name = "tiny-keccak";
+            packageId = "tiny-keccak";
+            features = [ "shake" ];
+          }
+        ];
+
+      };
+      "cookie" = rec {
+        crateName = "cookie";
+        version = "0.18.1";
+        edition = "2018";
+        sha256 = "0iy749flficrlvgr3hjmf3igr738lk81n5akzf4ym4cs6cxg7pjd";
+        authors = [
+          "Sergio Benitez <sb@sergio.bz>"
+          "Alex Crichton <alex@alexcrichton.com>"
+        ];
+        dependencies = [
+          {
+            name = "percent-encoding";
+            packageId = "percent-encoding";
+            optional = true;
+          }
+          {
+            name = "time";
+            packageId = "time";
+            usesDefaultFeatures = false;
+            features = [
+              "std"
+              "parsing"
+              "formatting"
+              "macros"
+            ];
+          }
+        ];
+        buildDependencies = [
+          {
+            name = "version_check";
             packageId = "version_check";
           }
         ];
@@ -1469,6 +1955,22 @@ rec {
           "link"
         ];
       };
+      "core_maths" = rec {
+        crateName = "core_maths";
+        version = "0.1.1";
+        edition = "2015";
+        sha256 = "0c0dv11ixxpc9bsx5xasvl98mb1dlprzcm6qq6ls3nsygw0mwx3p";
+        authors = [
+          "Robert Bastian <me@robertbastian.dev"
+        ];
+        dependencies = [
+          {
+            name = "libm";
+            packageId = "libm";
+          }
+        ];
+
+      };
       "cpufeatures" = rec {
         crateName = "cpufeatures";
         version = "0.2.17";
@@ -1510,6 +2012,29 @@ rec {
         ];
 
       };
+      "crc32fast" = rec {
+        crateName = "crc32fast";
+        version = "1.5.2";
+        edition = "2021";
+        sha256 = "0y0f955n2hr5a8rd9nw9sr23nhjc42ddx3bjc47dnlmqssgpk9q1";
+        authors = [
+          "Sam Rijs <srijs@airpost.net>"
+          "Alex Crichton <alex@alexcrichton.com>"
+        ];
+        dependencies = [
+          {
+            name = "cfg-if";
+            packageId = "cfg-if 1.0.3";
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
       "crossbeam-channel" = rec {
         crateName = "crossbeam-channel";
         version = "0.3.9";
@@ -1558,6 +2083,22 @@ rec {
           "std"
         ];
       };
+      "crunchy" = rec {
+        crateName = "crunchy";
+        version = "0.2.4";
+        edition = "2021";
+        sha256 = "1mbp5navim2qr3x48lyvadqblcxc1dm0lqr0swrkkwy2qblvw3s6";
+        authors = [
+          "Eira Fransham <jackefransham@gmail.com>"
+        ];
+        features = {
+          "default" = [ "limit_128" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "limit_128"
+        ];
+      };
       "crypto-common" = rec {
         crateName = "crypto-common";
         version = "0.1.6";
@@ -1584,6 +2125,55 @@ rec {
         };
         resolvedDefaultFeatures = [ "std" ];
       };
+      "csv" = rec {
+        crateName = "csv";
+        version = "1.4.0";
+        edition = "2021";
+        sha256 = "0f7r2ip0rbi7k377c3xmsh9xd69sillffhpfmbgnvz3yrxl9vkaj";
+        authors = [
+          "Andrew Gallant <jamslam@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "csv-core";
+            packageId = "csv-core";
+          }
+          {
+            name = "itoa";
+            packageId = "itoa";
+          }
+          {
+            name = "ryu";
+            packageId = "ryu";
+          }
+          {
+            name = "serde_core";
+            packageId = "serde_core";
+          }
+        ];
+
+      };
+      "csv-core" = rec {
+        crateName = "csv-core";
+        version = "0.1.13";
+        edition = "2018";
+        sha256 = "10lppd3fdb1i5npgx9xqjs5mjmy2qbdi8n16i48lg03ak4k3qjkh";
+        libName = "csv_core";
+        authors = [
+          "Andrew Gallant <jamslam@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "memchr";
+            packageId = "memchr";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "libc" = [ "memchr/libc" ];
+        };
+        resolvedDefaultFeatures = [ "default" ];
+      };
       "darling" = rec {
         crateName = "darling";
         version = "0.20.11";
@@ -1644,7 +2234,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "full"
               "extra-traits"
@@ -1680,7 +2270,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
           }
         ];
 
@@ -1704,6 +2294,25 @@ rec {
           "std"
         ];
       };
+      "data-url" = rec {
+        crateName = "data-url";
+        version = "0.3.2";
+        edition = "2018";
+        sha256 = "0xl30jidc8s3kh2z3nvnn1nyzhbq5b2wpiqwzj9gjdrndk50n7my";
+        libName = "data_url";
+        authors = [
+          "Simon Sapin <simon.sapin@exyr.org>"
+        ];
+        features = {
+          "default" = [ "std" ];
+          "std" = [ "alloc" ];
+        };
+        resolvedDefaultFeatures = [
+          "alloc"
+          "default"
+          "std"
+        ];
+      };
       "deranged" = rec {
         crateName = "deranged";
         version = "0.4.0";
@@ -1819,7 +2428,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "full"
               "parsing"
@@ -2002,7 +2611,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "derive"
               "fold"
@@ -2059,7 +2668,7 @@ rec {
         dependencies = [
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "full" ];
           }
         ];
@@ -2128,7 +2737,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
           }
         ];
         features = {
@@ -2163,7 +2772,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "extra-traits"
               "full"
@@ -2202,6 +2811,7 @@ rec {
         resolvedDefaultFeatures = [
           "default"
           "std"
+          "use_std"
         ];
       };
       "encode_unicode" = rec {
@@ -2304,6 +2914,33 @@ rec {
         };
         resolvedDefaultFeatures = [ "std" ];
       };
+      "euclid" = rec {
+        crateName = "euclid";
+        version = "0.22.14";
+        edition = "2021";
+        sha256 = "01ksjl4vb8ms89laswnjpld3z4n6c1s7qlqq0djx3imiwdjm787i";
+        authors = [
+          "The Servo Project Developers"
+        ];
+        dependencies = [
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "arbitrary" = [ "dep:arbitrary" ];
+          "bytemuck" = [ "dep:bytemuck" ];
+          "default" = [ "std" ];
+          "libm" = [ "num-traits/libm" ];
+          "malloc_size_of" = [ "dep:malloc_size_of" ];
+          "mint" = [ "dep:mint" ];
+          "serde" = [ "dep:serde" ];
+          "std" = [ "num-traits/std" ];
+        };
+        resolvedDefaultFeatures = [ "std" ];
+      };
       "fastrand" = rec {
         crateName = "fastrand";
         version = "2.3.0";
@@ -2327,6 +2964,22 @@ rec {
           "std"
         ];
       };
+      "fdeflate" = rec {
+        crateName = "fdeflate";
+        version = "0.3.7";
+        edition = "2021";
+        sha256 = "130ga18vyxbb5idbgi07njymdaavvk6j08yh1dfarm294ssm6s0y";
+        authors = [
+          "The image-rs Developers"
+        ];
+        dependencies = [
+          {
+            name = "simd-adler32";
+            packageId = "simd-adler32";
+          }
+        ];
+
+      };
       "figment" = rec {
         crateName = "figment";
         version = "0.10.19";
@@ -2405,6 +3058,110 @@ rec {
           "toml"
         ];
       };
+      "flate2" = rec {
+        crateName = "flate2";
+        version = "1.1.10";
+        edition = "2018";
+        sha256 = "1jvd2cl8j5hyf8imi62y1x7gwzz1hajirni0801yxhds1qp4wqvf";
+        authors = [
+          "Alex Crichton <alex@alexcrichton.com>"
+          "Josh Triplett <josh@joshtriplett.org>"
+        ];
+        dependencies = [
+          {
+            name = "crc32fast";
+            packageId = "crc32fast";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "miniz_oxide";
+            packageId = "miniz_oxide 0.9.1";
+            optional = true;
+            features = [ "simd" ];
+          }
+          {
+            name = "zlib-rs";
+            packageId = "zlib-rs";
+            optional = true;
+            usesDefaultFeatures = false;
+            features = [ "rust-allocator" ];
+          }
+        ];
+        features = {
+          "any_c_zlib" = [ "any_zlib" ];
+          "any_zlib" = [ "any_impl" ];
+          "cloudflare_zlib" = [ "zlib" ];
+          "default" = [
+            "rust_backend"
+            "runtime_detection"
+          ];
+          "document-features" = [ "dep:document-features" ];
+          "libz-ng-sys" = [ "dep:libz-ng-sys" ];
+          "libz-sys" = [ "dep:libz-sys" ];
+          "miniz-sys" = [ "rust_backend" ];
+          "miniz_oxide" = [
+            "any_impl"
+            "dep:miniz_oxide"
+            "dep:crc32fast"
+          ];
+          "runtime_detection" = [
+            "zlib-rs?/std"
+            "crc32fast?/std"
+          ];
+          "rust_backend" = [
+            "miniz_oxide"
+            "any_impl"
+          ];
+          "zlib" = [
+            "any_c_zlib"
+            "libz-sys"
+            "dep:crc32fast"
+          ];
+          "zlib-default" = [
+            "any_c_zlib"
+            "libz-sys/default"
+            "dep:crc32fast"
+          ];
+          "zlib-ng" = [
+            "any_c_zlib"
+            "libz-ng-sys"
+            "dep:crc32fast"
+          ];
+          "zlib-ng-compat" = [
+            "zlib"
+            "libz-sys/zlib-ng"
+            "dep:crc32fast"
+          ];
+          "zlib-rs" = [
+            "any_zlib"
+            "dep:zlib-rs"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "any_impl"
+          "default"
+          "miniz_oxide"
+          "runtime_detection"
+          "rust_backend"
+        ];
+      };
+      "float-cmp" = rec {
+        crateName = "float-cmp";
+        version = "0.9.0";
+        edition = "2018";
+        sha256 = "1i799ksbq7fj9rm9m82g1yqgm6xi3jnrmylddmqknmksajylpplq";
+        libName = "float_cmp";
+        authors = [
+          "Mike Dilger <mike@mikedilger.com>"
+        ];
+        features = {
+          "default" = [ "ratio" ];
+          "num-traits" = [ "dep:num-traits" ];
+          "ratio" = [ "num-traits" ];
+        };
+        resolvedDefaultFeatures = [ "std" ];
+      };
       "fnv" = rec {
         crateName = "fnv";
         version = "1.0.7";
@@ -2422,46 +3179,139 @@ rec {
           "std"
         ];
       };
-      "foreign-types" = rec {
-        crateName = "foreign-types";
-        version = "0.3.2";
-        edition = "2015";
-        sha256 = "1cgk0vyd7r45cj769jym4a6s7vwshvd0z4bqrb92q1fwibmkkwzn";
-        libName = "foreign_types";
-        authors = [
-          "Steven Fackler <sfackler@gmail.com>"
-        ];
+      "fontconfig-parser" = rec {
+        crateName = "fontconfig-parser";
+        version = "0.5.8";
+        edition = "2018";
+        sha256 = "0ijnbzg31sl6v49g7q2l7sl76hjj8z0hvlsz77cdvm029vi77ixv";
+        libName = "fontconfig_parser";
         dependencies = [
           {
-            name = "foreign-types-shared";
-            packageId = "foreign-types-shared";
+            name = "roxmltree";
+            packageId = "roxmltree 0.20.0";
           }
         ];
-
-      };
-      "foreign-types-shared" = rec {
-        crateName = "foreign-types-shared";
-        version = "0.1.1";
-        edition = "2015";
-        sha256 = "0jxgzd04ra4imjv8jgkmdq59kj8fsz6w4zxsbmlai34h26225c00";
-        libName = "foreign_types_shared";
-        authors = [
-          "Steven Fackler <sfackler@gmail.com>"
-        ];
-
+        features = {
+          "log" = [ "dep:log" ];
+          "serde" = [ "dep:serde" ];
+          "serialize" = [ "serde" ];
+        };
       };
-      "form_urlencoded" = rec {
-        crateName = "form_urlencoded";
-        version = "1.2.2";
+      "fontdb" = rec {
+        crateName = "fontdb";
+        version = "0.23.0";
         edition = "2018";
-        sha256 = "1kqzb2qn608rxl3dws04zahcklpplkd5r1vpabwga5l50d2v4k6b";
+        sha256 = "0199vry9x8zn9ix4x4rqvv53dy2ryhy68l53jwr580hj7ndphzj5";
         authors = [
-          "The rust-url developers"
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
         ];
         dependencies = [
           {
-            name = "percent-encoding";
-            packageId = "percent-encoding";
+            name = "fontconfig-parser";
+            packageId = "fontconfig-parser";
+            optional = true;
+            usesDefaultFeatures = false;
+            target =
+              { target, features }:
+              (
+                (target."unix" or false)
+                && (!(("macos" == target."os" or null) || ("android" == target."os" or null)))
+              );
+          }
+          {
+            name = "log";
+            packageId = "log";
+          }
+          {
+            name = "slotmap";
+            packageId = "slotmap";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "tinyvec";
+            packageId = "tinyvec";
+            features = [ "alloc" ];
+          }
+          {
+            name = "ttf-parser";
+            packageId = "ttf-parser";
+            usesDefaultFeatures = false;
+            features = [
+              "opentype-layout"
+              "apple-layout"
+              "variable-fonts"
+              "glyph-names"
+              "no-std-float"
+            ];
+          }
+        ];
+        features = {
+          "default" = [
+            "std"
+            "fs"
+            "memmap"
+            "fontconfig"
+          ];
+          "fontconfig" = [
+            "fontconfig-parser"
+            "fs"
+          ];
+          "fontconfig-parser" = [ "dep:fontconfig-parser" ];
+          "fs" = [ "std" ];
+          "memmap" = [
+            "fs"
+            "memmap2"
+          ];
+          "memmap2" = [ "dep:memmap2" ];
+          "std" = [ "ttf-parser/std" ];
+        };
+        resolvedDefaultFeatures = [
+          "fontconfig"
+          "fontconfig-parser"
+          "fs"
+          "std"
+        ];
+      };
+      "foreign-types" = rec {
+        crateName = "foreign-types";
+        version = "0.3.2";
+        edition = "2015";
+        sha256 = "1cgk0vyd7r45cj769jym4a6s7vwshvd0z4bqrb92q1fwibmkkwzn";
+        libName = "foreign_types";
+        authors = [
+          "Steven Fackler <sfackler@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "foreign-types-shared";
+            packageId = "foreign-types-shared";
+          }
+        ];
+
+      };
+      "foreign-types-shared" = rec {
+        crateName = "foreign-types-shared";
+        version = "0.1.1";
+        edition = "2015";
+        sha256 = "0jxgzd04ra4imjv8jgkmdq59kj8fsz6w4zxsbmlai34h26225c00";
+        libName = "foreign_types_shared";
+        authors = [
+          "Steven Fackler <sfackler@gmail.com>"
+        ];
+
+      };
+      "form_urlencoded" = rec {
+        crateName = "form_urlencoded";
+        version = "1.2.2";
+        edition = "2018";
+        sha256 = "1kqzb2qn608rxl3dws04zahcklpplkd5r1vpabwga5l50d2v4k6b";
+        authors = [
+          "The rust-url developers"
+        ];
+        dependencies = [
+          {
+            name = "percent-encoding";
+            packageId = "percent-encoding";
             usesDefaultFeatures = false;
           }
         ];
@@ -3267,6 +4117,62 @@ rec {
         features = {
         };
       };
+      "half" = rec {
+        crateName = "half";
+        version = "2.7.1";
+        edition = "2021";
+        sha256 = "0jyq42xfa6sghc397mx84av7fayd4xfxr4jahsqv90lmjr5xi8kf";
+        authors = [
+          "Kathryn Long <squeeself@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "cfg-if";
+            packageId = "cfg-if 1.0.3";
+          }
+          {
+            name = "crunchy";
+            packageId = "crunchy";
+            target = { target, features }: ("spirv" == target."arch" or null);
+          }
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            optional = true;
+            usesDefaultFeatures = false;
+            features = [ "libm" ];
+          }
+          {
+            name = "zerocopy";
+            packageId = "zerocopy";
+            usesDefaultFeatures = false;
+            features = [
+              "derive"
+              "simd"
+            ];
+          }
+        ];
+        devDependencies = [
+          {
+            name = "crunchy";
+            packageId = "crunchy";
+          }
+        ];
+        features = {
+          "arbitrary" = [ "dep:arbitrary" ];
+          "bytemuck" = [ "dep:bytemuck" ];
+          "default" = [ "std" ];
+          "num-traits" = [ "dep:num-traits" ];
+          "rand_distr" = [
+            "dep:rand"
+            "dep:rand_distr"
+          ];
+          "rkyv" = [ "dep:rkyv" ];
+          "serde" = [ "dep:serde" ];
+          "std" = [ "alloc" ];
+        };
+        resolvedDefaultFeatures = [ "num-traits" ];
+      };
       "hashbrown 0.12.3" = rec {
         crateName = "hashbrown";
         version = "0.12.3";
@@ -4778,6 +5684,66 @@ rec {
         };
         resolvedDefaultFeatures = [ "compiled_data" ];
       };
+      "imagesize" = rec {
+        crateName = "imagesize";
+        version = "0.14.0";
+        edition = "2021";
+        sha256 = "1725g398w4v35qrv9s3gl8gl5cqj5cwkamn7mvvl12y4niblxr89";
+        authors = [
+          "Maid Dog <maiddogsrl@gmail.com>"
+        ];
+        features = {
+          "default" = [
+            "aesprite"
+            "bmp"
+            "dds"
+            "exr"
+            "farbfeld"
+            "gif"
+            "hdr"
+            "ico"
+            "ilbm"
+            "jpeg"
+            "jxl"
+            "ktx2"
+            "mod"
+            "png"
+            "pnm"
+            "psd"
+            "qoi"
+            "tga"
+            "tiff"
+            "vtf"
+            "webp"
+            "heif"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "aesprite"
+          "bmp"
+          "dds"
+          "default"
+          "exr"
+          "farbfeld"
+          "gif"
+          "hdr"
+          "heif"
+          "ico"
+          "ilbm"
+          "jpeg"
+          "jxl"
+          "ktx2"
+          "mod"
+          "png"
+          "pnm"
+          "psd"
+          "qoi"
+          "tga"
+          "tiff"
+          "vtf"
+          "webp"
+        ];
+      };
       "indexmap 1.9.3" = rec {
         crateName = "indexmap";
         version = "1.9.3";
@@ -4924,6 +5890,29 @@ rec {
           "serde" = [ "dep:serde" ];
         };
       };
+      "integer-encoding" = rec {
+        crateName = "integer-encoding";
+        version = "3.0.4";
+        edition = "2018";
+        sha256 = "00ng7jmv6pqwqc8w1297f768bn0spwwicdr7hb40baax00r3gc4b";
+        libName = "integer_encoding";
+        authors = [
+          "Lewin Bormann <lbo@spheniscida.de>"
+        ];
+        features = {
+          "async-trait" = [ "dep:async-trait" ];
+          "futures-util" = [ "dep:futures-util" ];
+          "futures_async" = [
+            "futures-util"
+            "async-trait"
+          ];
+          "tokio" = [ "dep:tokio" ];
+          "tokio_async" = [
+            "tokio"
+            "async-trait"
+          ];
+        };
+      };
       "io-uring" = rec {
         crateName = "io-uring";
         version = "0.7.10";
@@ -5089,6 +6078,16 @@ rec {
           "Kerstin Humm <kerstin@erictapen.name>"
         ];
         dependencies = [
+          {
+            name = "async-compression";
+            packageId = "async-compression";
+            features = [
+              "tokio"
+              "gzip"
+              "brotli"
+              "zstd"
+            ];
+          }
           {
             name = "bus";
             packageId = "bus";
@@ -5102,6 +6101,10 @@ rec {
             packageId = "clap";
             features = [ "derive" ];
           }
+          {
+            name = "csv";
+            packageId = "csv";
+          }
           {
             name = "diesel";
             packageId = "diesel";
@@ -5136,11 +6139,26 @@ rec {
             name = "num-format";
             packageId = "num-format";
           }
+          {
+            name = "parquet";
+            packageId = "parquet";
+            usesDefaultFeatures = false;
+            features = [ "zstd" ];
+          }
           {
             name = "pq-sys";
             packageId = "pq-sys";
             features = [ "pkg-config" ];
           }
+          {
+            name = "prometheus";
+            packageId = "prometheus";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "prost";
+            packageId = "prost";
+          }
           {
             name = "reqwest";
             packageId = "reqwest";
@@ -5149,6 +6167,15 @@ rec {
               "json"
             ];
           }
+          {
+            name = "resvg";
+            packageId = "resvg";
+            usesDefaultFeatures = false;
+            features = [
+              "text"
+              "system-fonts"
+            ];
+          }
           {
             name = "rocket";
             packageId = "rocket";
@@ -5204,18 +6231,63 @@ rec {
             name = "tungstenite";
             packageId = "tungstenite";
           }
+          {
+            name = "twox-hash";
+            packageId = "twox-hash 2.1.5";
+            usesDefaultFeatures = false;
+            features = [ "xxhash3_64" ];
+          }
           {
             name = "urlencoding";
             packageId = "urlencoding";
           }
+          {
+            name = "utoipa";
+            packageId = "utoipa";
+            features = [ "rocket_extras" ];
+          }
           {
             name = "zstd";
-            packageId = "zstd";
+            packageId = "zstd 0.14.2";
             features = [ "zdict_builder" ];
           }
         ];
+        devDependencies = [
+          {
+            name = "bytes";
+            packageId = "bytes";
+          }
+        ];
 
       };
+      "itertools" = rec {
+        crateName = "itertools";
+        version = "0.14.0";
+        edition = "2018";
+        sha256 = "118j6l1vs2mx65dqhwyssbrxpawa90886m3mzafdvyip41w2q69b";
+        authors = [
+          "bluss"
+        ];
+        dependencies = [
+          {
+            name = "either";
+            packageId = "either";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "default" = [ "use_std" ];
+          "use_std" = [
+            "use_alloc"
+            "either/use_std"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "use_alloc"
+          "use_std"
+        ];
+      };
       "itoa" = rec {
         crateName = "itoa";
         version = "1.0.15";
@@ -5281,6 +6353,61 @@ rec {
           "std"
         ];
       };
+      "kurbo" = rec {
+        crateName = "kurbo";
+        version = "0.13.1";
+        edition = "2024";
+        sha256 = "1qjhhjgwr9admlz6r1s7kp38dl8nbd95abhryrnr4av55z1xyq2b";
+        dependencies = [
+          {
+            name = "arrayvec";
+            packageId = "arrayvec";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "euclid";
+            packageId = "euclid";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "polycool";
+            packageId = "polycool";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "smallvec";
+            packageId = "smallvec 1.15.1";
+            features = [ "const_new" ];
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "euclid" = [ "dep:euclid" ];
+          "libm" = [
+            "dep:libm"
+            "polycool/libm"
+            "euclid?/libm"
+          ];
+          "mint" = [ "dep:mint" ];
+          "schemars" = [
+            "schemars/smallvec"
+            "dep:schemars"
+          ];
+          "serde" = [
+            "smallvec/serde"
+            "dep:serde"
+          ];
+          "std" = [
+            "polycool/std"
+            "euclid?/std"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
       "lazy_static" = rec {
         crateName = "lazy_static";
         version = "1.5.0";
@@ -5316,6 +6443,29 @@ rec {
           "std"
         ];
       };
+      "libm" = rec {
+        crateName = "libm";
+        version = "0.2.16";
+        edition = "2021";
+        sha256 = "10brh0a3qjmbzkr5mf5xqi887nhs5y9layvnki89ykz9xb1wxlmn";
+        authors = [
+          "Alex Crichton <alex@alexcrichton.com>"
+          "Amanieu d'Antras <amanieu@gmail.com>"
+          "Jorge Aparicio <japaricious@gmail.com>"
+          "Trevor Gross <tg@trevorgross.com>"
+        ];
+        features = {
+          "default" = [ "arch" ];
+          "unstable" = [
+            "unstable-intrinsics"
+            "unstable-float"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "arch"
+          "default"
+        ];
+      };
       "linux-raw-sys" = rec {
         crateName = "linux-raw-sys";
         version = "0.9.4";
@@ -5632,7 +6782,7 @@ rec {
         ];
 
       };
-      "miniz_oxide" = rec {
+      "miniz_oxide 0.8.9" = rec {
         crateName = "miniz_oxide";
         version = "0.8.9";
         edition = "2021";
@@ -5648,6 +6798,12 @@ rec {
             packageId = "adler2";
             usesDefaultFeatures = false;
           }
+          {
+            name = "simd-adler32";
+            packageId = "simd-adler32";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
         ];
         features = {
           "alloc" = [ "dep:alloc" ];
@@ -5662,18 +6818,68 @@ rec {
           "simd" = [ "simd-adler32" ];
           "simd-adler32" = [ "dep:simd-adler32" ];
         };
+        resolvedDefaultFeatures = [
+          "default"
+          "simd"
+          "simd-adler32"
+          "with-alloc"
+        ];
       };
-      "mio" = rec {
-        crateName = "mio";
-        version = "1.0.4";
+      "miniz_oxide 0.9.1" = rec {
+        crateName = "miniz_oxide";
+        version = "0.9.1";
         edition = "2021";
-        sha256 = "073n3kam3nz8j8had35fd2nn7j6a33pi3y5w3kq608cari2d9gkq";
+        sha256 = "0k2bgjzk2sbsynpsv4wizwxbqp6vs7g08y5anbkrh3l6a15bqgxn";
         authors = [
-          "Carl Lerche <me@carllerche.com>"
-          "Thomas de Zeeuw <thomasdezeeuw@gmail.com>"
-          "Tokio Contributors <team@tokio.rs>"
-        ];
-        dependencies = [
+          "Frommi <daniil.liferenko@gmail.com>"
+          "oyvindln <oyvindln@users.noreply.github.com>"
+          "Rich Geldreich richgel99@gmail.com"
+        ];
+        dependencies = [
+          {
+            name = "adler2";
+            packageId = "adler2";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "simd-adler32";
+            packageId = "simd-adler32";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "alloc" = [ "dep:alloc" ];
+          "core" = [ "dep:core" ];
+          "default" = [ "with-alloc" ];
+          "rustc-dep-of-std" = [
+            "core"
+            "alloc"
+            "adler2/rustc-dep-of-std"
+          ];
+          "serde" = [ "dep:serde" ];
+          "simd" = [ "simd-adler32" ];
+          "simd-adler32" = [ "dep:simd-adler32" ];
+          "std" = [ "serde?/std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "simd"
+          "simd-adler32"
+          "with-alloc"
+        ];
+      };
+      "mio" = rec {
+        crateName = "mio";
+        version = "1.0.4";
+        edition = "2021";
+        sha256 = "073n3kam3nz8j8had35fd2nn7j6a33pi3y5w3kq608cari2d9gkq";
+        authors = [
+          "Carl Lerche <me@carllerche.com>"
+          "Thomas de Zeeuw <thomasdezeeuw@gmail.com>"
+          "Tokio Contributors <team@tokio.rs>"
+        ];
+        dependencies = [
           {
             name = "libc";
             packageId = "libc";
@@ -5774,7 +6980,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             usesDefaultFeatures = false;
             features = [
               "parsing"
@@ -5986,6 +7192,139 @@ rec {
           "serde" = [ "dep:serde" ];
         };
       };
+      "num" = rec {
+        crateName = "num";
+        version = "0.4.3";
+        edition = "2021";
+        sha256 = "08yb2fc1psig7pkzaplm495yp7c30m4pykpkwmi5bxrgid705g9m";
+        authors = [
+          "The Rust Project Developers"
+        ];
+        dependencies = [
+          {
+            name = "num-complex";
+            packageId = "num-complex";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "num-integer";
+            packageId = "num-integer";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+          {
+            name = "num-iter";
+            packageId = "num-iter";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+          {
+            name = "num-rational";
+            packageId = "num-rational";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+        ];
+        features = {
+          "alloc" = [
+            "dep:num-bigint"
+            "num-rational/num-bigint"
+          ];
+          "default" = [ "std" ];
+          "libm" = [
+            "num-complex/libm"
+            "num-traits/libm"
+          ];
+          "num-bigint" = [ "dep:num-bigint" ];
+          "rand" = [
+            "num-bigint/rand"
+            "num-complex/rand"
+          ];
+          "serde" = [
+            "num-bigint/serde"
+            "num-complex/serde"
+            "num-rational/serde"
+          ];
+          "std" = [
+            "dep:num-bigint"
+            "num-bigint/std"
+            "num-complex/std"
+            "num-integer/std"
+            "num-iter/std"
+            "num-rational/std"
+            "num-rational/num-bigint-std"
+            "num-traits/std"
+          ];
+        };
+      };
+      "num-bigint" = rec {
+        crateName = "num-bigint";
+        version = "0.4.8";
+        edition = "2021";
+        sha256 = "0ry3xjal8f5xhdinani268ci13h14mf7j4w0y1gflfzhw3knk7n8";
+        libName = "num_bigint";
+        authors = [
+          "The Rust Project Developers"
+        ];
+        dependencies = [
+          {
+            name = "num-integer";
+            packageId = "num-integer";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+        ];
+        features = {
+          "arbitrary" = [ "dep:arbitrary" ];
+          "default" = [ "std" ];
+          "quickcheck" = [ "dep:quickcheck" ];
+          "rand" = [ "dep:rand" ];
+          "serde" = [ "dep:serde" ];
+          "std" = [
+            "num-integer/std"
+            "num-traits/std"
+          ];
+        };
+      };
+      "num-complex" = rec {
+        crateName = "num-complex";
+        version = "0.4.6";
+        edition = "2021";
+        sha256 = "15cla16mnw12xzf5g041nxbjjm9m85hdgadd5dl5d0b30w9qmy3k";
+        libName = "num_complex";
+        authors = [
+          "The Rust Project Developers"
+        ];
+        dependencies = [
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+        ];
+        features = {
+          "bytecheck" = [ "dep:bytecheck" ];
+          "bytemuck" = [ "dep:bytemuck" ];
+          "default" = [ "std" ];
+          "libm" = [ "num-traits/libm" ];
+          "rand" = [ "dep:rand" ];
+          "rkyv" = [ "dep:rkyv" ];
+          "serde" = [ "dep:serde" ];
+          "std" = [ "num-traits/std" ];
+        };
+      };
       "num-conv" = rec {
         crateName = "num-conv";
         version = "0.1.0";
@@ -6054,6 +7393,99 @@ rec {
           "std"
         ];
       };
+      "num-integer" = rec {
+        crateName = "num-integer";
+        version = "0.1.47";
+        edition = "2018";
+        sha256 = "02z1p3azy6p10n99skrab4a6hhfd4amf2i9gm8sxqd1p9dfxkqkw";
+        libName = "num_integer";
+        authors = [
+          "The Rust Project Developers"
+        ];
+        dependencies = [
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "std" = [ "num-traits/std" ];
+        };
+        resolvedDefaultFeatures = [ "i128" ];
+      };
+      "num-iter" = rec {
+        crateName = "num-iter";
+        version = "0.1.46";
+        edition = "2018";
+        sha256 = "12q4x0lp9l6bvsak1p5q24lvfzl99ak9vzmwhqbwksm1d6yh0a69";
+        libName = "num_iter";
+        authors = [
+          "The Rust Project Developers"
+        ];
+        dependencies = [
+          {
+            name = "num-integer";
+            packageId = "num-integer";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "std" = [
+            "num-integer/std"
+            "num-traits/std"
+          ];
+        };
+        resolvedDefaultFeatures = [ "i128" ];
+      };
+      "num-rational" = rec {
+        crateName = "num-rational";
+        version = "0.4.2";
+        edition = "2021";
+        sha256 = "093qndy02817vpgcqjnj139im3jl7vkq4h68kykdqqh577d18ggq";
+        libName = "num_rational";
+        authors = [
+          "The Rust Project Developers"
+        ];
+        dependencies = [
+          {
+            name = "num-integer";
+            packageId = "num-integer";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+            features = [ "i128" ];
+          }
+        ];
+        features = {
+          "default" = [
+            "num-bigint"
+            "std"
+          ];
+          "num-bigint" = [ "dep:num-bigint" ];
+          "num-bigint-std" = [ "num-bigint/std" ];
+          "serde" = [ "dep:serde" ];
+          "std" = [
+            "num-bigint?/std"
+            "num-integer/std"
+            "num-traits/std"
+          ];
+        };
+      };
       "num-traits" = rec {
         crateName = "num-traits";
         version = "0.2.19";
@@ -6063,6 +7495,13 @@ rec {
         authors = [
           "The Rust Project Developers"
         ];
+        dependencies = [
+          {
+            name = "libm";
+            packageId = "libm";
+            optional = true;
+          }
+        ];
         buildDependencies = [
           {
             name = "autocfg";
@@ -6073,6 +7512,11 @@ rec {
           "default" = [ "std" ];
           "libm" = [ "dep:libm" ];
         };
+        resolvedDefaultFeatures = [
+          "i128"
+          "libm"
+          "std"
+        ];
       };
       "num_cpus" = rec {
         crateName = "num_cpus";
@@ -6330,7 +7774,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "full" ];
           }
         ];
@@ -6388,6 +7832,42 @@ rec {
           "vendored" = [ "openssl-src" ];
         };
       };
+      "ordered-float" = rec {
+        crateName = "ordered-float";
+        version = "2.10.1";
+        edition = "2018";
+        sha256 = "075i108hr95pr7hy4fgxivib5pky3b6b22rywya5qyd2wmkrvwb8";
+        libName = "ordered_float";
+        authors = [
+          "Jonathan Reem <jonathan.reem@gmail.com>"
+          "Matt Brubeck <mbrubeck@limpet.net>"
+        ];
+        dependencies = [
+          {
+            name = "num-traits";
+            packageId = "num-traits";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "arbitrary" = [ "dep:arbitrary" ];
+          "default" = [ "std" ];
+          "proptest" = [ "dep:proptest" ];
+          "rand" = [ "dep:rand" ];
+          "randtest" = [
+            "rand/std"
+            "rand/std_rng"
+          ];
+          "rkyv" = [ "dep:rkyv" ];
+          "schemars" = [ "dep:schemars" ];
+          "serde" = [ "dep:serde" ];
+          "std" = [ "num-traits/std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
       "overload" = rec {
         crateName = "overload";
         version = "0.1.1";
@@ -6540,6 +8020,176 @@ rec {
           "thread-id" = [ "dep:thread-id" ];
         };
       };
+      "parquet" = rec {
+        crateName = "parquet";
+        version = "54.3.1";
+        edition = "2021";
+        crateBin = [ ];
+        sha256 = "0kkmjz6847vgw1f4nsy34xdxffkq6fhkpqwrzllv8mkgmjb5gcdz";
+        authors = [
+          "Apache Arrow <dev@arrow.apache.org>"
+        ];
+        dependencies = [
+          {
+            name = "ahash";
+            packageId = "ahash";
+            usesDefaultFeatures = false;
+            target = { target, features }: (!("wasm32" == target."arch" or null));
+            features = [ "runtime-rng" ];
+          }
+          {
+            name = "ahash";
+            packageId = "ahash";
+            usesDefaultFeatures = false;
+            target = { target, features }: ("wasm32" == target."arch" or null);
+            features = [ "compile-time-rng" ];
+          }
+          {
+            name = "bytes";
+            packageId = "bytes";
+            usesDefaultFeatures = false;
+            features = [ "std" ];
+          }
+          {
+            name = "chrono";
+            packageId = "chrono";
+            usesDefaultFeatures = false;
+            features = [ "clock" ];
+          }
+          {
+            name = "half";
+            packageId = "half";
+            usesDefaultFeatures = false;
+            features = [ "num-traits" ];
+          }
+          {
+            name = "hashbrown";
+            packageId = "hashbrown 0.15.5";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "num";
+            packageId = "num";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "num-bigint";
+            packageId = "num-bigint";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "paste";
+            packageId = "paste";
+          }
+          {
+            name = "seq-macro";
+            packageId = "seq-macro";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "thrift";
+            packageId = "thrift";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "twox-hash";
+            packageId = "twox-hash 1.6.3";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "zstd";
+            packageId = "zstd 0.13.3";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+        ];
+        devDependencies = [
+          {
+            name = "zstd";
+            packageId = "zstd 0.13.3";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "arrow" = [
+            "base64"
+            "arrow-array"
+            "arrow-buffer"
+            "arrow-cast"
+            "arrow-data"
+            "arrow-schema"
+            "arrow-select"
+            "arrow-ipc"
+          ];
+          "arrow-array" = [ "dep:arrow-array" ];
+          "arrow-buffer" = [ "dep:arrow-buffer" ];
+          "arrow-cast" = [ "dep:arrow-cast" ];
+          "arrow-csv" = [ "dep:arrow-csv" ];
+          "arrow-data" = [ "dep:arrow-data" ];
+          "arrow-ipc" = [ "dep:arrow-ipc" ];
+          "arrow-schema" = [ "dep:arrow-schema" ];
+          "arrow-select" = [ "dep:arrow-select" ];
+          "arrow_canonical_extension_types" = [ "arrow-schema?/canonical_extension_types" ];
+          "async" = [
+            "futures"
+            "tokio"
+          ];
+          "base64" = [ "dep:base64" ];
+          "brotli" = [ "dep:brotli" ];
+          "clap" = [ "dep:clap" ];
+          "cli" = [
+            "json"
+            "base64"
+            "clap"
+            "arrow-csv"
+            "serde"
+          ];
+          "crc" = [ "dep:crc32fast" ];
+          "default" = [
+            "arrow"
+            "snap"
+            "brotli"
+            "flate2"
+            "lz4"
+            "zstd"
+            "base64"
+            "simdutf8"
+          ];
+          "encryption" = [ "dep:ring" ];
+          "flate2" = [ "dep:flate2" ];
+          "futures" = [ "dep:futures" ];
+          "json" = [
+            "serde_json"
+            "base64"
+          ];
+          "lz4" = [ "lz4_flex" ];
+          "lz4_flex" = [ "dep:lz4_flex" ];
+          "object_store" = [
+            "dep:object_store"
+            "async"
+          ];
+          "serde" = [ "dep:serde" ];
+          "serde_json" = [ "dep:serde_json" ];
+          "simdutf8" = [ "dep:simdutf8" ];
+          "snap" = [ "dep:snap" ];
+          "sysinfo" = [ "dep:sysinfo" ];
+          "test_common" = [ "arrow/test_utils" ];
+          "tokio" = [ "dep:tokio" ];
+          "zstd" = [ "dep:zstd" ];
+        };
+        resolvedDefaultFeatures = [ "zstd" ];
+      };
+      "paste" = rec {
+        crateName = "paste";
+        version = "1.0.15";
+        edition = "2018";
+        sha256 = "02pxffpdqkapy292harq6asfjvadgp1s005fip9ljfsn9fvxgh2p";
+        procMacro = true;
+        authors = [
+          "David Tolnay <dtolnay@gmail.com>"
+        ];
+
+      };
       "pear" = rec {
         crateName = "pear";
         version = "0.2.9";
@@ -6598,7 +8248,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "full"
               "extra-traits"
@@ -6627,6 +8277,22 @@ rec {
           "std"
         ];
       };
+      "pico-args" = rec {
+        crateName = "pico-args";
+        version = "0.5.0";
+        edition = "2018";
+        sha256 = "05d30pvxd6zlnkg2i3ilr5a70v3f3z2in18m67z25vinmykngqav";
+        libName = "pico_args";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+        features = {
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "eq-separator"
+        ];
+      };
       "pin-project-lite" = rec {
         crateName = "pin-project-lite";
         version = "0.2.16";
@@ -6657,6 +8323,61 @@ rec {
         ];
 
       };
+      "png" = rec {
+        crateName = "png";
+        version = "0.18.1";
+        edition = "2021";
+        sha256 = "0qca282xp8a6d7mikxrwji3f52mjn4vnqxz2v9iz5adj665rnxk0";
+        authors = [
+          "The image-rs Developers"
+        ];
+        dependencies = [
+          {
+            name = "bitflags";
+            packageId = "bitflags 2.9.3";
+          }
+          {
+            name = "crc32fast";
+            packageId = "crc32fast";
+          }
+          {
+            name = "fdeflate";
+            packageId = "fdeflate";
+          }
+          {
+            name = "flate2";
+            packageId = "flate2";
+          }
+          {
+            name = "miniz_oxide";
+            packageId = "miniz_oxide 0.8.9";
+            features = [ "simd" ];
+          }
+        ];
+        features = {
+          "unstable" = [ "crc32fast/nightly" ];
+          "zlib-rs" = [ "flate2/zlib-rs" ];
+        };
+      };
+      "polycool" = rec {
+        crateName = "polycool";
+        version = "0.4.0";
+        edition = "2024";
+        sha256 = "19m52bzlr779l2ci8vpqp9qxyrlfaq4h5m5cbkvxanpb17f6snah";
+        dependencies = [
+          {
+            name = "arrayvec";
+            packageId = "arrayvec";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "arbitrary" = [ "dep:arbitrary" ];
+          "default" = [ "std" ];
+          "libm" = [ "dep:libm" ];
+        };
+        resolvedDefaultFeatures = [ "std" ];
+      };
       "portable-atomic" = rec {
         crateName = "portable-atomic";
         version = "1.11.1";
@@ -6828,7 +8549,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
           }
           {
             name = "yansi";
@@ -6853,6 +8574,136 @@ rec {
           "yansi"
         ];
       };
+      "prometheus" = rec {
+        crateName = "prometheus";
+        version = "0.14.0";
+        edition = "2018";
+        sha256 = "0fpl98whrg5j4bpb3qfswii4yfa58zws7rl7rnd0m58bimnk599w";
+        authors = [
+          "overvenus@gmail.com"
+          "siddontang@gmail.com"
+          "vistaswx@gmail.com"
+        ];
+        dependencies = [
+          {
+            name = "cfg-if";
+            packageId = "cfg-if 1.0.3";
+          }
+          {
+            name = "fnv";
+            packageId = "fnv";
+          }
+          {
+            name = "lazy_static";
+            packageId = "lazy_static";
+          }
+          {
+            name = "memchr";
+            packageId = "memchr";
+          }
+          {
+            name = "parking_lot";
+            packageId = "parking_lot";
+          }
+          {
+            name = "thiserror";
+            packageId = "thiserror";
+          }
+        ];
+        features = {
+          "default" = [ "protobuf" ];
+          "gen" = [ "protobuf-codegen" ];
+          "libc" = [ "dep:libc" ];
+          "nightly" = [ "libc" ];
+          "process" = [
+            "libc"
+            "procfs"
+          ];
+          "procfs" = [ "dep:procfs" ];
+          "protobuf" = [ "dep:protobuf" ];
+          "protobuf-codegen" = [ "dep:protobuf-codegen" ];
+          "push" = [
+            "reqwest"
+            "libc"
+            "protobuf"
+          ];
+          "reqwest" = [ "dep:reqwest" ];
+        };
+      };
+      "prost" = rec {
+        crateName = "prost";
+        version = "0.14.4";
+        edition = "2021";
+        sha256 = "1qas5v5rap45f43v3ja0jngxrrafrkcwl0iw5a3ld1pz2rscd2jj";
+        authors = [
+          "Dan Burkert <dan@danburkert.com>"
+          "Lucio Franco <luciofranco14@gmail.com>"
+          "Casper Meijn <casper@meijn.net>"
+          "Tokio Contributors <team@tokio.rs>"
+        ];
+        dependencies = [
+          {
+            name = "bytes";
+            packageId = "bytes";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "prost-derive";
+            packageId = "prost-derive";
+            optional = true;
+          }
+        ];
+        features = {
+          "default" = [
+            "derive"
+            "std"
+          ];
+          "derive" = [ "dep:prost-derive" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "derive"
+          "std"
+        ];
+      };
+      "prost-derive" = rec {
+        crateName = "prost-derive";
+        version = "0.14.4";
+        edition = "2021";
+        sha256 = "1pqa77d7da5pf6ba3kjj7510m5cynz6902ax01ckvr0pfrgv4w5m";
+        procMacro = true;
+        libName = "prost_derive";
+        authors = [
+          "Dan Burkert <dan@danburkert.com>"
+          "Lucio Franco <luciofranco14@gmail.com>"
+          "Casper Meijn <casper@meijn.net>"
+          "Tokio Contributors <team@tokio.rs>"
+        ];
+        dependencies = [
+          {
+            name = "anyhow";
+            packageId = "anyhow";
+          }
+          {
+            name = "itertools";
+            packageId = "itertools";
+          }
+          {
+            name = "proc-macro2";
+            packageId = "proc-macro2";
+          }
+          {
+            name = "quote";
+            packageId = "quote";
+          }
+          {
+            name = "syn";
+            packageId = "syn 2.0.106";
+            features = [ "extra-traits" ];
+          }
+        ];
+
+      };
       "quote" = rec {
         crateName = "quote";
         version = "1.0.40";
@@ -7608,7 +9459,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
           }
         ];
 
@@ -8453,6 +10304,98 @@ rec {
           "system-proxy"
         ];
       };
+      "resvg" = rec {
+        crateName = "resvg";
+        version = "0.47.0";
+        edition = "2024";
+        crateBin = [ ];
+        sha256 = "10cfzm1ldb2vmwcrsdfjdzmb7f4814xh7j746dpsjsi1danq7qcv";
+        dependencies = [
+          {
+            name = "log";
+            packageId = "log";
+          }
+          {
+            name = "pico-args";
+            packageId = "pico-args";
+            features = [ "eq-separator" ];
+          }
+          {
+            name = "rgb";
+            packageId = "rgb";
+          }
+          {
+            name = "svgtypes";
+            packageId = "svgtypes";
+          }
+          {
+            name = "tiny-skia";
+            packageId = "tiny-skia";
+          }
+          {
+            name = "usvg";
+            packageId = "usvg";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "default" = [
+            "text"
+            "system-fonts"
+            "memmap-fonts"
+            "raster-images"
+          ];
+          "gif" = [ "dep:gif" ];
+          "image-webp" = [ "dep:image-webp" ];
+          "memmap-fonts" = [ "usvg/memmap-fonts" ];
+          "raster-images" = [
+            "gif"
+            "image-webp"
+            "dep:zune-jpeg"
+          ];
+          "system-fonts" = [ "usvg/system-fonts" ];
+          "text" = [ "usvg/text" ];
+        };
+        resolvedDefaultFeatures = [
+          "system-fonts"
+          "text"
+        ];
+      };
+      "rgb" = rec {
+        crateName = "rgb";
+        version = "0.8.53";
+        edition = "2021";
+        sha256 = "1i0c55whln68zs6f5qqrkbg1mzai0p3qk1mwkwzdgr9i3dw4pcs7";
+        authors = [
+          "Kornel Lesiński <kornel@geekhood.net>"
+          "James Forster <james.forsterer@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "bytemuck";
+            packageId = "bytemuck";
+            optional = true;
+          }
+        ];
+        features = {
+          "as-bytes" = [ "bytemuck" ];
+          "bytemuck" = [ "dep:bytemuck" ];
+          "default" = [
+            "as-bytes"
+            "argb"
+            "grb"
+          ];
+          "defmt-03" = [ "dep:defmt" ];
+          "serde" = [ "dep:serde" ];
+        };
+        resolvedDefaultFeatures = [
+          "argb"
+          "as-bytes"
+          "bytemuck"
+          "default"
+          "grb"
+        ];
+      };
       "ring" = rec {
         crateName = "ring";
         version = "0.17.14";
@@ -8787,7 +10730,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "full"
               "visit"
@@ -9065,6 +11008,54 @@ rec {
         ];
 
       };
+      "roxmltree 0.20.0" = rec {
+        crateName = "roxmltree";
+        version = "0.20.0";
+        edition = "2021";
+        sha256 = "15vw91ps91wkmmgy62khf9zb63bdinvm80957dascbsw7dwvc83c";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+        features = {
+          "default" = [
+            "std"
+            "positions"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "positions"
+          "std"
+        ];
+      };
+      "roxmltree 0.21.1" = rec {
+        crateName = "roxmltree";
+        version = "0.21.1";
+        edition = "2021";
+        sha256 = "1fxc3jgvl2rk05bw0hj86azqg6mzlijh06gyi9pw69b1qw84p5pi";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "memchr";
+            packageId = "memchr";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "default" = [
+            "std"
+            "positions"
+          ];
+          "std" = [ "memchr/std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "positions"
+          "std"
+        ];
+      };
       "rustc-demangle" = rec {
         crateName = "rustc-demangle";
         version = "0.1.26";
@@ -9609,6 +11600,83 @@ rec {
         ];
 
       };
+      "rustybuzz" = rec {
+        crateName = "rustybuzz";
+        version = "0.20.1";
+        edition = "2021";
+        sha256 = "00hp1gwykjfli258zs7lqg8p2zdh94dv2mw8zx7f73m0z2b7qg7x";
+        authors = [
+          "Caleb Maclennan <caleb@alerque.com>"
+          "Laurenz Stampfl <laurenz.stampfl@gmail.com>"
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+          "خالد حسني (Khaled Hosny) <khaled@aliftype.com>"
+        ];
+        dependencies = [
+          {
+            name = "bitflags";
+            packageId = "bitflags 2.9.3";
+          }
+          {
+            name = "bytemuck";
+            packageId = "bytemuck";
+            features = [ "extern_crate_alloc" ];
+          }
+          {
+            name = "core_maths";
+            packageId = "core_maths";
+          }
+          {
+            name = "log";
+            packageId = "log";
+          }
+          {
+            name = "smallvec";
+            packageId = "smallvec 1.15.1";
+          }
+          {
+            name = "ttf-parser";
+            packageId = "ttf-parser";
+            usesDefaultFeatures = false;
+            features = [
+              "opentype-layout"
+              "apple-layout"
+              "variable-fonts"
+              "glyph-names"
+              "no-std-float"
+            ];
+          }
+          {
+            name = "unicode-bidi-mirroring";
+            packageId = "unicode-bidi-mirroring";
+          }
+          {
+            name = "unicode-ccc";
+            packageId = "unicode-ccc";
+          }
+          {
+            name = "unicode-properties";
+            packageId = "unicode-properties";
+            usesDefaultFeatures = false;
+            features = [ "general-category" ];
+          }
+          {
+            name = "unicode-script";
+            packageId = "unicode-script";
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "std" = [ "ttf-parser/std" ];
+          "wasm-shaper" = [
+            "std"
+            "dep:wasmi"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
       "ryu" = rec {
         crateName = "ryu";
         version = "1.0.20";
@@ -9963,22 +12031,68 @@ rec {
           "Steve Klabnik <steve@steveklabnik.com>"
         ];
 
+      };
+      "seq-macro" = rec {
+        crateName = "seq-macro";
+        version = "0.3.6";
+        edition = "2018";
+        sha256 = "1k4sshn0x2i6a9g97sy5jl7ghlqgmmh3n76aj3rrjwxy1x0i3iqv";
+        procMacro = true;
+        libName = "seq_macro";
+        authors = [
+          "David Tolnay <dtolnay@gmail.com>"
+        ];
+
       };
       "serde" = rec {
         crateName = "serde";
-        version = "1.0.219";
-        edition = "2018";
-        sha256 = "1dl6nyxnsi82a197sd752128a4avm6mxnscywas1jq30srp2q3jz";
+        version = "1.0.229";
+        edition = "2021";
+        sha256 = "1fp04fq4a79bpm61xz1zy0pbz4kpc7d771zii1k3inmszq55jj21";
         authors = [
           "Erick Tryzelaar <erick.tryzelaar@gmail.com>"
           "David Tolnay <dtolnay@gmail.com>"
         ];
         dependencies = [
+          {
+            name = "serde_core";
+            packageId = "serde_core";
+            usesDefaultFeatures = false;
+            features = [ "result" ];
+          }
           {
             name = "serde_derive";
             packageId = "serde_derive";
             optional = true;
           }
+        ];
+        features = {
+          "alloc" = [ "serde_core/alloc" ];
+          "default" = [ "std" ];
+          "derive" = [ "serde_derive" ];
+          "rc" = [ "serde_core/rc" ];
+          "serde_derive" = [ "dep:serde_derive" ];
+          "std" = [ "serde_core/std" ];
+          "unstable" = [ "serde_core/unstable" ];
+        };
+        resolvedDefaultFeatures = [
+          "alloc"
+          "default"
+          "derive"
+          "serde_derive"
+          "std"
+        ];
+      };
+      "serde_core" = rec {
+        crateName = "serde_core";
+        version = "1.0.229";
+        edition = "2021";
+        sha256 = "0j1ajiha76h3nmd976il9li6975k121xa7jb39ws8n0yqp4s5p37";
+        authors = [
+          "Erick Tryzelaar <erick.tryzelaar@gmail.com>"
+          "David Tolnay <dtolnay@gmail.com>"
+        ];
+        dependencies = [
           {
             name = "serde_derive";
             packageId = "serde_derive";
@@ -9992,23 +12106,23 @@ rec {
           }
         ];
         features = {
-          "default" = [ "std" ];
-          "derive" = [ "serde_derive" ];
-          "serde_derive" = [ "dep:serde_derive" ];
+          "default" = [
+            "std"
+            "result"
+          ];
         };
         resolvedDefaultFeatures = [
           "alloc"
           "default"
-          "derive"
-          "serde_derive"
+          "result"
           "std"
         ];
       };
       "serde_derive" = rec {
         crateName = "serde_derive";
-        version = "1.0.219";
-        edition = "2015";
-        sha256 = "001azhjmj7ya52pmfiw4ppxm16nd44y15j2pf5gkcwrcgz7pc0jv";
+        version = "1.0.229";
+        edition = "2021";
+        sha256 = "0j4k63i7h1bikxwz2c89ig0hrwbnl9mz1czn85xx99x5cc9dg9g7";
         procMacro = true;
         authors = [
           "Erick Tryzelaar <erick.tryzelaar@gmail.com>"
@@ -10029,7 +12143,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 3.0.9";
             usesDefaultFeatures = false;
             features = [
               "clone-impls"
@@ -10461,7 +12575,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "extra-traits"
               "full"
@@ -10578,6 +12692,27 @@ rec {
         ];
 
       };
+      "simd-adler32" = rec {
+        crateName = "simd-adler32";
+        version = "0.3.10";
+        edition = "2018";
+        sha256 = "1sny4y2qa5mwyxx5x59ln2p02vsdh92004njlslnx98imjc9489s";
+        libName = "simd_adler32";
+        authors = [
+          "Marvin Countryman <me@maar.vin>"
+        ];
+        features = {
+          "default" = [
+            "std"
+            "const-generics"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "const-generics"
+          "default"
+          "std"
+        ];
+      };
       "simple_logger" = rec {
         crateName = "simple_logger";
         version = "5.0.0";
@@ -10635,6 +12770,50 @@ rec {
           "timestamps"
         ];
       };
+      "simplecss" = rec {
+        crateName = "simplecss";
+        version = "0.2.2";
+        edition = "2021";
+        sha256 = "0v0kid7b2602kcka2x2xs9wwfjf8lnvpgpl8x287qg4wra1ni73s";
+        dependencies = [
+          {
+            name = "log";
+            packageId = "log";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "std" = [ "log/std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
+      "siphasher" = rec {
+        crateName = "siphasher";
+        version = "1.0.4";
+        edition = "2018";
+        sha256 = "0mn28y43123jdpskdn6r9wibmn066f7h30zkkqn88bd6hj8zxx1k";
+        authors = [
+          "Frank Denis <github@pureftpd.org>"
+        ];
+        features = {
+          "default" = [ "std" ];
+          "serde" = [ "dep:serde" ];
+          "serde_json" = [ "dep:serde_json" ];
+          "serde_no_std" = [ "serde/alloc" ];
+          "serde_std" = [
+            "std"
+            "serde/std"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
       "slab" = rec {
         crateName = "slab";
         version = "0.4.11";
@@ -10652,6 +12831,25 @@ rec {
           "std"
         ];
       };
+      "slotmap" = rec {
+        crateName = "slotmap";
+        version = "1.1.1";
+        edition = "2018";
+        sha256 = "0f20xf53zaysx9ydzkwwqm6hsjyb8lj2j6amhg57iln3jcy8rmdx";
+        authors = [
+          "Orson Peters <orsonpeters@gmail.com>"
+        ];
+        buildDependencies = [
+          {
+            name = "version_check";
+            packageId = "version_check";
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "serde" = [ "dep:serde" ];
+        };
+      };
       "smallvec 0.6.14" = rec {
         crateName = "smallvec";
         version = "0.6.14";
@@ -10863,6 +13061,46 @@ rec {
         features = {
         };
       };
+      "static_assertions" = rec {
+        crateName = "static_assertions";
+        version = "1.1.0";
+        edition = "2015";
+        sha256 = "0gsl6xmw10gvn3zs1rv99laj5ig7ylffnh71f9l34js4nr4r7sx2";
+        authors = [
+          "Nikolai Vazquez"
+        ];
+        features = {
+        };
+      };
+      "strict-num" = rec {
+        crateName = "strict-num";
+        version = "0.1.1";
+        edition = "2018";
+        sha256 = "0cb7l1vhb8zj90mzm8avlk815k40sql9515s865rqdrdfavvldv6";
+        libName = "strict_num";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "float-cmp";
+            packageId = "float-cmp";
+            optional = true;
+            usesDefaultFeatures = false;
+            features = [ "std" ];
+          }
+        ];
+        features = {
+          "approx-eq" = [ "float-cmp" ];
+          "default" = [ "approx-eq" ];
+          "float-cmp" = [ "dep:float-cmp" ];
+        };
+        resolvedDefaultFeatures = [
+          "approx-eq"
+          "default"
+          "float-cmp"
+        ];
+      };
       "strsim" = rec {
         crateName = "strsim";
         version = "0.11.1";
@@ -10890,7 +13128,33 @@ rec {
           ];
         };
       };
-      "syn" = rec {
+      "svgtypes" = rec {
+        crateName = "svgtypes";
+        version = "0.16.1";
+        edition = "2024";
+        sha256 = "0gdw5a7znyv5sh4a575ddlhkvnvf45dd5grvkflsy78knf85fnv9";
+        dependencies = [
+          {
+            name = "kurbo";
+            packageId = "kurbo";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "siphasher";
+            packageId = "siphasher";
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "libm" = [ "kurbo/libm" ];
+          "std" = [ "kurbo/std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
+      "syn 2.0.106" = rec {
         crateName = "syn";
         version = "2.0.106";
         edition = "2021";
@@ -10944,6 +13208,54 @@ rec {
           "visit-mut"
         ];
       };
+      "syn 3.0.9" = rec {
+        crateName = "syn";
+        version = "3.0.9";
+        edition = "2021";
+        sha256 = "0fw28lhl90kls24q2h2sp39yjb0lsvz5cwh9fd3f3w3v9kp8v36p";
+        authors = [
+          "David Tolnay <dtolnay@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "proc-macro2";
+            packageId = "proc-macro2";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "quote";
+            packageId = "quote";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "unicode-ident";
+            packageId = "unicode-ident";
+          }
+        ];
+        features = {
+          "default" = [
+            "derive"
+            "parsing"
+            "printing"
+            "clone-impls"
+            "proc-macro"
+          ];
+          "printing" = [ "dep:quote" ];
+          "proc-macro" = [
+            "proc-macro2/proc-macro"
+            "quote?/proc-macro"
+          ];
+          "test" = [ "syn-test-suite/all-features" ];
+        };
+        resolvedDefaultFeatures = [
+          "clone-impls"
+          "derive"
+          "parsing"
+          "printing"
+          "proc-macro"
+        ];
+      };
       "sync_wrapper" = rec {
         crateName = "sync_wrapper";
         version = "1.0.2";
@@ -10990,7 +13302,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             usesDefaultFeatures = false;
             features = [
               "derive"
@@ -11198,7 +13510,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
           }
         ];
 
@@ -11238,6 +13550,38 @@ rec {
         ];
 
       };
+      "thrift" = rec {
+        crateName = "thrift";
+        version = "0.17.0";
+        edition = "2018";
+        sha256 = "02cydaqqlp25ri19y3ixi77a7nd85fwvbfn4fp0qpakzzj2vqm3y";
+        authors = [
+          "Apache Thrift Developers <dev@thrift.apache.org>"
+        ];
+        dependencies = [
+          {
+            name = "byteorder";
+            packageId = "byteorder";
+          }
+          {
+            name = "integer-encoding";
+            packageId = "integer-encoding";
+          }
+          {
+            name = "ordered-float";
+            packageId = "ordered-float";
+          }
+        ];
+        features = {
+          "default" = [ "server" ];
+          "log" = [ "dep:log" ];
+          "server" = [
+            "threadpool"
+            "log"
+          ];
+          "threadpool" = [ "dep:threadpool" ];
+        };
+      };
       "time" = rec {
         crateName = "time";
         version = "0.3.41";
@@ -11411,6 +13755,138 @@ rec {
           "serde"
         ];
       };
+      "tiny-keccak" = rec {
+        crateName = "tiny-keccak";
+        version = "2.0.2";
+        edition = "2018";
+        sha256 = "0dq2x0hjffmixgyf6xv9wgsbcxkd65ld0wrfqmagji8a829kg79c";
+        libName = "tiny_keccak";
+        authors = [
+          "debris <marek.kotewicz@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "crunchy";
+            packageId = "crunchy";
+          }
+        ];
+        features = {
+          "fips202" = [
+            "keccak"
+            "shake"
+            "sha3"
+          ];
+          "kmac" = [ "cshake" ];
+          "parallel_hash" = [ "cshake" ];
+          "sp800" = [
+            "cshake"
+            "kmac"
+            "tuple_hash"
+          ];
+          "tuple_hash" = [ "cshake" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "shake"
+        ];
+      };
+      "tiny-skia" = rec {
+        crateName = "tiny-skia";
+        version = "0.12.0";
+        edition = "2021";
+        sha256 = "1sliba6ghl038ig7r4wds7aq9v5x1swmdqxh1xipylpmm9gfxzs7";
+        libName = "tiny_skia";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "arrayref";
+            packageId = "arrayref";
+          }
+          {
+            name = "arrayvec";
+            packageId = "arrayvec";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "bytemuck";
+            packageId = "bytemuck";
+            features = [ "aarch64_simd" ];
+          }
+          {
+            name = "cfg-if";
+            packageId = "cfg-if 1.0.3";
+          }
+          {
+            name = "log";
+            packageId = "log";
+          }
+          {
+            name = "png";
+            packageId = "png";
+            optional = true;
+          }
+          {
+            name = "tiny-skia-path";
+            packageId = "tiny-skia-path";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "default" = [
+            "std"
+            "simd"
+            "png-format"
+          ];
+          "no-std-float" = [ "tiny-skia-path/no-std-float" ];
+          "png-format" = [
+            "std"
+            "dep:png"
+          ];
+          "std" = [ "tiny-skia-path/std" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "png-format"
+          "simd"
+          "std"
+        ];
+      };
+      "tiny-skia-path" = rec {
+        crateName = "tiny-skia-path";
+        version = "0.12.0";
+        edition = "2021";
+        sha256 = "1fgfzl0mzf2vfcqk3817wxynhmvcza05jg2r0rysdk5c7xf3djpd";
+        libName = "tiny_skia_path";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "arrayref";
+            packageId = "arrayref";
+          }
+          {
+            name = "bytemuck";
+            packageId = "bytemuck";
+          }
+          {
+            name = "strict-num";
+            packageId = "strict-num";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "libm" = [ "dep:libm" ];
+          "no-std-float" = [ "libm" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "std"
+        ];
+      };
       "tinystr" = rec {
         crateName = "tinystr";
         version = "0.8.1";
@@ -11444,6 +13920,35 @@ rec {
           "zerovec"
         ];
       };
+      "tinyvec" = rec {
+        crateName = "tinyvec";
+        version = "1.13.3";
+        edition = "2018";
+        sha256 = "1vphg4gnlpykjy5h4v7r8nl38ij4zr9qyh7qd34ddvwjyqaa6g7x";
+        authors = [
+          "Lokathor <zefria@gmail.com>"
+        ];
+        features = {
+          "arbitrary" = [ "dep:arbitrary" ];
+          "bin-proto" = [ "dep:bin-proto" ];
+          "borsh" = [ "dep:borsh" ];
+          "defmt" = [ "dep:defmt" ];
+          "generic-array" = [ "dep:generic-array" ];
+          "latest_stable_rust" = [ "rustc_1_61" ];
+          "real_blackbox" = [ "criterion/real_blackbox" ];
+          "rustc_1_61" = [ "rustc_1_57" ];
+          "schemars" = [
+            "dep:schemars"
+            "alloc"
+          ];
+          "serde" = [ "dep:serde_core" ];
+          "std" = [ "alloc" ];
+        };
+        resolvedDefaultFeatures = [
+          "alloc"
+          "default"
+        ];
+      };
       "tokio" = rec {
         crateName = "tokio";
         version = "1.47.1";
@@ -11662,7 +14167,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "full" ];
           }
         ];
@@ -12752,7 +15257,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             usesDefaultFeatures = false;
             features = [
               "full"
@@ -13031,6 +15536,49 @@ rec {
         ];
 
       };
+      "ttf-parser" = rec {
+        crateName = "ttf-parser";
+        version = "0.25.1";
+        edition = "2018";
+        sha256 = "0cbgqglcwwjg3hirwq6xlza54w04mb5x02kf7zx4hrw50xmr1pyj";
+        libName = "ttf_parser";
+        authors = [
+          "Caleb Maclennan <caleb@alerque.com>"
+          "Laurenz Stampfl <laurenz.stampfl@gmail.com>"
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+          "خالد حسني (Khaled Hosny) <khaled@aliftype.com>"
+        ];
+        dependencies = [
+          {
+            name = "core_maths";
+            packageId = "core_maths";
+            optional = true;
+          }
+        ];
+        features = {
+          "core_maths" = [ "dep:core_maths" ];
+          "default" = [
+            "std"
+            "opentype-layout"
+            "apple-layout"
+            "variable-fonts"
+            "glyph-names"
+          ];
+          "gvar-alloc" = [ "std" ];
+          "no-std-float" = [ "core_maths" ];
+        };
+        resolvedDefaultFeatures = [
+          "apple-layout"
+          "core_maths"
+          "default"
+          "glyph-names"
+          "gvar-alloc"
+          "no-std-float"
+          "opentype-layout"
+          "std"
+          "variable-fonts"
+        ];
+      };
       "tungstenite" = rec {
         crateName = "tungstenite";
         version = "0.27.0";
@@ -13133,6 +15681,63 @@ rec {
           "sha1"
         ];
       };
+      "twox-hash 1.6.3" = rec {
+        crateName = "twox-hash";
+        version = "1.6.3";
+        edition = "2018";
+        crateBin = [ ];
+        sha256 = "0xgn72j36a270l5ls1jk88n7bmq2dhlfkbhdh5554hbagjsydzlp";
+        libName = "twox_hash";
+        authors = [
+          "Jake Goulding <jake.goulding@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "cfg-if";
+            packageId = "cfg-if 1.0.3";
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "static_assertions";
+            packageId = "static_assertions";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "default" = [ "std" ];
+          "digest" = [ "dep:digest" ];
+          "digest_0_10" = [ "dep:digest_0_10" ];
+          "digest_0_9" = [ "dep:digest_0_9" ];
+          "rand" = [ "dep:rand" ];
+          "serde" = [ "dep:serde" ];
+          "serialize" = [ "serde" ];
+          "std" = [ "rand" ];
+        };
+      };
+      "twox-hash 2.1.5" = rec {
+        crateName = "twox-hash";
+        version = "2.1.5";
+        edition = "2021";
+        sha256 = "1hj1g6nz5dxv00pxdh7mwh2mx7m259k111wc8rns0hm3rsrh3a46";
+        libName = "twox_hash";
+        authors = [
+          "Jake Goulding <jake.goulding@gmail.com>"
+        ];
+        features = {
+          "default" = [
+            "random"
+            "xxhash32"
+            "xxhash64"
+            "xxhash3_64"
+            "xxhash3_128"
+            "std"
+          ];
+          "random" = [ "dep:rand" ];
+          "serialize" = [ "dep:serde" ];
+          "std" = [ "alloc" ];
+        };
+        resolvedDefaultFeatures = [ "xxhash3_64" ];
+      };
       "typenum" = rec {
         crateName = "typenum";
         version = "1.18.0";
@@ -13210,6 +15815,58 @@ rec {
           "with-serde-alloc"
         ];
       };
+      "unicode-bidi" = rec {
+        crateName = "unicode-bidi";
+        version = "0.3.18";
+        edition = "2018";
+        sha256 = "1xcxwbsqa24b8vfchhzyyzgj0l6bn51ib5v8j6krha0m77dva72w";
+        libName = "unicode_bidi";
+        authors = [
+          "The Servo Project Developers"
+        ];
+        features = {
+          "default" = [
+            "std"
+            "hardcoded-data"
+          ];
+          "flame" = [ "dep:flame" ];
+          "flame_it" = [
+            "flame"
+            "flamer"
+          ];
+          "flamer" = [ "dep:flamer" ];
+          "serde" = [ "dep:serde" ];
+          "smallvec" = [ "dep:smallvec" ];
+          "with_serde" = [ "serde" ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "hardcoded-data"
+          "std"
+        ];
+      };
+      "unicode-bidi-mirroring" = rec {
+        crateName = "unicode-bidi-mirroring";
+        version = "0.4.0";
+        edition = "2018";
+        sha256 = "1zirs1z3ahlwy7swg7apnm3pc6vix1g15q0kn6fx8rmvc266xyjx";
+        libName = "unicode_bidi_mirroring";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+
+      };
+      "unicode-ccc" = rec {
+        crateName = "unicode-ccc";
+        version = "0.4.0";
+        edition = "2018";
+        sha256 = "0gjhxwx27ywm3rcbb0m5q20w8zxi51440b3ps6swi6ywpj4d8qff";
+        libName = "unicode_ccc";
+        authors = [
+          "Yevhenii Reizner <razrfalcon@gmail.com>"
+        ];
+
+      };
       "unicode-ident" = rec {
         crateName = "unicode-ident";
         version = "1.0.18";
@@ -13220,6 +15877,53 @@ rec {
           "David Tolnay <dtolnay@gmail.com>"
         ];
 
+      };
+      "unicode-properties" = rec {
+        crateName = "unicode-properties";
+        version = "0.1.4";
+        edition = "2021";
+        sha256 = "07fpm3sqq7lm9gmgpxa93z31q933h3c3ypfwy4cdh6l42g3miw3x";
+        libName = "unicode_properties";
+        authors = [
+          "Charles Lew <crlf0710@gmail.com>"
+          "Manish Goregaokar <manishsmail@gmail.com>"
+        ];
+        features = {
+          "default" = [
+            "general-category"
+            "emoji"
+          ];
+        };
+        resolvedDefaultFeatures = [ "general-category" ];
+      };
+      "unicode-script" = rec {
+        crateName = "unicode-script";
+        version = "0.5.8";
+        edition = "2018";
+        sha256 = "1vmifpgd0map3frmvhszhl96k82crcry083prv05wii7p45x8fiq";
+        libName = "unicode_script";
+        authors = [
+          "Manish Goregaokar <manishsmail@gmail.com>"
+        ];
+        features = {
+          "core" = [ "dep:core" ];
+          "rustc-dep-of-std" = [
+            "std"
+            "core"
+          ];
+          "std" = [ "dep:std" ];
+        };
+      };
+      "unicode-vo" = rec {
+        crateName = "unicode-vo";
+        version = "0.1.0";
+        edition = "2015";
+        sha256 = "151sha088v9jyfvbg5164xh4dk72g53b82xm4zzbf5dlagzqdlxi";
+        libName = "unicode_vo";
+        authors = [
+          "Evgeniy Reizner <razrfalcon@gmail.com>"
+        ];
+
       };
       "unicode-width" = rec {
         crateName = "unicode-width";
@@ -13360,6 +16064,142 @@ rec {
         ];
 
       };
+      "usvg" = rec {
+        crateName = "usvg";
+        version = "0.47.0";
+        edition = "2024";
+        crateBin = [ ];
+        sha256 = "1ph7khq3vp5h94i1pml964qbj2sw0ykwcfv9m6vkd3a9bxngjv6l";
+        dependencies = [
+          {
+            name = "base64";
+            packageId = "base64";
+          }
+          {
+            name = "data-url";
+            packageId = "data-url";
+          }
+          {
+            name = "flate2";
+            packageId = "flate2";
+            usesDefaultFeatures = false;
+            features = [ "rust_backend" ];
+          }
+          {
+            name = "fontdb";
+            packageId = "fontdb";
+            optional = true;
+            usesDefaultFeatures = false;
+          }
+          {
+            name = "imagesize";
+            packageId = "imagesize";
+          }
+          {
+            name = "kurbo";
+            packageId = "kurbo";
+          }
+          {
+            name = "log";
+            packageId = "log";
+          }
+          {
+            name = "pico-args";
+            packageId = "pico-args";
+            features = [ "eq-separator" ];
+          }
+          {
+            name = "roxmltree";
+            packageId = "roxmltree 0.21.1";
+          }
+          {
+            name = "rustybuzz";
+            packageId = "rustybuzz";
+            optional = true;
+          }
+          {
+            name = "simplecss";
+            packageId = "simplecss";
+          }
+          {
+            name = "siphasher";
+            packageId = "siphasher";
+          }
+          {
+            name = "strict-num";
+            packageId = "strict-num";
+          }
+          {
+            name = "svgtypes";
+            packageId = "svgtypes";
+          }
+          {
+            name = "tiny-skia-path";
+            packageId = "tiny-skia-path";
+          }
+          {
+            name = "ttf-parser";
+            packageId = "ttf-parser";
+            optional = true;
+            features = [ "gvar-alloc" ];
+          }
+          {
+            name = "unicode-bidi";
+            packageId = "unicode-bidi";
+            optional = true;
+          }
+          {
+            name = "unicode-script";
+            packageId = "unicode-script";
+            optional = true;
+          }
+          {
+            name = "unicode-vo";
+            packageId = "unicode-vo";
+            optional = true;
+          }
+          {
+            name = "xmlwriter";
+            packageId = "xmlwriter";
+          }
+        ];
+        features = {
+          "default" = [
+            "text"
+            "system-fonts"
+            "memmap-fonts"
+          ];
+          "fontdb" = [ "dep:fontdb" ];
+          "memmap-fonts" = [ "fontdb/memmap" ];
+          "rustybuzz" = [ "dep:rustybuzz" ];
+          "system-fonts" = [
+            "fontdb/fs"
+            "fontdb/fontconfig"
+          ];
+          "text" = [
+            "fontdb"
+            "rustybuzz"
+            "ttf-parser"
+            "unicode-bidi"
+            "unicode-script"
+            "unicode-vo"
+          ];
+          "ttf-parser" = [ "dep:ttf-parser" ];
+          "unicode-bidi" = [ "dep:unicode-bidi" ];
+          "unicode-script" = [ "dep:unicode-script" ];
+          "unicode-vo" = [ "dep:unicode-vo" ];
+        };
+        resolvedDefaultFeatures = [
+          "fontdb"
+          "rustybuzz"
+          "system-fonts"
+          "text"
+          "ttf-parser"
+          "unicode-bidi"
+          "unicode-script"
+          "unicode-vo"
+        ];
+      };
       "utf-8" = rec {
         crateName = "utf-8";
         version = "0.7.6";
@@ -13394,6 +16234,130 @@ rec {
         };
         resolvedDefaultFeatures = [ "default" ];
       };
+      "utoipa" = rec {
+        crateName = "utoipa";
+        version = "5.5.0";
+        edition = "2021";
+        sha256 = "0q01idama7ndvhknm9fsi24ibbbh0zl1d5kbs73ic2z8d3gibplb";
+        authors = [
+          "Juha Kukkonen <juha7kukkonen@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "indexmap";
+            packageId = "indexmap 2.11.0";
+            features = [ "serde" ];
+          }
+          {
+            name = "serde";
+            packageId = "serde";
+            features = [ "derive" ];
+          }
+          {
+            name = "serde_json";
+            packageId = "serde_json";
+          }
+          {
+            name = "utoipa-gen";
+            packageId = "utoipa-gen";
+            optional = true;
+          }
+        ];
+        features = {
+          "actix_extras" = [ "utoipa-gen?/actix_extras" ];
+          "auto_into_responses" = [ "utoipa-gen?/auto_into_responses" ];
+          "axum_extras" = [ "utoipa-gen?/axum_extras" ];
+          "chrono" = [ "utoipa-gen?/chrono" ];
+          "config" = [ "utoipa-gen?/config" ];
+          "debug" = [ "utoipa-gen?/debug" ];
+          "decimal" = [ "utoipa-gen?/decimal" ];
+          "decimal_float" = [ "utoipa-gen?/decimal_float" ];
+          "default" = [ "macros" ];
+          "indexmap" = [ "utoipa-gen?/indexmap" ];
+          "jiff_0_2" = [ "utoipa-gen?/jiff_0_2" ];
+          "macros" = [ "dep:utoipa-gen" ];
+          "non_strict_integers" = [ "utoipa-gen?/non_strict_integers" ];
+          "rc_schema" = [ "utoipa-gen?/rc_schema" ];
+          "repr" = [ "utoipa-gen?/repr" ];
+          "rocket_extras" = [ "utoipa-gen?/rocket_extras" ];
+          "serde_norway" = [ "dep:serde_norway" ];
+          "smallvec" = [ "utoipa-gen?/smallvec" ];
+          "time" = [ "utoipa-gen?/time" ];
+          "ulid" = [ "utoipa-gen?/ulid" ];
+          "url" = [ "utoipa-gen?/url" ];
+          "uuid" = [ "utoipa-gen?/uuid" ];
+          "yaml" = [
+            "serde_norway"
+            "utoipa-gen?/yaml"
+          ];
+        };
+        resolvedDefaultFeatures = [
+          "default"
+          "macros"
+          "rocket_extras"
+        ];
+      };
+      "utoipa-gen" = rec {
+        crateName = "utoipa-gen";
+        version = "5.5.0";
+        edition = "2021";
+        sha256 = "1y1fnnamhi9j21swzcvvi8gscb8hjz3l0f6r6n305wrdwngbk83b";
+        procMacro = true;
+        libName = "utoipa_gen";
+        authors = [
+          "Juha Kukkonen <juha7kukkonen@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "proc-macro2";
+            packageId = "proc-macro2";
+          }
+          {
+            name = "quote";
+            packageId = "quote";
+          }
+          {
+            name = "regex";
+            packageId = "regex";
+            optional = true;
+          }
+          {
+            name = "syn";
+            packageId = "syn 2.0.106";
+            features = [
+              "full"
+              "extra-traits"
+            ];
+          }
+        ];
+        features = {
+          "actix_extras" = [
+            "regex"
+            "syn/extra-traits"
+          ];
+          "axum_extras" = [
+            "regex"
+            "syn/extra-traits"
+          ];
+          "config" = [
+            "dep:utoipa-config"
+            "dep:once_cell"
+          ];
+          "debug" = [ "syn/extra-traits" ];
+          "regex" = [ "dep:regex" ];
+          "rocket_extras" = [
+            "regex"
+            "syn/extra-traits"
+          ];
+          "ulid" = [ "dep:ulid" ];
+          "url" = [ "dep:url" ];
+          "uuid" = [ "dep:uuid" ];
+        };
+        resolvedDefaultFeatures = [
+          "regex"
+          "rocket_extras"
+        ];
+      };
       "valuable" = rec {
         crateName = "valuable";
         version = "0.1.1";
@@ -13585,7 +16549,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "full" ];
           }
           {
@@ -13696,7 +16660,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [
               "visit"
               "visit-mut"
@@ -16110,7 +19074,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             usesDefaultFeatures = false;
             features = [
               "parsing"
@@ -16146,7 +19110,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             usesDefaultFeatures = false;
             features = [
               "parsing"
@@ -17935,6 +20899,16 @@ rec {
           "either" = [ "dep:either" ];
         };
       };
+      "xmlwriter" = rec {
+        crateName = "xmlwriter";
+        version = "0.1.0";
+        edition = "2018";
+        sha256 = "1fg0ldmkgiis6hnxpi1c9gy7v23y0lpi824bp8yp12fi3r82lypc";
+        authors = [
+          "Evgeniy Reizner <razrfalcon@gmail.com>"
+        ];
+
+      };
       "yansi" = rec {
         crateName = "yansi";
         version = "1.0.1";
@@ -18053,7 +21027,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "fold" ];
           }
           {
@@ -18102,7 +21076,11 @@ rec {
           "std" = [ "alloc" ];
           "zerocopy-derive" = [ "dep:zerocopy-derive" ];
         };
-        resolvedDefaultFeatures = [ "simd" ];
+        resolvedDefaultFeatures = [
+          "derive"
+          "simd"
+          "zerocopy-derive"
+        ];
       };
       "zerocopy-derive" = rec {
         crateName = "zerocopy-derive";
@@ -18126,7 +21104,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "full" ];
           }
         ];
@@ -18178,7 +21156,7 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "fold" ];
           }
           {
@@ -18336,13 +21314,36 @@ rec {
           }
           {
             name = "syn";
-            packageId = "syn";
+            packageId = "syn 2.0.106";
             features = [ "extra-traits" ];
           }
         ];
 
       };
-      "zstd" = rec {
+      "zlib-rs" = rec {
+        crateName = "zlib-rs";
+        version = "0.6.8";
+        edition = "2021";
+        sha256 = "04j158293bx73kv5pj1i89ai411q7fxc9zwk3wkpqgb9gj7fas5j";
+        libName = "zlib_rs";
+        features = {
+          "__internal-fuzz" = [ "arbitrary" ];
+          "__internal-test" = [ "quickcheck" ];
+          "arbitrary" = [ "dep:arbitrary" ];
+          "avx512" = [ "vpclmulqdq" ];
+          "default" = [
+            "std"
+            "c-allocator"
+          ];
+          "quickcheck" = [ "dep:quickcheck" ];
+          "std" = [ "rust-allocator" ];
+        };
+        resolvedDefaultFeatures = [
+          "rust-allocator"
+          "std"
+        ];
+      };
+      "zstd 0.13.3" = rec {
         crateName = "zstd";
         version = "0.13.3";
         edition = "2018";
@@ -18353,7 +21354,43 @@ rec {
         dependencies = [
           {
             name = "zstd-safe";
-            packageId = "zstd-safe";
+            packageId = "zstd-safe 7.2.4";
+            usesDefaultFeatures = false;
+            features = [ "std" ];
+          }
+        ];
+        features = {
+          "arrays" = [ "zstd-safe/arrays" ];
+          "bindgen" = [ "zstd-safe/bindgen" ];
+          "debug" = [ "zstd-safe/debug" ];
+          "default" = [
+            "legacy"
+            "arrays"
+            "zdict_builder"
+          ];
+          "experimental" = [ "zstd-safe/experimental" ];
+          "fat-lto" = [ "zstd-safe/fat-lto" ];
+          "legacy" = [ "zstd-safe/legacy" ];
+          "no_asm" = [ "zstd-safe/no_asm" ];
+          "pkg-config" = [ "zstd-safe/pkg-config" ];
+          "thin" = [ "zstd-safe/thin" ];
+          "thin-lto" = [ "zstd-safe/thin-lto" ];
+          "zdict_builder" = [ "zstd-safe/zdict_builder" ];
+          "zstdmt" = [ "zstd-safe/zstdmt" ];
+        };
+      };
+      "zstd 0.14.2" = rec {
+        crateName = "zstd";
+        version = "0.14.2";
+        edition = "2018";
+        sha256 = "14vgv67df41q8g1h9xdy20pgzjdl4hk5jjd8mnh67hzs1j8zsz05";
+        authors = [
+          "Alexandre Bury <alexandre.bury@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "zstd-safe";
+            packageId = "zstd-safe 8.1.0";
             usesDefaultFeatures = false;
             features = [ "std" ];
           }
@@ -18361,6 +21398,7 @@ rec {
         features = {
           "arrays" = [ "zstd-safe/arrays" ];
           "bindgen" = [ "zstd-safe/bindgen" ];
+          "cmake" = [ "zstd-safe/cmake" ];
           "debug" = [ "zstd-safe/debug" ];
           "default" = [
             "legacy"
@@ -18374,6 +21412,8 @@ rec {
           "pkg-config" = [ "zstd-safe/pkg-config" ];
           "thin" = [ "zstd-safe/thin" ];
           "thin-lto" = [ "zstd-safe/thin-lto" ];
+          "vendored" = [ "zstd-safe/vendored" ];
+          "with-rust-allocator" = [ "zstd-safe/with-rust-allocator" ];
           "zdict_builder" = [ "zstd-safe/zdict_builder" ];
           "zstdmt" = [ "zstd-safe/zstdmt" ];
         };
@@ -18384,7 +21424,7 @@ rec {
           "zdict_builder"
         ];
       };
-      "zstd-safe" = rec {
+      "zstd-safe 7.2.4" = rec {
         crateName = "zstd-safe";
         version = "7.2.4";
         edition = "2018";
@@ -18420,6 +21460,47 @@ rec {
           "zdict_builder" = [ "zstd-sys/zdict_builder" ];
           "zstdmt" = [ "zstd-sys/zstdmt" ];
         };
+        resolvedDefaultFeatures = [ "std" ];
+      };
+      "zstd-safe 8.1.0" = rec {
+        crateName = "zstd-safe";
+        version = "8.1.0";
+        edition = "2018";
+        sha256 = "1b5girg7cgds7hy7a7x0af8xsbihb8qj8nvm2wvizsc4f9m4rm5x";
+        libName = "zstd_safe";
+        authors = [
+          "Alexandre Bury <alexandre.bury@gmail.com>"
+        ];
+        dependencies = [
+          {
+            name = "zstd-sys";
+            packageId = "zstd-sys";
+            usesDefaultFeatures = false;
+          }
+        ];
+        features = {
+          "bindgen" = [ "zstd-sys/bindgen" ];
+          "cmake" = [ "zstd-sys/cmake" ];
+          "debug" = [ "zstd-sys/debug" ];
+          "default" = [
+            "legacy"
+            "arrays"
+            "zdict_builder"
+          ];
+          "experimental" = [ "zstd-sys/experimental" ];
+          "fat-lto" = [ "zstd-sys/fat-lto" ];
+          "legacy" = [ "zstd-sys/legacy" ];
+          "no_asm" = [ "zstd-sys/no_asm" ];
+          "pkg-config" = [ "zstd-sys/pkg-config" ];
+          "seekable" = [ "zstd-sys/seekable" ];
+          "std" = [ "zstd-sys/std" ];
+          "thin" = [ "zstd-sys/thin" ];
+          "thin-lto" = [ "zstd-sys/thin-lto" ];
+          "vendored" = [ "zstd-sys/vendored" ];
+          "with-rust-allocator" = [ "experimental" ];
+          "zdict_builder" = [ "zstd-sys/zdict_builder" ];
+          "zstdmt" = [ "zstd-sys/zstdmt" ];
+        };
         resolvedDefaultFeatures = [
           "arrays"
           "legacy"
@@ -18429,10 +21510,10 @@ rec {
       };
       "zstd-sys" = rec {
         crateName = "zstd-sys";
-        version = "2.0.16+zstd.1.5.7";
+        version = "2.1.1+zstd.1.5.7";
         edition = "2018";
         links = "zstd";
-        sha256 = "0j1pd2iaqpvaxlgqmmijj68wma7xwdv9grrr63j873yw5ay9xqci";
+        sha256 = "0y50xj2hmnbyzls0g8b6ja7bncxargzx0fz2069d1fzz5nprxv5f";
         libName = "zstd_sys";
         authors = [
           "Alexandre Bury <alexandre.bury@gmail.com>"
@@ -18450,10 +21531,10 @@ rec {
         ];
         features = {
           "bindgen" = [ "dep:bindgen" ];
+          "cmake" = [ "dep:cmake" ];
           "default" = [
             "legacy"
             "zdict_builder"
-            "bindgen"
           ];
         };
         resolvedDefaultFeatures = [
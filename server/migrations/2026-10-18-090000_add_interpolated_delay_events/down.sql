@@ -0,0 +1,5 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+ALTER TABLE delay_events DROP COLUMN interpolated;
@@ -0,0 +1,9 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+ALTER TABLE delay_events ADD COLUMN interpolated BOOLEAN NOT NULL DEFAULT false;
+
+-- delay_events is a cache table, so we empty it in order to have the gaps in it filled with
+-- interpolated events on the next startup.
+TRUNCATE delay_events;
@@ -0,0 +1,10 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+CREATE TABLE IF NOT EXISTS quarantine
+          ( id BIGSERIAL NOT NULL PRIMARY KEY
+          , fetched_json_id BIGINT NOT NULL UNIQUE REFERENCES fetched_json(id)
+          , quarantined_at TIMESTAMP WITH TIME ZONE NOT NULL
+          , reason TEXT NOT NULL
+          );
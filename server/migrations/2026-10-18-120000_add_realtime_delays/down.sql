@@ -0,0 +1,9 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+ALTER TABLE delay_events DROP COLUMN has_realtime;
+
+ALTER TABLE delay_records DROP COLUMN has_realtime;
+ALTER TABLE delay_records DROP COLUMN departure_delay;
+ALTER TABLE delay_records DROP COLUMN arrival_delay;
@@ -0,0 +1,14 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+ALTER TABLE delay_records ADD COLUMN arrival_delay BIGINT;
+ALTER TABLE delay_records ADD COLUMN departure_delay BIGINT;
+ALTER TABLE delay_records ADD COLUMN has_realtime BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE delay_events ADD COLUMN has_realtime BOOLEAN NOT NULL DEFAULT false;
+
+-- Both tables are cache tables. We can't fill the new columns from the existing rows, so we
+-- empty them in order to have them regenerated from fetched_json on the next startup.
+TRUNCATE delay_events;
+TRUNCATE delay_records;
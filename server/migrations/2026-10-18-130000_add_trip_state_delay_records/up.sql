@@ -0,0 +1,10 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+ALTER TABLE delay_records ADD COLUMN trip_state TEXT NOT NULL DEFAULT 'en_route';
+
+-- Trips waiting at their origin weren't recorded so far and trips at their terminus carried the
+-- wrong delay, so we regenerate both cache tables on the next startup.
+TRUNCATE delay_events;
+TRUNCATE delay_records;
@@ -0,0 +1,7 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+DROP INDEX trip_summaries_planned_departure_index;
+
+DROP TABLE trip_summaries;
@@ -0,0 +1,25 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+CREATE TABLE IF NOT EXISTS trip_summaries
+          ( trip_id TEXT NOT NULL PRIMARY KEY
+          , line TEXT
+          , train_number TEXT
+          , direction TEXT CHECK (direction IN ('westwards', 'eastwards'))
+          , headsign TEXT
+          , origin TEXT NOT NULL
+          , destination TEXT NOT NULL
+          , planned_departure TIMESTAMP WITH TIME ZONE NOT NULL
+          , first_observation TIMESTAMP WITH TIME ZONE NOT NULL
+          , last_observation TIMESTAMP WITH TIME ZONE NOT NULL
+          , start_delay BIGINT
+          , final_delay BIGINT
+          , max_delay BIGINT
+          , stations_covered BIGINT NOT NULL
+          , cancelled BOOLEAN NOT NULL
+          , cancelled_stops BIGINT NOT NULL
+          , last_fetched_json_id BIGINT NOT NULL REFERENCES fetched_json(id)
+          );
+
+CREATE INDEX trip_summaries_planned_departure_index ON trip_summaries (planned_departure);
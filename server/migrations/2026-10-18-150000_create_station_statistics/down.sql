@@ -0,0 +1,13 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+DROP INDEX station_punctuality_period_start_index;
+
+DROP TABLE station_punctuality;
+
+DROP INDEX station_visits_planned_departure_index;
+DROP INDEX station_visits_planned_arrival_index;
+DROP INDEX station_visits_fetched_json_id_index;
+
+DROP TABLE station_visits;
@@ -0,0 +1,40 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- Every stop of every trip, along with the delays it actually had there.
+CREATE TABLE IF NOT EXISTS station_visits
+          ( trip_id TEXT NOT NULL
+          , station BIGINT NOT NULL
+          , direction TEXT NOT NULL CHECK (direction IN ('westwards', 'eastwards'))
+          , planned_arrival TIMESTAMP WITH TIME ZONE
+          , arrival_delay BIGINT
+          , planned_departure TIMESTAMP WITH TIME ZONE
+          , departure_delay BIGINT
+          , fetched_json_id BIGINT NOT NULL REFERENCES fetched_json(id)
+          , PRIMARY KEY (trip_id, station)
+          );
+
+CREATE INDEX station_visits_fetched_json_id_index ON station_visits (fetched_json_id);
+CREATE INDEX station_visits_planned_arrival_index ON station_visits (planned_arrival);
+CREATE INDEX station_visits_planned_departure_index ON station_visits (planned_departure);
+
+-- Punctuality per station, direction and hour, day or week, aggregated from station_visits.
+CREATE TABLE IF NOT EXISTS station_punctuality
+          ( station BIGINT NOT NULL
+          , direction TEXT NOT NULL CHECK (direction IN ('westwards', 'eastwards'))
+          , period TEXT NOT NULL
+          , period_start TIMESTAMP WITH TIME ZONE NOT NULL
+          , kind TEXT NOT NULL
+          , count BIGINT NOT NULL
+          , within_0 DOUBLE PRECISION NOT NULL
+          , within_3 DOUBLE PRECISION NOT NULL
+          , within_6 DOUBLE PRECISION NOT NULL
+          , within_15 DOUBLE PRECISION NOT NULL
+          , median_delay DOUBLE PRECISION NOT NULL
+          , p90_delay DOUBLE PRECISION NOT NULL
+          , fetched_json_id BIGINT NOT NULL
+          , PRIMARY KEY (station, direction, period, period_start, kind)
+          );
+
+CREATE INDEX station_punctuality_period_start_index ON station_punctuality (period_start);
@@ -0,0 +1,8 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+DROP INDEX segment_delays_planned_departure_index;
+DROP INDEX segment_delays_fetched_json_id_index;
+
+DROP TABLE segment_delays;
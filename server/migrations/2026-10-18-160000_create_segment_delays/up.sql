@@ -0,0 +1,23 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- How much delay every trip gained (or lost, if negative) between two consecutive stations,
+-- split into the running time on the segment and the dwell time at the station it leads to.
+CREATE TABLE IF NOT EXISTS segment_delays
+          ( trip_id TEXT NOT NULL
+          , from_station BIGINT NOT NULL
+          , to_station BIGINT NOT NULL
+          , direction TEXT NOT NULL
+          , planned_departure TIMESTAMP WITH TIME ZONE NOT NULL
+          , departure_delay BIGINT
+          , arrival_delay BIGINT
+          , next_departure_delay BIGINT
+          , running_gain BIGINT
+          , dwell_gain BIGINT
+          , fetched_json_id BIGINT NOT NULL REFERENCES fetched_json(id)
+          , PRIMARY KEY (trip_id, from_station)
+          );
+
+CREATE INDEX segment_delays_fetched_json_id_index ON segment_delays (fetched_json_id);
+CREATE INDEX segment_delays_planned_departure_index ON segment_delays (planned_departure);
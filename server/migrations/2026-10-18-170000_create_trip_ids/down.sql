@@ -0,0 +1,14 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+DROP INDEX trip_ids_canonical_trip_id_index;
+
+DROP TABLE trip_ids;
+
+TRUNCATE delay_events;
+TRUNCATE delay_records;
+TRUNCATE trip_summaries;
+TRUNCATE station_visits;
+TRUNCATE station_punctuality;
+TRUNCATE segment_delays;
@@ -0,0 +1,21 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- HAFAS trip ids can change for the same train run, so we map them to a canonical trip id.
+CREATE TABLE IF NOT EXISTS trip_ids
+          ( hafas_trip_id TEXT PRIMARY KEY
+          , canonical_trip_id TEXT NOT NULL
+          , fetched_json_id BIGINT NOT NULL REFERENCES fetched_json(id)
+          );
+
+CREATE INDEX trip_ids_canonical_trip_id_index ON trip_ids (canonical_trip_id);
+
+-- Everything derived from delay_records is keyed by trip_id, which is now the canonical one.
+-- All of these get regenerated on startup.
+TRUNCATE delay_events;
+TRUNCATE delay_records;
+TRUNCATE trip_summaries;
+TRUNCATE station_visits;
+TRUNCATE station_punctuality;
+TRUNCATE segment_delays;
@@ -0,0 +1,61 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- The route model. Stations with a position are on the route, ordered from Cottbus (0) to
+-- Magdeburg. Stations we only learnt from stopovers don't have a position. Coordinates are learnt
+-- from stopovers, km is the distance along the route from its first station.
+CREATE TABLE IF NOT EXISTS stations
+          ( id BIGINT PRIMARY KEY
+          , name TEXT NOT NULL
+          , short_name TEXT
+          , important BOOLEAN NOT NULL DEFAULT FALSE
+          , position INTEGER UNIQUE
+          , km DOUBLE PRECISION
+          , latitude DOUBLE PRECISION
+          , longitude DOUBLE PRECISION
+          );
+
+INSERT INTO stations (id, name, short_name, important, position) VALUES
+          (900470000, 'Cottbus, Hauptbahnhof', 'Cottbus', TRUE, 0)
+        , (900445593, 'Guben, Bahnhof', 'Guben', FALSE, 1)
+        , (900311307, 'Eisenhüttenstadt, Bahnhof', 'Eisenhüttenstadt', FALSE, 2)
+        , (900360000, 'Frankfurt (Oder), Bahnhof', 'Frankfurt (Oder)', TRUE, 3)
+        , (900360004, 'Frankfurt (Oder), Rosengarten Bhf', 'Rosengarten', FALSE, 4)
+        , (900310008, 'Pillgram, Bahnhof', 'Pillgram', FALSE, 5)
+        , (900310007, 'Jacobsdorf (Mark), Bahnhof', 'Jacobsdorf', FALSE, 6)
+        , (900310006, 'Briesen (Mark), Bahnhof', 'Briesen', FALSE, 7)
+        , (900310005, 'Berkenbrück (LOS), Bahnhof', 'Berkenbrück', FALSE, 8)
+        , (900310001, 'Fürstenwalde, Bahnhof', 'Fürstenwalde', FALSE, 9)
+        , (900310002, 'Hangelsberg, Bahnhof', 'Hangelsberg', FALSE, 10)
+        , (900310003, 'Grünheide, Fangschleuse Bhf', 'Fangschleuse', FALSE, 11)
+        , (900310004, 'S Erkner Bhf', 'Erkner', FALSE, 12)
+        , (900120003, 'S Ostkreuz Bhf (Berlin)', 'Ostkreuz', FALSE, 13)
+        , (900120005, 'S Ostbahnhof (Berlin)', 'Ostbahnhof', FALSE, 14)
+        , (900100003, 'S+U Alexanderplatz Bhf (Berlin)', 'Alexanderplatz', FALSE, 15)
+        , (900100001, 'S+U Friedrichstr. Bhf (Berlin)', 'Friedrichstr.', FALSE, 16)
+        , (900003201, 'S+U Berlin Hauptbahnhof', 'Berlin Hbf', TRUE, 17)
+        , (900023201, 'S+U Zoologischer Garten Bhf (Berlin)', 'Zoologischer Garten', FALSE, 18)
+        , (900024101, 'S Charlottenburg Bhf (Berlin)', 'Charlottenburg', FALSE, 19)
+        , (900053301, 'S Wannsee Bhf (Berlin)', 'Wannsee', FALSE, 20)
+        , (900230999, 'S Potsdam Hauptbahnhof', 'Potsdam', TRUE, 21)
+        , (900230006, 'Potsdam, Charlottenhof Bhf', 'Charlottenhof', FALSE, 22)
+        , (900230007, 'Potsdam, Park Sanssouci Bhf', 'Park Sanssouci', FALSE, 23)
+        , (900220009, 'Werder (Havel), Bahnhof', 'Werder (Havel)', FALSE, 24)
+        , (900220699, 'Groß Kreutz, Bahnhof', 'Groß Kreutz', FALSE, 25)
+        , (900220182, 'Götz, Bahnhof', 'Götz', FALSE, 26)
+        , (900275110, 'Brandenburg, Hauptbahnhof', 'Brandenburg', TRUE, 27)
+        , (900275719, 'Brandenburg, Kirchmöser Bhf', 'Kirchmöser', FALSE, 28)
+        , (900220249, 'Wusterwitz, Bahnhof', 'Wusterwitz', FALSE, 29)
+        , (900550073, 'Genthin, Bahnhof', 'Genthin', FALSE, 30)
+        , (900550078, 'Güsen, Bahnhof', 'Güsen', FALSE, 31)
+        , (900550062, 'Burg (bei Magdeburg), Bahnhof', 'Burg (bei Magdeburg)', FALSE, 32)
+        , (900550255, 'Magdeburg-Neustadt, Bahnhof', 'Magdeburg-Neustadt', FALSE, 33)
+        , (900550094, 'Magdeburg, Hauptbahnhof', 'Magdeburg', TRUE, 34);
+
+ALTER TABLE delay_records ADD COLUMN direction TEXT;
+ALTER TABLE delay_events ADD COLUMN direction TEXT;
+
+-- Regenerate them with directions.
+TRUNCATE delay_events;
+TRUNCATE delay_records;
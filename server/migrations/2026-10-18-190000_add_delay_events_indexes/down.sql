@@ -0,0 +1,6 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+DROP INDEX delay_events_trip_id_index;
+DROP INDEX delay_events_time_id_index;
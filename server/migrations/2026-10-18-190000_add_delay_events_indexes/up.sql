@@ -0,0 +1,7 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- For keyset pagination and filtering in /api/delay_events.
+CREATE INDEX delay_events_time_id_index ON delay_events (time, id);
+CREATE INDEX delay_events_trip_id_index ON delay_events (trip_id);
@@ -0,0 +1,8 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+DROP INDEX trip_changes_fetched_json_id_index;
+DROP INDEX trip_changes_trip_id_index;
+
+DROP TABLE trip_changes;
@@ -0,0 +1,19 @@
+-- SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+--
+-- SPDX-License-Identifier: GPL-3.0-or-later
+
+-- What changed between two consecutive snapshots of a trip. Trip level changes have no stop.
+CREATE TABLE IF NOT EXISTS trip_changes
+          ( id BIGSERIAL PRIMARY KEY
+          , trip_id TEXT NOT NULL
+          , fetched_json_id BIGINT NOT NULL REFERENCES fetched_json(id)
+          , previous_fetched_json_id BIGINT NOT NULL REFERENCES fetched_json(id)
+          , time TIMESTAMPTZ NOT NULL
+          , stop BIGINT
+          , kind TEXT NOT NULL
+          , old_value TEXT
+          , new_value TEXT
+          );
+
+CREATE INDEX trip_changes_trip_id_index ON trip_changes (trip_id);
+CREATE INDEX trip_changes_fetched_json_id_index ON trip_changes (fetched_json_id);
@@ -3,7 +3,11 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use crate::cli_utils::progress_style;
-use crate::models::{DelayEvent, DelayRecord, DelayRecordWithID};
+use crate::models::{
+    DelayEvent, DelayRecord, DelayRecordWithID, Stopover, TripIdMapping, ValidationError,
+};
+use crate::route::Route;
+use crate::transport_rest_vbb_v6::{deserialize, HafasMsg, TripOverview};
 
 use diesel::pg::PgConnection;
 use diesel::pg::PgRowByRowLoadingMode;
@@ -16,10 +20,16 @@ use std::error::Error;
 use time::Duration;
 use time::OffsetDateTime;
 
+/// Two DelayRecord's of a trip further apart than this (e.g. because the crawler wasn't running
+/// inbetween) don't make up any delay events, as we can't tell what happened in the meantime. This
+/// also bounds how long after its time a delay event can still show up.
+pub const MAX_DELAY_RECORD_GAP: Duration = Duration::hours(3);
+
 /// Cache state that we don't save in db but generate on each startup.
 /// Nothing too expensive should get in here, in order to preserve fast startup times.
 pub struct CacheState {
     pub trip_id_map: HashMap<String, (i64, DelayRecord)>,
+    pub route: Route,
 }
 
 /// Update all the cache tables. This is everytime on startup, but would only do actual work in the
@@ -29,7 +39,12 @@ pub fn update_caches(
     db1: &mut PgConnection,
     mut db2: PgConnection,
 ) -> Result<CacheState, Box<dyn Error>> {
-    update_delay_records(db1, &mut db2)?;
+    let route = crate::route::update_route(db1)?;
+    update_delay_records(db1, &mut db2, &route)?;
+    crate::trip_summaries::update_trip_summaries(db1)?;
+    crate::statistics::update_statistics(db1)?;
+    crate::segments::update_segment_delays(db1)?;
+    crate::trip_changes::update_trip_changes(db1, &mut db2)?;
     let trip_id_map = update_delay_events(db1, db2)?;
     let usage: u64 = trip_id_map.dynamic_usage().try_into().unwrap();
     info!(
@@ -37,12 +52,13 @@ pub fn update_caches(
         trip_id_map.len().to_formatted_string(&Locale::en),
         bytefmt::format(usage)
     );
-    Ok(CacheState { trip_id_map })
+    Ok(CacheState { trip_id_map, route })
 }
 
 pub fn update_delay_records(
     db1: &mut PgConnection,
     db2: &mut PgConnection,
+    route: &Route,
 ) -> Result<(), Box<dyn Error>> {
     use crate::models::delay_record_from_trip_overview;
     use crate::schema::delay_records;
@@ -51,6 +67,7 @@ pub fn update_delay_records(
     use diesel::QueryDsl;
     use diesel::{ExpressionMethods, RunQueryDsl};
     use std::sync::mpsc::channel;
+    use std::sync::Arc;
     use threadpool::ThreadPool;
 
     let bodies_count: i64 = fetched_json::dsl::fetched_json.count().get_result(db1)?;
@@ -93,17 +110,21 @@ pub fn update_delay_records(
     let pool = ThreadPool::new(thread_count);
 
     let (tx, rx) = channel();
+    let route = Arc::new(route.clone());
 
     for select_result in fetched_json_iter {
         if pool.queued_count() < MAX_QUEUED_COUNT {
             let (row_id, fetched_at, json_body) = select_result?;
             let tx = tx.clone();
+            let route = Arc::clone(&route);
             pool.execute(move || {
-                if let Ok(trip_overview) = serde_json::from_str(&json_body) {
+                if let Ok(trip_overview) = serde_json::from_str::<TripOverview>(&json_body) {
+                    let hafas_trip_id = trip_overview.trip.id.clone();
                     if let Some(dr) =
-                        delay_record_from_trip_overview(trip_overview, row_id, fetched_at)
+                        delay_record_from_trip_overview(trip_overview, row_id, fetched_at, &route)
                     {
-                        tx.send(dr).expect("Can't send DelayRecord through channel");
+                        tx.send((hafas_trip_id, dr))
+                            .expect("Can't send DelayRecord through channel");
                     }
                 }
             });
@@ -118,7 +139,27 @@ pub fn update_delay_records(
 
     drop(tx);
 
-    let delay_records = rx.iter().collect::<Vec<DelayRecord>>();
+    let mut delay_records = Vec::new();
+    let mut trip_ids: HashMap<String, TripIdMapping> = HashMap::new();
+    for (hafas_trip_id, dr) in rx.iter() {
+        // The threadpool doesn't preserve order, but we want to remember when we first saw a
+        // HAFAS trip id.
+        let mapping = trip_ids
+            .entry(hafas_trip_id.clone())
+            .or_insert_with(|| TripIdMapping {
+                hafas_trip_id,
+                canonical_trip_id: dr.trip_id.clone(),
+                fetched_json_id: dr.fetched_json_id,
+            });
+        if dr.fetched_json_id < mapping.fetched_json_id {
+            mapping.canonical_trip_id = dr.trip_id.clone();
+            mapping.fetched_json_id = dr.fetched_json_id;
+        }
+        delay_records.push(dr);
+    }
+
+    let trip_ids: Vec<TripIdMapping> = trip_ids.into_values().collect();
+    crate::trip_ids::record_trip_ids(db2, &trip_ids)?;
 
     info!(
         "Inserting {} DelayRecord's into delay_records table.",
@@ -190,10 +231,35 @@ pub fn update_delay_events(
     for new_delay_record_with_id in delay_records_iter {
         let new_delay_record = DelayRecord::from(new_delay_record_with_id?);
 
-        let des = delay_events_from_delay_record(&mut trip_id_map, &new_delay_record);
-
         // We only write to db if the delay event isn't there yet.
-        if latest_to_id <= new_delay_record.fetched_json_id {
+        let write_to_db = latest_to_id <= new_delay_record.fetched_json_id;
+
+        // Looking up the timetable is expensive, so we only do it if the trip passed a station
+        // and we are actually going to store the resulting delay events.
+        let passed_station = trip_id_map
+            .get(&new_delay_record.trip_id)
+            .is_some_and(|(_, old)| old.next_station != new_delay_record.next_station);
+        let stopovers = if write_to_db && passed_station {
+            load_stopovers(&mut db2, new_delay_record.fetched_json_id)?
+        } else {
+            None
+        };
+
+        let des = match delay_events_from_delay_record(
+            &mut trip_id_map,
+            &new_delay_record,
+            stopovers.as_deref(),
+        ) {
+            Ok(des) => des,
+            Err(e) => {
+                if write_to_db {
+                    crate::quarantine::quarantine(&mut db2, new_delay_record.fetched_json_id, &e)?;
+                }
+                vec![]
+            }
+        };
+
+        if write_to_db {
             for de in des {
                 chunk.push(de);
             }
@@ -210,27 +276,56 @@ pub fn update_delay_events(
 
     diesel::insert_into(delay_events::table)
         .values(&chunk)
-        .execute(&mut db2)
-        .unwrap();
+        .execute(&mut db2)?;
 
     progress_bar.finish();
 
     Ok(trip_id_map)
 }
 
-/// Creates zero, one or two delay events from two rows (datapoints) from the fetched_json table.
+/// Load the stopovers of the TripOverview that is stored in a given fetched_json row.
+fn load_stopovers(
+    db: &mut PgConnection,
+    fetched_json_id: i64,
+) -> Result<Option<Vec<Stopover>>, Box<dyn Error>> {
+    use crate::schema::fetched_json;
+    use diesel::QueryDsl;
+    use diesel::RunQueryDsl;
+
+    let body: String = fetched_json::table
+        .find(fetched_json_id)
+        .select(fetched_json::body)
+        .first(db)?;
+
+    Ok(match deserialize(&body) {
+        Ok(HafasMsg::TripOverview(to)) => {
+            Some(to.trip.stopovers.iter().map(Stopover::from).collect())
+        }
+        _ => None,
+    })
+}
+
+/// Creates zero, one or more delay events from two rows (datapoints) from the fetched_json table.
 /// One, if the two datapoints happened in the same track segment.
-/// Two, if the train changed the track segment to an adjacent track segment.
-/// Zero, if nothing of the above applies.
+/// Two, if the train changed the track segment to an adjacent track segment. If `stopovers` is
+/// available, the two are split at the actual departure from the passed station.
+/// One per traversed track segment, if the train passed more than one station. These are
+/// interpolated from the stopovers, so they can only be built if `stopovers` is available.
+/// Zero, if the two datapoints are more than MAX_DELAY_RECORD_GAP apart or nothing of the above
+/// applies.
+///
+/// A ValidationError is returned if the two datapoints don't make sense together. The new
+/// DelayRecord still becomes the starting point for the next delay events in this case.
 pub fn delay_events_from_delay_record(
     trip_id_map: &mut HashMap<String, (i64, DelayRecord)>,
     new_delay_record: &DelayRecord,
-) -> Vec<DelayEvent> {
+    stopovers: Option<&[Stopover]>,
+) -> Result<Vec<DelayEvent>, ValidationError> {
     let trip_id = new_delay_record.trip_id.clone();
 
     let new_row_id: i64 = new_delay_record.fetched_json_id;
 
-    let mut result = vec![];
+    let mut result = Ok(vec![]);
 
     // We expect new_delay_record to be the starting point of a trip otherwise. In that case we'd
     // skip the creation of a delay event and create one when the trip_id occurs next.
@@ -238,10 +333,14 @@ pub fn delay_events_from_delay_record(
         let old = old_delay_record;
         let new = &new_delay_record;
 
-        // The duration in seconds inbetween the two data points.
-        let duration = (new.time - old.time).whole_seconds();
-
-        if old.previous_station == new.previous_station && old.next_station == new.next_station {
+        if new.time - old.time > MAX_DELAY_RECORD_GAP {
+            debug!(
+                "Can't build a delay_event, as {} and {} are too far apart.",
+                old_row_id, new_row_id
+            );
+        } else if old.previous_station == new.previous_station
+            && old.next_station == new.next_station
+        {
             // The trip didn't change the segment inbetween the two datapoints
 
             let delay_event = DelayEvent {
@@ -249,90 +348,223 @@ pub fn delay_events_from_delay_record(
                 to_id: new_row_id,
                 trip_id: trip_id.clone(),
                 time: old.time + ((new.time - old.time) / 2),
-                duration,
+                duration: (new.time - old.time).whole_seconds(),
                 previous_station: old.previous_station,
                 next_station: old.next_station,
                 percentage_segment: old.percentage_segment
                     + ((new.percentage_segment - old.percentage_segment) / 2.0),
                 delay: (old.delay + new.delay) / 2,
+                interpolated: false,
+                has_realtime: old.has_realtime && new.has_realtime,
+                direction: new.direction,
             };
 
-            result = vec![delay_event];
+            result = Ok(vec![delay_event]);
         } else if old.next_station == new.previous_station {
             // The trip passed a station inbetwwen the two datapoints, so we have to
             // create two delay events.
-
-            // The ratio we assume between the two delay events. This is inaccurate and
-            // will likely cause artifacts, as we are using percentage of distance as a
-            // proxy for time. For the real value we'd need an actual timetable.
-            let ratio = {
-                let mut v = (1.0 - old.percentage_segment)
-                    * (1.0 / (1.0 - old.percentage_segment + new.percentage_segment));
-                if v.is_nan() {
-                    // In this case (e.g. if the train is in a station for the entire duration) we
-                    // just divide the event 50/50.
-                    v = 0.5;
-                }
-                v
+            result = match stopovers.and_then(|stopovers| {
+                timetable_delay_events(*old_row_id, old, new_row_id, new, stopovers, false)
+            }) {
+                Some(des) => Ok(des),
+                None => estimated_delay_events(*old_row_id, old, new_row_id, new),
             };
-            assert!(
-                (0.0..=1.0).contains(&ratio),
-                "{:?} --- {:?}: {}",
-                old,
-                new,
-                ratio
-            );
+        } else {
+            // More than one station was passed inbetween the two datapoints, so we fill the gap
+            // with the help of the timetable.
+            result = Ok(stopovers
+                .and_then(|stopovers| {
+                    timetable_delay_events(*old_row_id, old, new_row_id, new, stopovers, true)
+                })
+                .unwrap_or_else(|| {
+                    debug!(
+                        "Can't build a delay_event, as Between {} and {} more than one station was passed.",
+                        old_row_id, new_row_id
+                    );
+                    vec![]
+                }));
+        }
+    };
+    trip_id_map.insert(trip_id.clone(), (new_row_id, new_delay_record.clone()));
+    result
+}
 
-            // The timestamp inbetween the two delay events, e.g. an idealised point in
-            // time where the train was in the station.
-            let switch_time = old.time + Duration::seconds((duration as f64 * ratio) as i64);
+/// The delay at a given point in time, interpolated linearly between two DelayRecord's.
+fn delay_at(old: &DelayRecord, new: &DelayRecord, time: OffsetDateTime) -> i64 {
+    let progress = (time - old.time).as_seconds_f64() / (new.time - old.time).as_seconds_f64();
+    if progress.is_nan() {
+        return old.delay;
+    }
+    old.delay + ((new.delay - old.delay) as f64 * progress) as i64
+}
 
-            let time1 = old.time + ((switch_time - old.time) / 2);
-            let time2 = switch_time + ((new.time - switch_time) / 2);
-            let duration1 = (switch_time - old.time).whole_seconds();
-            let duration2 = (new.time - switch_time).whole_seconds();
-            let percentage_segment1 = (old.percentage_segment + 1.0) / 2.0;
-            let percentage_segment2 = (0.0 + new.percentage_segment) / 2.0;
+/// Creates one delay event per track segment that was traversed between two DelayRecord's,
+/// which are at least one station apart. The segments are split at the actual departure times
+/// (planned time plus delay) of the passed stations, so the time the train is waiting in a
+/// station counts towards the segment leading to that station. The delay of each event is
+/// interpolated linearly between the two observed delays.
+///
+/// Returns None if the stations can't be found in the stopovers in the right order.
+fn timetable_delay_events(
+    old_row_id: i64,
+    old: &DelayRecord,
+    new_row_id: i64,
+    new: &DelayRecord,
+    stopovers: &[Stopover],
+    interpolated: bool,
+) -> Option<Vec<DelayEvent>> {
+    if new.time <= old.time {
+        return None;
+    }
 
-            let delay_event1 = DelayEvent {
-                from_id: *old_row_id,
-                to_id: new_row_id,
-                trip_id: trip_id.clone(),
-                time: time1,
-                duration: duration1,
-                previous_station: old.previous_station,
-                next_station: old.next_station,
-                percentage_segment: percentage_segment1,
-                delay: (old.delay as f64 * ratio) as i64,
-            };
-            let delay_event2 = DelayEvent {
-                from_id: *old_row_id,
-                to_id: new_row_id,
-                trip_id: trip_id.clone(),
-                time: time2,
-                duration: duration2,
-                previous_station: new.previous_station,
-                next_station: new.next_station,
-                percentage_segment: percentage_segment2,
-                delay: (old.delay as f64 * (1.0 - ratio)) as i64,
-            };
+    let position = |station: i64| stopovers.iter().position(|so| so.stop == station);
+    let first = position(old.next_station)?;
+    let last = position(new.previous_station)?;
+    if first > last || position(old.previous_station)? + 1 != first {
+        return None;
+    }
 
-            result = vec![delay_event1, delay_event2];
-        } else {
-            debug!(
-                "Can't build a delay_event, as Between {} and {} more than one station was passed.",
-                old_row_id, new_row_id
-            );
-            result = vec![];
+    // All the timestamps we get from the timetable have to fit inbetween the two datapoints.
+    let departure = |so: &Stopover| {
+        so.departure()
+            .or_else(|| so.arrival())
+            .map(|t| t.clamp(old.time, new.time))
+    };
+    let delay_event = |from: OffsetDateTime,
+                       to: OffsetDateTime,
+                       previous_station: i64,
+                       next_station: i64,
+                       percentage_segment: f64| {
+        let time = from + ((to - from) / 2);
+        DelayEvent {
+            from_id: old_row_id,
+            to_id: new_row_id,
+            trip_id: new.trip_id.clone(),
+            time,
+            duration: (to - from).whole_seconds(),
+            previous_station,
+            next_station,
+            percentage_segment,
+            delay: delay_at(old, new, time),
+            interpolated,
+            has_realtime: old.has_realtime && new.has_realtime,
+            direction: new.direction,
         }
     };
-    trip_id_map.insert(trip_id.clone(), (new_row_id, new_delay_record.clone()));
-    result
+
+    let mut result = Vec::new();
+
+    // The rest of the segment the trip was in at the old datapoint.
+    let mut switch_time = departure(&stopovers[first])?;
+    result.push(delay_event(
+        old.time,
+        switch_time,
+        old.previous_station,
+        old.next_station,
+        (old.percentage_segment + 1.0) / 2.0,
+    ));
+
+    // The segments that were traversed entirely.
+    for segment in stopovers[first..=last].windows(2) {
+        let next_switch_time = departure(&segment[1])?;
+        result.push(delay_event(
+            switch_time,
+            next_switch_time,
+            segment[0].stop,
+            segment[1].stop,
+            0.5,
+        ));
+        switch_time = next_switch_time;
+    }
+
+    // The beginning of the segment the trip is in at the new datapoint.
+    result.push(delay_event(
+        switch_time,
+        new.time,
+        new.previous_station,
+        new.next_station,
+        new.percentage_segment / 2.0,
+    ));
+
+    Some(result)
+}
+
+/// Creates two delay events for a trip that passed one station inbetween two DelayRecord's,
+/// without knowing the timetable.
+fn estimated_delay_events(
+    old_row_id: i64,
+    old: &DelayRecord,
+    new_row_id: i64,
+    new: &DelayRecord,
+) -> Result<Vec<DelayEvent>, ValidationError> {
+    // The duration in seconds inbetween the two data points.
+    let duration = (new.time - old.time).whole_seconds();
+
+    // The ratio we assume between the two delay events. This is inaccurate and
+    // will likely cause artifacts, as we are using percentage of distance as a
+    // proxy for time.
+    let ratio = {
+        let mut v = (1.0 - old.percentage_segment)
+            * (1.0 / (1.0 - old.percentage_segment + new.percentage_segment));
+        if v.is_nan() {
+            // In this case (e.g. if the train is in a station for the entire duration) we
+            // just divide the event 50/50.
+            v = 0.5;
+        }
+        v
+    };
+    if !(0.0..=1.0).contains(&ratio) {
+        debug!("{:?} --- {:?}: {}", old, new, ratio);
+        return Err(ValidationError::RatioOutOfRange(ratio));
+    }
+
+    // The timestamp inbetween the two delay events, e.g. an idealised point in
+    // time where the train was in the station.
+    let switch_time = old.time + Duration::seconds((duration as f64 * ratio) as i64);
+
+    let time1 = old.time + ((switch_time - old.time) / 2);
+    let time2 = switch_time + ((new.time - switch_time) / 2);
+    let duration1 = (switch_time - old.time).whole_seconds();
+    let duration2 = (new.time - switch_time).whole_seconds();
+    let percentage_segment1 = (old.percentage_segment + 1.0) / 2.0;
+    let percentage_segment2 = (0.0 + new.percentage_segment) / 2.0;
+
+    let delay_event1 = DelayEvent {
+        from_id: old_row_id,
+        to_id: new_row_id,
+        trip_id: new.trip_id.clone(),
+        time: time1,
+        duration: duration1,
+        previous_station: old.previous_station,
+        next_station: old.next_station,
+        percentage_segment: percentage_segment1,
+        delay: delay_at(old, new, time1),
+        interpolated: false,
+        has_realtime: old.has_realtime && new.has_realtime,
+        direction: new.direction,
+    };
+    let delay_event2 = DelayEvent {
+        from_id: old_row_id,
+        to_id: new_row_id,
+        trip_id: new.trip_id.clone(),
+        time: time2,
+        duration: duration2,
+        previous_station: new.previous_station,
+        next_station: new.next_station,
+        percentage_segment: percentage_segment2,
+        delay: delay_at(old, new, time2),
+        interpolated: false,
+        has_realtime: old.has_realtime && new.has_realtime,
+        direction: new.direction,
+    };
+
+    Ok(vec![delay_event1, delay_event2])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::TripState;
     use std::collections::HashMap;
     use time::{Duration, OffsetDateTime};
 
@@ -350,8 +582,14 @@ mod tests {
                 next_station: 1,
                 percentage_segment: 0.5,
                 delay: 0,
+                arrival_delay: Some(0),
+                departure_delay: None,
+                has_realtime: true,
+                trip_state: TripState::EnRoute,
+                direction: None,
             },
-        );
+            None,
+        )?;
 
         assert_eq!(delay_events, vec![]);
 
@@ -365,8 +603,14 @@ mod tests {
                 next_station: 1,
                 percentage_segment: 0.7,
                 delay: 60,
+                arrival_delay: Some(60),
+                departure_delay: None,
+                has_realtime: true,
+                trip_state: TripState::EnRoute,
+                direction: None,
             },
-        );
+            None,
+        )?;
 
         assert_eq!(
             delay_events,
@@ -379,10 +623,219 @@ mod tests {
                 previous_station: 0,
                 next_station: 1,
                 percentage_segment: 0.6,
-                delay: 30
+                delay: 30,
+                interpolated: false,
+                has_realtime: true,
+                direction: None,
             }]
         );
 
         Ok(())
     }
+
+    fn at(seconds: i64) -> OffsetDateTime {
+        OffsetDateTime::UNIX_EPOCH + Duration::seconds(seconds)
+    }
+
+    fn stopover(stop: i64, arrival: Option<i64>, departure: Option<i64>, delay: i64) -> Stopover {
+        Stopover {
+            stop,
+            planned_arrival: arrival.map(at),
+            arrival_delay: arrival.map(|_| delay),
+            planned_departure: departure.map(at),
+            departure_delay: departure.map(|_| delay),
+        }
+    }
+
+    fn delay_record(
+        fetched_json_id: i64,
+        time: i64,
+        previous_station: i64,
+        next_station: i64,
+        percentage_segment: f64,
+        delay: i64,
+    ) -> DelayRecord {
+        DelayRecord {
+            fetched_json_id,
+            trip_id: "t1".to_string(),
+            time: at(time),
+            previous_station,
+            next_station,
+            percentage_segment,
+            delay,
+            arrival_delay: Some(delay),
+            departure_delay: None,
+            has_realtime: true,
+            trip_state: TripState::EnRoute,
+            direction: None,
+        }
+    }
+
+    fn delay_event(
+        time: i64,
+        duration: i64,
+        previous_station: i64,
+        next_station: i64,
+        percentage_segment: f64,
+        delay: i64,
+        interpolated: bool,
+    ) -> DelayEvent {
+        DelayEvent {
+            from_id: 0,
+            to_id: 1,
+            trip_id: "t1".to_string(),
+            time: at(time),
+            duration,
+            previous_station,
+            next_station,
+            percentage_segment,
+            delay,
+            interpolated,
+            has_realtime: true,
+            direction: None,
+        }
+    }
+
+    /// Feed two DelayRecord's and return the DelayEvent's resulting from the second one.
+    fn try_delay_events_between(
+        old: DelayRecord,
+        new: DelayRecord,
+        stopovers: Option<&[Stopover]>,
+    ) -> Result<Vec<DelayEvent>, ValidationError> {
+        let mut trip_id_map = HashMap::new();
+        assert_eq!(
+            delay_events_from_delay_record(&mut trip_id_map, &old, stopovers),
+            Ok(vec![])
+        );
+        delay_events_from_delay_record(&mut trip_id_map, &new, stopovers)
+    }
+
+    fn delay_events_between(
+        old: DelayRecord,
+        new: DelayRecord,
+        stopovers: Option<&[Stopover]>,
+    ) -> Vec<DelayEvent> {
+        try_delay_events_between(old, new, stopovers).unwrap()
+    }
+
+    #[test]
+    fn no_delay_event_across_gaps() {
+        let gap = MAX_DELAY_RECORD_GAP.whole_seconds();
+        assert_eq!(
+            delay_events_between(
+                delay_record(0, 0, 0, 1, 0.5, 0),
+                delay_record(1, gap + 1, 0, 1, 0.7, 60),
+                None
+            ),
+            vec![]
+        );
+        assert_eq!(
+            delay_events_between(
+                delay_record(0, 0, 0, 1, 0.5, 0),
+                delay_record(1, gap, 0, 1, 0.7, 60),
+                None
+            ),
+            vec![delay_event(gap / 2, gap, 0, 1, 0.6, 30, false)]
+        );
+    }
+
+    #[test]
+    fn station_pass_with_timetable() {
+        // The train arrives a minute late at station 1 and also leaves a minute late after
+        // waiting there for 100 seconds.
+        let stopovers = vec![
+            stopover(0, None, Some(-1000), 0),
+            stopover(1, Some(100), Some(200), 60),
+            stopover(2, Some(1000), None, 60),
+        ];
+
+        assert_eq!(
+            delay_events_between(
+                delay_record(0, 0, 0, 1, 0.5, 60),
+                delay_record(1, 400, 1, 2, 0.2, 120),
+                Some(&stopovers),
+            ),
+            vec![
+                delay_event(130, 260, 0, 1, 0.75, 79, false),
+                delay_event(330, 140, 1, 2, 0.1, 109, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn station_pass_while_waiting_in_station() {
+        let stopovers = vec![
+            stopover(0, None, Some(-1000), 0),
+            stopover(1, Some(-100), Some(100), 0),
+            stopover(2, Some(1000), None, 0),
+        ];
+
+        assert_eq!(
+            delay_events_between(
+                delay_record(0, 0, 0, 1, 1.0, 0),
+                delay_record(1, 300, 1, 2, 0.25, 30),
+                Some(&stopovers),
+            ),
+            vec![
+                delay_event(50, 100, 0, 1, 1.0, 5, false),
+                delay_event(200, 200, 1, 2, 0.125, 20, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn station_pass_without_timetable() {
+        assert_eq!(
+            delay_events_between(
+                delay_record(0, 0, 0, 1, 0.5, 0),
+                delay_record(1, 100, 1, 2, 0.5, 100),
+                None,
+            ),
+            vec![
+                delay_event(25, 50, 0, 1, 0.75, 25, false),
+                delay_event(75, 50, 1, 2, 0.25, 75, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolated_delay_events() {
+        let stopovers = vec![
+            stopover(0, None, Some(-100), 0),
+            stopover(1, Some(100), Some(200), 0),
+            stopover(2, Some(400), Some(500), 0),
+            stopover(3, Some(700), Some(800), 0),
+            stopover(4, Some(1200), None, 0),
+        ];
+
+        let old = delay_record(0, 0, 0, 1, 0.5, 0);
+        let new = delay_record(1, 1000, 3, 4, 0.5, 100);
+
+        // Without a timetable, we can't fill the gap.
+        assert_eq!(delay_events_between(old.clone(), new.clone(), None), vec![]);
+
+        assert_eq!(
+            delay_events_between(old, new, Some(&stopovers)),
+            vec![
+                delay_event(100, 200, 0, 1, 0.75, 10, true),
+                delay_event(350, 300, 1, 2, 0.5, 35, true),
+                delay_event(650, 300, 2, 3, 0.5, 65, true),
+                delay_event(900, 200, 3, 4, 0.25, 90, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_station_pass_is_rejected() {
+        // The crawler never produces such a DelayRecord, but rows in delay_records might be
+        // broken.
+        assert_eq!(
+            try_delay_events_between(
+                delay_record(0, 0, 0, 1, 1.5, 0),
+                delay_record(1, 100, 1, 2, 0.5, 0),
+                None,
+            ),
+            Err(ValidationError::RatioOutOfRange(f64::NEG_INFINITY))
+        );
+    }
 }
@@ -2,10 +2,12 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::models::DelayEvent;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use log::{error, info};
+use std::io::Write;
 
 /// Our progress bar template.
 pub fn progress_style() -> ProgressStyle {
@@ -96,3 +98,229 @@ pub fn validate_hafas_schema(db: &mut PgConnection) -> Result<(), Box<dyn std::e
         Ok(())
     }
 }
+
+/// Print all quarantined fetched_json rows as tab separated values.
+pub fn list_quarantine(db: &mut PgConnection) -> Result<(), Box<dyn std::error::Error>> {
+    let quarantined = crate::quarantine::load_quarantine(db)?;
+
+    for q in &quarantined {
+        println!("{}\t{}\t{}", q.fetched_json_id, q.quarantined_at, q.reason);
+    }
+    info!("{} rows in quarantine.", quarantined.len());
+
+    Ok(())
+}
+
+/// Measure how long it takes to serialize and compress `days` days of delay_events the way the
+/// web API does it, and how large the responses get.
+///
+/// Without a database, i.e. with `--synthetic`, the events of a generated timetable are used, see
+/// [synthetic_delay_events]. Those are the same on every run, so the numbers can be compared
+/// between versions and machines. Otherwise the latest days of delay_events in the database are
+/// used. Timings are only meaningful in a release build.
+pub fn benchmark_delay_events(
+    db: Option<&mut PgConnection>,
+    days: i64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::schema::delay_events;
+    use crate::web_utils::{send_json_array, Encoding};
+    use rocket::tokio::io::AsyncReadExt;
+    use std::time::Instant;
+
+    let start = Instant::now();
+    let events = match db {
+        Some(db) => {
+            let Some(latest) = delay_events::table
+                .select(diesel::dsl::max(delay_events::time))
+                .first::<Option<time::OffsetDateTime>>(db)?
+            else {
+                return Err("There are no delay events.".into());
+            };
+            delay_events::table
+                .select(DelayEvent::as_select())
+                .filter(delay_events::time.gt(latest - time::Duration::days(days)))
+                .load(db)?
+        }
+        None => synthetic_delay_events(days),
+    };
+    println!(
+        "Loaded {} delay events from {} to {} in {:.3?}",
+        events.len(),
+        events
+            .first()
+            .map_or(String::new(), |de| de.time.to_string()),
+        events
+            .last()
+            .map_or(String::new(), |de| de.time.to_string()),
+        start.elapsed()
+    );
+
+    let start = Instant::now();
+    let json = serde_json::to_vec(&events)?;
+    println!(
+        "serialize at once\t{} bytes\t{:.3?}",
+        json.len(),
+        start.elapsed()
+    );
+
+    let start = Instant::now();
+    let (tx, mut rx) = rocket::tokio::sync::mpsc::channel::<Vec<u8>>(16);
+    let receiver = std::thread::spawn(move || {
+        let mut chunks = 0;
+        while rx.blocking_recv().is_some() {
+            chunks += 1;
+        }
+        chunks
+    });
+    send_json_array(events.into_iter().map(Ok::<_, std::io::Error>), &tx)
+        .map_err(|e| e.to_string())?;
+    drop(tx);
+    let chunks = receiver.join().map_err(|_| "Receiving thread panicked")?;
+    println!(
+        "serialize in {} chunks\t{} bytes\t{:.3?}",
+        chunks,
+        json.len(),
+        start.elapsed()
+    );
+
+    let rt = rocket::tokio::runtime::Runtime::new()?;
+    for encoding in Encoding::ALL {
+        let start = Instant::now();
+        let mut compressed = Vec::new();
+        rt.block_on(
+            encoding
+                .encode(json.as_slice())
+                .read_to_end(&mut compressed),
+        )?;
+        println!(
+            "{}\t{} bytes ({:.1}%)\t{:.3?}",
+            encoding.as_str(),
+            compressed.len(),
+            100.0 * compressed.len() as f64 / json.len() as f64,
+            start.elapsed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Delay events of `days` days of an RE1-like timetable, starting on monday, 2023-11-13. Two
+/// trips per hour and direction run from 4 to 24 o'clock, each spending six minutes on each of its
+/// 39 segments with a DelayRecord every minute. That makes 131040 events a week. Delays follow a
+/// random walk with a fixed seed, so they are the same on every call.
+fn synthetic_delay_events(days: i64) -> Vec<DelayEvent> {
+    use crate::models::Direction;
+
+    const STATIONS: i64 = 40;
+    const MINUTES_PER_SEGMENT: i64 = 6;
+
+    let monday = time::OffsetDateTime::from_unix_timestamp(1_699_833_600).unwrap();
+    // xorshift64, as we neither need nor want real randomness here.
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut events = Vec::new();
+    let mut id = 0;
+    for day in 0..days {
+        for departure in (4 * 60..24 * 60).step_by(30) {
+            for direction in [Direction::Westwards, Direction::Eastwards] {
+                let train_number =
+                    3700 + departure / 30 * 2 + (direction == Direction::Eastwards) as i64;
+                let start = monday + time::Duration::days(day) + time::Duration::minutes(departure);
+                let trip_id = format!("RE1_{}_{}", train_number, start.unix_timestamp());
+                let mut delay: i64 = 0;
+                for minute in 0..(STATIONS - 1) * MINUTES_PER_SEGMENT {
+                    delay = (delay + (random() % 61) as i64 - 28).max(-60);
+                    let segment = minute / MINUTES_PER_SEGMENT;
+                    let (previous_station, next_station) = match direction {
+                        Direction::Westwards => (segment, segment + 1),
+                        Direction::Eastwards => (STATIONS - 1 - segment, STATIONS - 2 - segment),
+                    };
+                    events.push(DelayEvent {
+                        from_id: id,
+                        to_id: id + 1,
+                        trip_id: trip_id.clone(),
+                        time: start + time::Duration::seconds(minute * 60 + 30),
+                        duration: 60,
+                        previous_station: 900_000_000 + previous_station,
+                        next_station: 900_000_000 + next_station,
+                        percentage_segment: (minute % MINUTES_PER_SEGMENT) as f64
+                            / MINUTES_PER_SEGMENT as f64,
+                        delay,
+                        interpolated: false,
+                        has_realtime: true,
+                        direction: Some(direction),
+                    });
+                    id += 1;
+                }
+            }
+        }
+    }
+    events.sort_by_key(|de| de.time);
+    events
+}
+
+/// Export a table for a time range, given as unix timestamps, to a file or to stdout.
+pub fn export(
+    db: &mut PgConnection,
+    table: crate::export::ExportTable,
+    format: crate::export::ExportFormat,
+    from: i64,
+    to: Option<i64>,
+    output: Option<std::path::PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let from = time::OffsetDateTime::from_unix_timestamp(from)?;
+    let to = match to {
+        Some(to) => time::OffsetDateTime::from_unix_timestamp(to)?,
+        None => time::OffsetDateTime::now_utc(),
+    };
+    let count = match output {
+        Some(path) => {
+            let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+            crate::export::export(db, table, format, from, to, file)?
+        }
+        None => {
+            let stdout = std::io::BufWriter::new(std::io::stdout());
+            crate::export::export(db, table, format, from, to, stdout)?
+        }
+    };
+    info!("Exported {} rows of {}.", count, table.as_str());
+
+    Ok(())
+}
+
+/// Render a diagram of a time range to a file or stdout.
+pub fn render(
+    db: &mut PgConnection,
+    diagram: crate::render::Diagram,
+    format: crate::render::RenderFormat,
+    from: Option<i64>,
+    to: Option<i64>,
+    direction: Option<crate::models::Direction>,
+    output: Option<std::path::PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let to = match to {
+        Some(to) => time::OffsetDateTime::from_unix_timestamp(to)?,
+        None => time::OffsetDateTime::now_utc(),
+    };
+    let from = match from {
+        Some(from) => time::OffsetDateTime::from_unix_timestamp(from)?,
+        None => to - time::Duration::DAY,
+    };
+    if from >= to || to - from > crate::render::MAX_RENDER_RANGE {
+        return Err("The time range has to be positive and at most 7 days long.".into());
+    }
+    let image = crate::render::render(db, diagram, format, from, to, direction)?;
+    match output {
+        Some(path) => std::fs::write(path, image)?,
+        None => std::io::stdout().write_all(&image)?,
+    }
+    info!("Rendered the {} diagram.", diagram.as_str());
+
+    Ok(())
+}
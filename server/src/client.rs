@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Serves the built Elm client from a directory, so a single process is enough for local
+//! development and small deployments, without nginx in front.
+
+use crate::web_utils::{set_cache_headers, Conditional, Validators};
+use log::warn;
+use rocket::fs::NamedFile;
+use rocket::http::{ContentType, Status};
+use rocket::response::{self, Responder};
+use rocket::{get, routes, Request, Response, State};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Where the output of the client build lives, i.e. index.html and the assets directory, along
+/// with the ETags of its files.
+pub struct ClientDir {
+    path: PathBuf,
+    /// By path relative to the directory.
+    etags: HashMap<PathBuf, String>,
+}
+
+/// Served for every route of the client.
+const INDEX: &str = "index.html";
+
+/// Which file of the client answers a request path. Paths without an extension are routes of
+/// the client itself, so they get index.html. Requests for the API never fall back to the
+/// client, so unknown API paths stay a 404.
+fn target(path: &Path) -> Option<PathBuf> {
+    if path.starts_with("api") {
+        None
+    } else if path.extension().is_none() && !path.starts_with("assets") {
+        Some(PathBuf::from(INDEX))
+    } else {
+        Some(path.to_path_buf())
+    }
+}
+
+/// An ETag derived from the content of a file. The mtime can't be used for that, as it's the
+/// same for all files in the Nix store.
+fn content_etag(content: &[u8]) -> String {
+    format!("\"{:016x}\"", twox_hash::XxHash3_64::oneshot(content))
+}
+
+/// The ETags of all files below `dir`, by their path relative to `root`.
+fn content_etags(
+    root: &Path,
+    dir: &Path,
+    etags: &mut HashMap<PathBuf, String>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        // Follows symlinks, as the icons are linked into the assets directory.
+        if std::fs::metadata(&path)?.is_dir() {
+            content_etags(root, &path, etags)?;
+        } else {
+            let relative = path.strip_prefix(root).expect("Below root").to_path_buf();
+            etags.insert(relative, content_etag(&std::fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// A file of the client along with its caching headers. The client build doesn't put hashes into
+/// the names of its assets, e.g. index.html always loads /assets/Main.min.js. So none of them can
+/// be cached as immutable, and every file has to be revalidated, so a deployment reaches the
+/// clients right away.
+struct ClientFile {
+    file: NamedFile,
+    validators: Validators,
+    not_modified: bool,
+}
+
+impl<'r> Responder<'r, 'static> for ClientFile {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let is_manifest = self
+            .file
+            .path()
+            .extension()
+            .is_some_and(|ext| ext == "webmanifest");
+        let mut response = if self.not_modified {
+            Response::build().status(Status::NotModified).finalize()
+        } else {
+            self.file.respond_to(request)?
+        };
+        if is_manifest {
+            response.set_header(ContentType::new("application", "manifest+json"));
+        }
+        set_cache_headers(&mut response, Some(&self.validators));
+        Ok(response)
+    }
+}
+
+#[get("/<path..>", rank = 100)]
+async fn client_file(
+    path: PathBuf,
+    dir: &State<ClientDir>,
+    conditional: Conditional,
+) -> Option<ClientFile> {
+    let target = target(&path)?;
+    let validators = Validators {
+        etag: dir.etags.get(&target)?.clone(),
+        last_modified: None,
+        immutable: false,
+    };
+    Some(ClientFile {
+        not_modified: conditional.is_fresh(&validators),
+        file: NamedFile::open(dir.path.join(target)).await.ok()?,
+        validators,
+    })
+}
+
+/// The routes serving the client from `dir`, along with the state they need. The ETags of its
+/// files are computed once here, so only the files that exist now get served. After rebuilding the
+/// client, the server has to be restarted.
+pub fn client_routes(dir: PathBuf) -> (Vec<rocket::Route>, ClientDir) {
+    if !dir.join(INDEX).is_file() {
+        warn!("There is no {} in {}.", INDEX, dir.display());
+    }
+    let mut etags = HashMap::new();
+    if let Err(e) = content_etags(&dir, &dir, &mut etags) {
+        warn!("Unable to read the client from {}: {}", dir.display(), e);
+    }
+    (routes![client_file], ClientDir { path: dir, etags })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets() {
+        let target = |path: &str| target(Path::new(path)).map(|p| p.display().to_string());
+        assert_eq!(target(""), Some("index.html".to_string()));
+        assert_eq!(target("de/about"), Some("index.html".to_string()));
+        assert_eq!(
+            target("assets/style.css"),
+            Some("assets/style.css".to_string())
+        );
+        assert_eq!(target("assets/icons"), Some("assets/icons".to_string()));
+        assert_eq!(target("api/unknown"), None);
+        assert_eq!(target("api"), None);
+    }
+
+    #[test]
+    fn content_etags() {
+        let etag = content_etag(b"<!DOCTYPE html>");
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag, content_etag(b"<!DOCTYPE html>"));
+        assert_ne!(etag, content_etag(b"<!DOCTYPE html>\n"));
+        // Stable across builds and platforms, unlike std's DefaultHasher.
+        assert_eq!(content_etag(b""), "\"2d06800538d394c2\"");
+    }
+}
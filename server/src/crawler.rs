@@ -3,6 +3,8 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use crate::cache::{delay_events_from_delay_record, CacheState};
+use crate::health::Health;
+use crate::metrics::METRICS;
 use crate::models::*;
 use crate::transport_rest_vbb_v6::{deserialize, HafasMsg, TripOverview, TripsOverview};
 use bus::Bus;
@@ -19,11 +21,22 @@ const TRIPS_PATH: &str = "/trips";
 fn fetch_json_and_store_in_db(
     db: &mut PgConnection,
     url: String,
+    endpoint: &str,
     fetched_at: OffsetDateTime,
 ) -> Result<(i64, String), Box<dyn Error>> {
     use crate::schema::fetched_json;
 
-    let response_text = reqwest::blocking::get(url.clone())?.text()?;
+    let timer = METRICS
+        .fetch_duration
+        .with_label_values(&[endpoint])
+        .start_timer();
+    let response_text = reqwest::blocking::get(url.clone())
+        .and_then(|response| response.text())
+        .inspect_err(|_| {
+            METRICS.fetches.with_label_values(&["request_error"]).inc();
+        })?;
+    timer.observe_duration();
+    METRICS.fetched_bytes.inc_by(response_text.len() as u64);
     let fetched_json = FetchedJson {
         fetched_at,
         url,
@@ -38,6 +51,14 @@ fn fetch_json_and_store_in_db(
     }
 }
 
+/// Derived tables can be rebuilt from fetched_json at any time, so failing to update one of them
+/// is only logged and doesn't stop the crawler.
+fn log_derivation_error<T>(table: &str, result: QueryResult<T>) -> Option<T> {
+    result
+        .inspect_err(|e| error!("Unable to update {}: {}", table, e))
+        .ok()
+}
+
 fn hafas_base_url() -> String {
     std::env::var("HAFAS_BASE_URL").expect("HAFAS_BASE_URL must be set")
 }
@@ -54,6 +75,7 @@ pub fn crawler(
     db: &mut PgConnection,
     mut bus: Bus<DelayRecord>,
     mut cache_state: CacheState,
+    health: &Health,
 ) -> Result<(), Box<dyn Error>> {
     // It looks like, HAFAS is only cabable of showing new state every 30seconds anyway.
     let loop_interval = Duration::from_secs(30);
@@ -68,15 +90,20 @@ pub fn crawler(
                 hafas_base_url()
             );
             let fetched_at = OffsetDateTime::now_utc();
-            let (_, json) = match fetch_json_and_store_in_db(db, url, fetched_at) {
+            let (_, json) = match fetch_json_and_store_in_db(db, url, "overview", fetched_at) {
                 Ok(fj) => fj,
                 Err(e) => {
                     error!("{}", e);
                     continue;
                 }
             };
-            match deserialize(&json) {
-                Ok(HafasMsg::TripsOverview(res)) => res,
+            let msg = deserialize(&json);
+            METRICS.fetched(msg.as_ref().ok());
+            match msg {
+                Ok(HafasMsg::TripsOverview(res)) => {
+                    health.overview_fetched(fetched_at, res.trips.len());
+                    res
+                }
                 Ok(_) => {
                     error!("HafasMsg is not a TripsOverview");
                     continue;
@@ -98,14 +125,16 @@ pub fn crawler(
             let url = trips_url(&trip.id);
             info!("Fetching trip data from {}", url);
             let fetched_at = OffsetDateTime::now_utc();
-            let (row_id, json) = match fetch_json_and_store_in_db(db, url, fetched_at) {
+            let (row_id, json) = match fetch_json_and_store_in_db(db, url, "trip", fetched_at) {
                 Ok(fj) => fj,
                 Err(e) => {
                     error!("{}", e);
                     continue;
                 }
             };
-            let trip_overview: TripOverview = match deserialize(&json) {
+            let msg = deserialize(&json);
+            METRICS.fetched(msg.as_ref().ok());
+            let trip_overview: TripOverview = match msg {
                 Ok(HafasMsg::TripOverview(res)) => res,
                 Ok(_) => {
                     error!("HafasMsg is not a TripOverview");
@@ -117,26 +146,106 @@ pub fn crawler(
                 }
             };
 
-            let delay_record = delay_record_from_trip_overview(trip_overview, row_id, fetched_at);
+            health.trip_fetched(fetched_at);
+
+            log_derivation_error(
+                "trip_ids",
+                crate::trip_ids::record_trip_ids(
+                    db,
+                    &[TripIdMapping {
+                        hafas_trip_id: trip_overview.trip.id.clone(),
+                        canonical_trip_id: canonical_trip_id(&trip_overview.trip),
+                        fetched_json_id: row_id,
+                    }],
+                ),
+            );
+            log_derivation_error(
+                "trip_changes",
+                crate::trip_changes::record_trip_changes(
+                    db,
+                    row_id,
+                    fetched_at,
+                    &trip_overview.trip,
+                ),
+            );
+
+            if trip_overview
+                .trip
+                .stopovers
+                .iter()
+                .any(|so| !cache_state.route.knows(so.stop.id))
+            {
+                info!("Learning new stations from fetched_json row {}", row_id);
+                if let Some(route) =
+                    log_derivation_error("stations", crate::route::learn_stations(db, row_id))
+                {
+                    cache_state.route = route;
+                }
+            }
+
+            let stopovers: Vec<Stopover> = trip_overview
+                .trip
+                .stopovers
+                .iter()
+                .map(Stopover::from)
+                .collect();
+
+            let delay_record = delay_record_from_trip_overview(
+                trip_overview,
+                row_id,
+                fetched_at,
+                &cache_state.route,
+            );
             debug!("{:?}", delay_record);
             if let Some(delay_record) = delay_record {
+                METRICS.bus_broadcasts.inc();
+                METRICS
+                    .bus_queue_depth
+                    .add(health.subscriber_count() as i64);
                 bus.broadcast(delay_record.clone());
 
                 use crate::schema::delay_records;
                 diesel::insert_into(delay_records::table)
                     .values(&delay_record)
                     .execute(db)?;
+                METRICS.delay_records.inc();
 
-                let delay_events: Vec<DelayEvent> =
-                    delay_events_from_delay_record(&mut cache_state.trip_id_map, &delay_record);
+                log_derivation_error(
+                    "trip_summaries",
+                    crate::trip_summaries::refresh_trip_summary(db, &delay_record.trip_id),
+                );
+                log_derivation_error(
+                    "station_visits",
+                    crate::statistics::refresh_station_visits(db, &delay_record.trip_id),
+                );
+                log_derivation_error(
+                    "segment_delays",
+                    crate::segments::refresh_segment_delays(db, &delay_record.trip_id),
+                );
+
+                let delay_events: Vec<DelayEvent> = match delay_events_from_delay_record(
+                    &mut cache_state.trip_id_map,
+                    &delay_record,
+                    Some(&stopovers),
+                ) {
+                    Ok(des) => des,
+                    Err(e) => {
+                        crate::quarantine::quarantine(db, row_id, &e)?;
+                        continue;
+                    }
+                };
 
                 use crate::schema::delay_events;
                 diesel::insert_into(delay_events::table)
                     .values(&delay_events)
-                    .execute(db)
-                    .unwrap();
+                    .execute(db)?;
+                METRICS.delay_events.inc_by(delay_events.len() as u64);
             }
         }
+        log_derivation_error(
+            "station_punctuality",
+            crate::statistics::update_station_punctuality(db),
+        );
         sleep(next_execution - Instant::now());
     }
 }
@@ -0,0 +1,680 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Bulk export of delay data as CSV or Apache Parquet, e.g. for analysis with pandas or DuckDB.
+//!
+//! The column schemas of the exported tables are part of the API, so they are documented along
+//! with /api/export in [crate::web_api]. Keep both in sync.
+
+use crate::models::{DelayEvent, DelayRecordWithID, TripSummary};
+use diesel::pg::{PgConnection, PgRowByRowLoadingMode};
+use diesel::prelude::*;
+use parquet::basic::{Compression, LogicalType, Repetition, TimeUnit, Type as PhysicalType};
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::types::Type;
+use std::error::Error;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// How many rows go into one Parquet row group.
+const ROW_GROUP_SIZE: usize = 64 * 1024;
+
+/// The tables that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTable {
+    DelayRecords,
+    DelayEvents,
+    TripSummaries,
+}
+
+impl ExportTable {
+    pub const ALL: [ExportTable; 3] = [
+        ExportTable::DelayRecords,
+        ExportTable::DelayEvents,
+        ExportTable::TripSummaries,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportTable::DelayRecords => "delay_records",
+            ExportTable::DelayEvents => "delay_events",
+            ExportTable::TripSummaries => "trip_summaries",
+        }
+    }
+
+    fn columns(&self) -> &'static [Column] {
+        match self {
+            ExportTable::DelayRecords => DELAY_RECORDS_COLUMNS,
+            ExportTable::DelayEvents => DELAY_EVENTS_COLUMNS,
+            ExportTable::TripSummaries => TRIP_SUMMARIES_COLUMNS,
+        }
+    }
+
+    /// To check that the API docs list all columns.
+    #[cfg(test)]
+    pub fn column_names(&self) -> Vec<&'static str> {
+        self.columns().iter().map(|c| c.name).collect()
+    }
+}
+
+impl FromStr for ExportTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ExportTable::ALL
+            .into_iter()
+            .find(|table| table.as_str() == s)
+            .ok_or_else(|| format!("Unknown table: {}", s))
+    }
+}
+
+/// The file formats we can export to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    /// The usual file extension.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "parquet" => Ok(ExportFormat::Parquet),
+            _ => Err(format!("Unknown format: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Int,
+    Float,
+    Bool,
+    Text,
+    Time,
+}
+
+#[derive(Debug)]
+struct Column {
+    name: &'static str,
+    column_type: ColumnType,
+    nullable: bool,
+}
+
+const fn column(name: &'static str, column_type: ColumnType, nullable: bool) -> Column {
+    Column {
+        name,
+        column_type,
+        nullable,
+    }
+}
+
+const DELAY_RECORDS_COLUMNS: &[Column] = &[
+    column("id", ColumnType::Int, false),
+    column("fetched_json_id", ColumnType::Int, false),
+    column("trip_id", ColumnType::Text, false),
+    column("time", ColumnType::Time, false),
+    column("previous_station", ColumnType::Int, false),
+    column("next_station", ColumnType::Int, false),
+    column("percentage_segment", ColumnType::Float, false),
+    column("delay", ColumnType::Int, false),
+    column("arrival_delay", ColumnType::Int, true),
+    column("departure_delay", ColumnType::Int, true),
+    column("has_realtime", ColumnType::Bool, false),
+    column("trip_state", ColumnType::Text, false),
+    column("direction", ColumnType::Text, true),
+];
+
+const DELAY_EVENTS_COLUMNS: &[Column] = &[
+    column("id", ColumnType::Int, false),
+    column("from_id", ColumnType::Int, false),
+    column("to_id", ColumnType::Int, false),
+    column("trip_id", ColumnType::Text, false),
+    column("time", ColumnType::Time, false),
+    column("duration", ColumnType::Int, false),
+    column("previous_station", ColumnType::Int, false),
+    column("next_station", ColumnType::Int, false),
+    column("percentage_segment", ColumnType::Float, false),
+    column("delay", ColumnType::Int, false),
+    column("interpolated", ColumnType::Bool, false),
+    column("has_realtime", ColumnType::Bool, false),
+    column("direction", ColumnType::Text, true),
+];
+
+const TRIP_SUMMARIES_COLUMNS: &[Column] = &[
+    column("trip_id", ColumnType::Text, false),
+    column("line", ColumnType::Text, true),
+    column("train_number", ColumnType::Text, true),
+    column("direction", ColumnType::Text, true),
+    column("headsign", ColumnType::Text, true),
+    column("origin", ColumnType::Text, false),
+    column("destination", ColumnType::Text, false),
+    column("planned_departure", ColumnType::Time, false),
+    column("first_observation", ColumnType::Time, false),
+    column("last_observation", ColumnType::Time, false),
+    column("start_delay", ColumnType::Int, true),
+    column("final_delay", ColumnType::Int, true),
+    column("max_delay", ColumnType::Int, true),
+    column("stations_covered", ColumnType::Int, false),
+    column("cancelled", ColumnType::Bool, false),
+    column("cancelled_stops", ColumnType::Int, false),
+];
+
+/// One field of an exported row. The variant has to match the type of its column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(Option<i64>),
+    Float(Option<f64>),
+    Bool(Option<bool>),
+    Text(Option<String>),
+    Time(Option<OffsetDateTime>),
+}
+
+impl Value {
+    fn is_null(&self) -> bool {
+        match self {
+            Value::Int(v) => v.is_none(),
+            Value::Float(v) => v.is_none(),
+            Value::Bool(v) => v.is_none(),
+            Value::Text(v) => v.is_none(),
+            Value::Time(v) => v.is_none(),
+        }
+    }
+
+    fn to_csv(&self) -> Result<String, time::error::Format> {
+        Ok(match self {
+            Value::Int(v) => v.map(|v| v.to_string()).unwrap_or_default(),
+            Value::Float(v) => v.map(|v| v.to_string()).unwrap_or_default(),
+            Value::Bool(v) => v.map(|v| v.to_string()).unwrap_or_default(),
+            Value::Text(v) => v.clone().unwrap_or_default(),
+            Value::Time(v) => match v {
+                Some(v) => v.format(&Rfc3339)?,
+                None => String::new(),
+            },
+        })
+    }
+}
+
+fn delay_record_row(dr: DelayRecordWithID) -> Vec<Value> {
+    vec![
+        Value::Int(Some(dr.id)),
+        Value::Int(Some(dr.fetched_json_id)),
+        Value::Text(Some(dr.trip_id)),
+        Value::Time(Some(dr.time)),
+        Value::Int(Some(dr.previous_station)),
+        Value::Int(Some(dr.next_station)),
+        Value::Float(Some(dr.percentage_segment)),
+        Value::Int(Some(dr.delay)),
+        Value::Int(dr.arrival_delay),
+        Value::Int(dr.departure_delay),
+        Value::Bool(Some(dr.has_realtime)),
+        Value::Text(Some(dr.trip_state.as_str().to_string())),
+        Value::Text(dr.direction.map(|d| d.as_str().to_string())),
+    ]
+}
+
+fn delay_event_row((id, de): (i64, DelayEvent)) -> Vec<Value> {
+    vec![
+        Value::Int(Some(id)),
+        Value::Int(Some(de.from_id)),
+        Value::Int(Some(de.to_id)),
+        Value::Text(Some(de.trip_id)),
+        Value::Time(Some(de.time)),
+        Value::Int(Some(de.duration)),
+        Value::Int(Some(de.previous_station)),
+        Value::Int(Some(de.next_station)),
+        Value::Float(Some(de.percentage_segment)),
+        Value::Int(Some(de.delay)),
+        Value::Bool(Some(de.interpolated)),
+        Value::Bool(Some(de.has_realtime)),
+        Value::Text(de.direction.map(|d| d.as_str().to_string())),
+    ]
+}
+
+fn trip_summary_row(ts: TripSummary) -> Vec<Value> {
+    vec![
+        Value::Text(Some(ts.trip_id)),
+        Value::Text(ts.line),
+        Value::Text(ts.train_number),
+        Value::Text(ts.direction.map(|d| d.as_str().to_string())),
+        Value::Text(ts.headsign),
+        Value::Text(Some(ts.origin)),
+        Value::Text(Some(ts.destination)),
+        Value::Time(Some(ts.planned_departure)),
+        Value::Time(Some(ts.first_observation)),
+        Value::Time(Some(ts.last_observation)),
+        Value::Int(ts.start_delay),
+        Value::Int(ts.final_delay),
+        Value::Int(ts.max_delay),
+        Value::Int(Some(ts.stations_covered)),
+        Value::Bool(Some(ts.cancelled)),
+        Value::Int(Some(ts.cancelled_stops)),
+    ]
+}
+
+/// Something exported rows can be written to.
+trait Sink {
+    fn write_row(&mut self, row: Vec<Value>) -> Result<(), Box<dyn Error>>;
+    fn finish(self: Box<Self>) -> Result<(), Box<dyn Error>>;
+}
+
+struct CsvSink<W: Write> {
+    writer: csv::Writer<W>,
+}
+
+impl<W: Write> CsvSink<W> {
+    fn new(out: W, columns: &[Column]) -> Result<Self, Box<dyn Error>> {
+        let mut writer = csv::Writer::from_writer(out);
+        writer.write_record(columns.iter().map(|c| c.name))?;
+        Ok(CsvSink { writer })
+    }
+}
+
+impl<W: Write> Sink for CsvSink<W> {
+    fn write_row(&mut self, row: Vec<Value>) -> Result<(), Box<dyn Error>> {
+        let fields = row
+            .iter()
+            .map(Value::to_csv)
+            .collect::<Result<Vec<String>, _>>()?;
+        self.writer.write_record(fields)?;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), Box<dyn Error>> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Collects rows column by column and writes them as a row group once there are enough of them.
+struct ParquetSink<W: Write + Send> {
+    writer: SerializedFileWriter<W>,
+    columns: &'static [Column],
+    rows: Vec<Vec<Value>>,
+}
+
+impl<W: Write + Send> ParquetSink<W> {
+    fn new(out: W, columns: &'static [Column]) -> Result<Self, Box<dyn Error>> {
+        let fields = columns
+            .iter()
+            .map(|column| {
+                let (physical_type, logical_type) = match column.column_type {
+                    ColumnType::Int => (PhysicalType::INT64, None),
+                    ColumnType::Float => (PhysicalType::DOUBLE, None),
+                    ColumnType::Bool => (PhysicalType::BOOLEAN, None),
+                    ColumnType::Text => (PhysicalType::BYTE_ARRAY, Some(LogicalType::String)),
+                    ColumnType::Time => (
+                        PhysicalType::INT64,
+                        Some(LogicalType::Timestamp {
+                            is_adjusted_to_u_t_c: true,
+                            unit: TimeUnit::MICROS(Default::default()),
+                        }),
+                    ),
+                };
+                let repetition = if column.nullable {
+                    Repetition::OPTIONAL
+                } else {
+                    Repetition::REQUIRED
+                };
+                Type::primitive_type_builder(column.name, physical_type)
+                    .with_repetition(repetition)
+                    .with_logical_type(logical_type)
+                    .build()
+                    .map(Arc::new)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let schema = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(Default::default()))
+            .build();
+        Ok(ParquetSink {
+            writer: SerializedFileWriter::new(out, Arc::new(schema), Arc::new(properties))?,
+            columns,
+            rows: Vec::with_capacity(ROW_GROUP_SIZE),
+        })
+    }
+
+    fn write_row_group(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let mut row_group = self.writer.next_row_group()?;
+        for (i, column) in self.columns.iter().enumerate() {
+            let values = self.rows.iter().map(|row| &row[i]);
+            let def_levels: Vec<i16> = values.clone().map(|v| i16::from(!v.is_null())).collect();
+            let def_levels = column.nullable.then_some(def_levels.as_slice());
+            let mut writer = row_group
+                .next_column()?
+                .ok_or("Parquet schema has less columns than the export")?;
+            match column.column_type {
+                ColumnType::Int | ColumnType::Time => {
+                    let values: Vec<i64> = values
+                        .filter_map(|v| match v {
+                            Value::Int(v) => *v,
+                            Value::Time(v) => v.map(|t| (t.unix_timestamp_nanos() / 1000) as i64),
+                            _ => None,
+                        })
+                        .collect();
+                    writer
+                        .typed::<Int64Type>()
+                        .write_batch(&values, def_levels, None)?;
+                }
+                ColumnType::Float => {
+                    let values: Vec<f64> = values
+                        .filter_map(|v| match v {
+                            Value::Float(v) => *v,
+                            _ => None,
+                        })
+                        .collect();
+                    writer
+                        .typed::<DoubleType>()
+                        .write_batch(&values, def_levels, None)?;
+                }
+                ColumnType::Bool => {
+                    let values: Vec<bool> = values
+                        .filter_map(|v| match v {
+                            Value::Bool(v) => *v,
+                            _ => None,
+                        })
+                        .collect();
+                    writer
+                        .typed::<BoolType>()
+                        .write_batch(&values, def_levels, None)?;
+                }
+                ColumnType::Text => {
+                    let values: Vec<ByteArray> = values
+                        .filter_map(|v| match v {
+                            Value::Text(v) => v.as_deref().map(ByteArray::from),
+                            _ => None,
+                        })
+                        .collect();
+                    writer
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, def_levels, None)?;
+                }
+            }
+            writer.close()?;
+        }
+        row_group.close()?;
+        self.rows.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> Sink for ParquetSink<W> {
+    fn write_row(&mut self, row: Vec<Value>) -> Result<(), Box<dyn Error>> {
+        self.rows.push(row);
+        if self.rows.len() >= ROW_GROUP_SIZE {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<(), Box<dyn Error>> {
+        self.write_row_group()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// The rows of an exported table, with their values in the order of the table's columns.
+pub type Rows<'a> = Box<dyn Iterator<Item = QueryResult<Vec<Value>>> + 'a>;
+
+/// Load the rows of a table in a time range (`from` inclusive, `to` exclusive). The rows are
+/// streamed from the db, so this works for arbitrarily large time ranges.
+pub fn rows(
+    db: &mut PgConnection,
+    table: ExportTable,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+) -> QueryResult<Rows<'_>> {
+    Ok(match table {
+        ExportTable::DelayRecords => {
+            use crate::schema::delay_records;
+
+            Box::new(
+                delay_records::table
+                    .filter(delay_records::time.ge(from))
+                    .filter(delay_records::time.lt(to))
+                    .order((delay_records::time.asc(), delay_records::id.asc()))
+                    .load_iter::<DelayRecordWithID, PgRowByRowLoadingMode>(db)?
+                    .map(|dr| dr.map(delay_record_row)),
+            )
+        }
+        ExportTable::DelayEvents => {
+            use crate::schema::delay_events;
+
+            Box::new(
+                delay_events::table
+                    .select((delay_events::id, DelayEvent::as_select()))
+                    .filter(delay_events::time.ge(from))
+                    .filter(delay_events::time.lt(to))
+                    .order((delay_events::time.asc(), delay_events::id.asc()))
+                    .load_iter::<(i64, DelayEvent), PgRowByRowLoadingMode>(db)?
+                    .map(|de| de.map(delay_event_row)),
+            )
+        }
+        ExportTable::TripSummaries => {
+            use crate::schema::trip_summaries;
+
+            Box::new(
+                trip_summaries::table
+                    .filter(trip_summaries::planned_departure.ge(from))
+                    .filter(trip_summaries::planned_departure.lt(to))
+                    .order(trip_summaries::planned_departure.asc())
+                    .load_iter::<TripSummary, PgRowByRowLoadingMode>(db)?
+                    .map(|ts| ts.map(trip_summary_row)),
+            )
+        }
+    })
+}
+
+/// Write the rows of a table to `out`. Returns the number of written rows.
+pub fn write_rows(
+    table: ExportTable,
+    format: ExportFormat,
+    rows: impl Iterator<Item = QueryResult<Vec<Value>>>,
+    out: impl Write + Send,
+) -> Result<usize, Box<dyn Error>> {
+    let columns = table.columns();
+    let mut sink: Box<dyn Sink + '_> = match format {
+        ExportFormat::Csv => Box::new(CsvSink::new(out, columns)?),
+        ExportFormat::Parquet => Box::new(ParquetSink::new(out, columns)?),
+    };
+
+    let mut count = 0;
+    for row in rows {
+        sink.write_row(row?)?;
+        count += 1;
+    }
+    sink.finish()?;
+
+    Ok(count)
+}
+
+/// Write the rows of a table in a time range (`from` inclusive, `to` exclusive) to `out`. Returns
+/// the number of exported rows.
+pub fn export(
+    db: &mut PgConnection,
+    table: ExportTable,
+    format: ExportFormat,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    out: impl Write + Send,
+) -> Result<usize, Box<dyn Error>> {
+    write_rows(table, format, rows(db, table, from, to)?, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Direction;
+
+    fn summary() -> TripSummary {
+        TripSummary {
+            trip_id: "RE1_3709_20230701T0900".to_string(),
+            line: Some("RE1".to_string()),
+            train_number: Some("3709".to_string()),
+            direction: Some(Direction::Eastwards),
+            headsign: Some("Frankfurt (Oder)".to_string()),
+            origin: "Magdeburg, Hauptbahnhof".to_string(),
+            destination: "Frankfurt (Oder), Bahnhof".to_string(),
+            planned_departure: OffsetDateTime::from_unix_timestamp(1688194800).unwrap(),
+            first_observation: OffsetDateTime::from_unix_timestamp(1688194800).unwrap(),
+            last_observation: OffsetDateTime::from_unix_timestamp(1688204800).unwrap(),
+            start_delay: Some(60),
+            final_delay: None,
+            max_delay: Some(180),
+            stations_covered: 30,
+            cancelled: false,
+            cancelled_stops: 0,
+            last_fetched_json_id: 42,
+        }
+    }
+
+    #[test]
+    fn rows_match_columns() {
+        for (table, row) in [
+            (ExportTable::TripSummaries, trip_summary_row(summary())),
+            (
+                ExportTable::DelayEvents,
+                delay_event_row((
+                    1,
+                    DelayEvent {
+                        from_id: 1,
+                        to_id: 2,
+                        trip_id: "t".to_string(),
+                        time: OffsetDateTime::UNIX_EPOCH,
+                        duration: 60,
+                        previous_station: 1,
+                        next_station: 2,
+                        percentage_segment: 0.5,
+                        delay: 60,
+                        interpolated: false,
+                        has_realtime: true,
+                        direction: None,
+                    },
+                )),
+            ),
+        ] {
+            let columns = table.columns();
+            assert_eq!(row.len(), columns.len(), "{:?}", table);
+            for (value, column) in row.iter().zip(columns) {
+                let column_type = match value {
+                    Value::Int(_) => ColumnType::Int,
+                    Value::Float(_) => ColumnType::Float,
+                    Value::Bool(_) => ColumnType::Bool,
+                    Value::Text(_) => ColumnType::Text,
+                    Value::Time(_) => ColumnType::Time,
+                };
+                assert_eq!(column_type, column.column_type, "{}", column.name);
+                assert!(column.nullable || !value.is_null(), "{}", column.name);
+            }
+        }
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut out = Vec::new();
+        let rows = std::iter::once(Ok(trip_summary_row(summary())));
+        let count = write_rows(
+            ExportTable::TripSummaries,
+            ExportFormat::Csv,
+            rows,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+
+        let csv = String::from_utf8(out).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("trip_id,line,train_number,direction,headsign,"));
+        assert_eq!(
+            lines.next().unwrap(),
+            r#"RE1_3709_20230701T0900,RE1,3709,eastwards,Frankfurt (Oder),"Magdeburg, Hauptbahnhof","Frankfurt (Oder), Bahnhof",2023-07-01T07:00:00Z,2023-07-01T07:00:00Z,2023-07-01T09:46:40Z,60,,180,30,false,0"#
+        );
+    }
+
+    #[test]
+    fn parquet_export() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use parquet::record::Field;
+
+        let mut out = Vec::new();
+        let rows = std::iter::once(Ok(trip_summary_row(summary())));
+        let count = write_rows(
+            ExportTable::TripSummaries,
+            ExportFormat::Parquet,
+            rows,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+
+        let reader = SerializedFileReader::new(bytes::Bytes::from(out)).unwrap();
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        let fields: Vec<(&str, &Field)> = rows[0]
+            .get_column_iter()
+            .map(|(name, field)| (name.as_str(), field))
+            .collect();
+        let text = |s: &str| Field::Str(s.to_string());
+        assert_eq!(
+            fields,
+            vec![
+                ("trip_id", &text("RE1_3709_20230701T0900")),
+                ("line", &text("RE1")),
+                ("train_number", &text("3709")),
+                ("direction", &text("eastwards")),
+                ("headsign", &text("Frankfurt (Oder)")),
+                ("origin", &text("Magdeburg, Hauptbahnhof")),
+                ("destination", &text("Frankfurt (Oder), Bahnhof")),
+                (
+                    "planned_departure",
+                    &Field::TimestampMicros(1688194800000000)
+                ),
+                (
+                    "first_observation",
+                    &Field::TimestampMicros(1688194800000000)
+                ),
+                (
+                    "last_observation",
+                    &Field::TimestampMicros(1688204800000000)
+                ),
+                ("start_delay", &Field::Long(60)),
+                ("final_delay", &Field::Null),
+                ("max_delay", &Field::Long(180)),
+                ("stations_covered", &Field::Long(30)),
+                ("cancelled", &Field::Bool(false)),
+                ("cancelled_stops", &Field::Long(0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,543 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! A GTFS-Realtime feed built from the latest DelayRecord of every active trip, so tools like
+//! OpenTripPlanner can consume our data.
+//!
+//! The messages below are a hand written subset of
+//! <https://gtfs.org/realtime/proto/>, which saves us from running protoc at build time. Field
+//! names and tags must match the official gtfs-realtime.proto.
+//!
+//! Trips are identified by their canonical trip_id, stops by their HAFAS station id. Neither
+//! matches any static GTFS feed out of the box, so consumers have to map them themselves.
+
+use crate::models::{DelayRecord, Direction, TripState};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use serde::{Serialize, Serializer};
+use serde_with::skip_serializing_none;
+use std::collections::HashMap;
+use time::{Duration, OffsetDateTime};
+
+pub const GTFS_REALTIME_VERSION: &str = "2.0";
+
+/// A trip is considered active, if its latest DelayRecord isn't older than this. The crawler
+/// visits every running trip about twice a minute.
+const ACTIVE_WINDOW: Duration = Duration::minutes(10);
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct FeedMessage {
+    #[prost(message, required, tag = "1")]
+    pub header: FeedHeader,
+    #[prost(message, repeated, tag = "2")]
+    pub entity: Vec<FeedEntity>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct FeedHeader {
+    #[prost(string, required, tag = "1")]
+    pub gtfs_realtime_version: String,
+    #[prost(enumeration = "Incrementality", optional, tag = "2")]
+    #[serde(serialize_with = "enum_name::<Incrementality, _>")]
+    pub incrementality: Option<i32>,
+    #[prost(uint64, optional, tag = "3")]
+    pub timestamp: Option<u64>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct FeedEntity {
+    #[prost(string, required, tag = "1")]
+    pub id: String,
+    #[prost(message, optional, tag = "3")]
+    pub trip_update: Option<TripUpdate>,
+    #[prost(message, optional, tag = "4")]
+    pub vehicle: Option<VehiclePosition>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct TripUpdate {
+    #[prost(message, required, tag = "1")]
+    pub trip: TripDescriptor,
+    #[prost(message, repeated, tag = "2")]
+    pub stop_time_update: Vec<StopTimeUpdate>,
+    #[prost(message, optional, tag = "3")]
+    pub vehicle: Option<VehicleDescriptor>,
+    #[prost(uint64, optional, tag = "4")]
+    pub timestamp: Option<u64>,
+    #[prost(int32, optional, tag = "5")]
+    pub delay: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct StopTimeUpdate {
+    #[prost(message, optional, tag = "2")]
+    pub arrival: Option<StopTimeEvent>,
+    #[prost(message, optional, tag = "3")]
+    pub departure: Option<StopTimeEvent>,
+    #[prost(string, optional, tag = "4")]
+    pub stop_id: Option<String>,
+    #[prost(enumeration = "StopScheduleRelationship", optional, tag = "5")]
+    #[serde(serialize_with = "enum_name::<StopScheduleRelationship, _>")]
+    pub schedule_relationship: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct StopTimeEvent {
+    #[prost(int32, optional, tag = "1")]
+    pub delay: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct VehiclePosition {
+    #[prost(message, optional, tag = "1")]
+    pub trip: Option<TripDescriptor>,
+    #[prost(message, optional, tag = "2")]
+    pub position: Option<Position>,
+    #[prost(enumeration = "VehicleStopStatus", optional, tag = "4")]
+    #[serde(serialize_with = "enum_name::<VehicleStopStatus, _>")]
+    pub current_status: Option<i32>,
+    #[prost(uint64, optional, tag = "5")]
+    pub timestamp: Option<u64>,
+    #[prost(string, optional, tag = "7")]
+    pub stop_id: Option<String>,
+    #[prost(message, optional, tag = "8")]
+    pub vehicle: Option<VehicleDescriptor>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct Position {
+    #[prost(float, required, tag = "1")]
+    pub latitude: f32,
+    #[prost(float, required, tag = "2")]
+    pub longitude: f32,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct TripDescriptor {
+    #[prost(string, optional, tag = "1")]
+    pub trip_id: Option<String>,
+    #[prost(enumeration = "TripScheduleRelationship", optional, tag = "4")]
+    #[serde(serialize_with = "enum_name::<TripScheduleRelationship, _>")]
+    pub schedule_relationship: Option<i32>,
+    #[prost(string, optional, tag = "5")]
+    pub route_id: Option<String>,
+    #[prost(uint32, optional, tag = "6")]
+    pub direction_id: Option<u32>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, prost::Message, Serialize)]
+pub struct VehicleDescriptor {
+    #[prost(string, optional, tag = "1")]
+    pub id: Option<String>,
+    #[prost(string, optional, tag = "2")]
+    pub label: Option<String>,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration, Serialize,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[repr(i32)]
+pub enum Incrementality {
+    FullDataset = 0,
+    Differential = 1,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration, Serialize,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[repr(i32)]
+pub enum StopScheduleRelationship {
+    Scheduled = 0,
+    Skipped = 1,
+    NoData = 2,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration, Serialize,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[repr(i32)]
+pub enum TripScheduleRelationship {
+    Scheduled = 0,
+    Canceled = 3,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration, Serialize,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[repr(i32)]
+pub enum VehicleStopStatus {
+    IncomingAt = 0,
+    StoppedAt = 1,
+    InTransitTo = 2,
+}
+
+/// Serialize protobuf enum fields by their name, like the JSON mapping of protobuf does. Absent
+/// fields are left out for the same reason.
+fn enum_name<E, S>(value: &Option<i32>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    E: TryFrom<i32> + Serialize,
+    S: Serializer,
+{
+    value
+        .and_then(|v| E::try_from(v).ok())
+        .serialize(serializer)
+}
+
+/// What we know about a trip besides its DelayRecord's.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TripInfo {
+    pub line: Option<String>,
+    pub train_number: Option<String>,
+    pub cancelled: bool,
+}
+
+fn timestamp(t: OffsetDateTime) -> Option<u64> {
+    u64::try_from(t.unix_timestamp()).ok()
+}
+
+fn stop_time_event(delay: Option<i64>) -> Option<StopTimeEvent> {
+    delay.map(|d| StopTimeEvent {
+        delay: Some(d as i32),
+    })
+}
+
+fn trip_descriptor(dr: &DelayRecord, info: &TripInfo) -> TripDescriptor {
+    TripDescriptor {
+        trip_id: Some(dr.trip_id.clone()),
+        schedule_relationship: Some(if info.cancelled {
+            TripScheduleRelationship::Canceled
+        } else {
+            TripScheduleRelationship::Scheduled
+        } as i32),
+        route_id: info
+            .line
+            .as_ref()
+            .map(|l| l.replace(char::is_whitespace, "")),
+        direction_id: dr.direction.map(|d| match d {
+            Direction::Westwards => 0,
+            Direction::Eastwards => 1,
+        }),
+    }
+}
+
+fn vehicle_descriptor(info: &TripInfo) -> Option<VehicleDescriptor> {
+    info.train_number.as_ref().map(|nr| VehicleDescriptor {
+        id: None,
+        label: Some(match &info.line {
+            Some(line) => format!("{line} ({nr})"),
+            None => nr.clone(),
+        }),
+    })
+}
+
+/// The delays we know from one DelayRecord, which are at most the departure from its previous
+/// station and the arrival at and departure from its next station.
+fn stop_time_updates(dr: &DelayRecord) -> Vec<StopTimeUpdate> {
+    if !dr.has_realtime {
+        return vec![StopTimeUpdate {
+            stop_id: Some(dr.next_station.to_string()),
+            schedule_relationship: Some(StopScheduleRelationship::NoData as i32),
+            ..Default::default()
+        }];
+    }
+    let waiting = dr.trip_state == TripState::EnRoute && dr.percentage_segment >= 1.0;
+    let mut updates = Vec::new();
+    if !waiting {
+        updates.push(StopTimeUpdate {
+            stop_id: Some(dr.previous_station.to_string()),
+            departure: stop_time_event(dr.departure_delay),
+            schedule_relationship: Some(StopScheduleRelationship::Scheduled as i32),
+            ..Default::default()
+        });
+    }
+    if dr.trip_state != TripState::AtOrigin {
+        updates.push(StopTimeUpdate {
+            stop_id: Some(dr.next_station.to_string()),
+            arrival: stop_time_event(dr.arrival_delay),
+            departure: stop_time_event(if waiting { dr.departure_delay } else { None }),
+            schedule_relationship: Some(StopScheduleRelationship::Scheduled as i32),
+        });
+    }
+    updates.retain(|u| u.arrival.is_some() || u.departure.is_some());
+    updates
+}
+
+/// The position of the train, linearly interpolated between the coordinates of its previous and
+/// next station.
+fn position(dr: &DelayRecord, coordinates: &HashMap<i64, (f64, f64)>) -> Option<Position> {
+    let (lat1, lon1) = coordinates.get(&dr.previous_station)?;
+    let (lat2, lon2) = coordinates.get(&dr.next_station)?;
+    let p = dr.percentage_segment;
+    Some(Position {
+        latitude: (lat1 + (lat2 - lat1) * p) as f32,
+        longitude: (lon1 + (lon2 - lon1) * p) as f32,
+    })
+}
+
+fn vehicle_position(
+    dr: &DelayRecord,
+    info: &TripInfo,
+    coordinates: &HashMap<i64, (f64, f64)>,
+) -> VehiclePosition {
+    let (status, stop) = match dr.trip_state {
+        TripState::AtOrigin => (VehicleStopStatus::StoppedAt, dr.previous_station),
+        TripState::AtTerminus => (VehicleStopStatus::StoppedAt, dr.next_station),
+        TripState::EnRoute if dr.percentage_segment >= 1.0 => {
+            (VehicleStopStatus::StoppedAt, dr.next_station)
+        }
+        TripState::EnRoute => (VehicleStopStatus::InTransitTo, dr.next_station),
+    };
+    VehiclePosition {
+        trip: Some(trip_descriptor(dr, info)),
+        position: position(dr, coordinates),
+        current_status: Some(status as i32),
+        timestamp: timestamp(dr.time),
+        stop_id: Some(stop.to_string()),
+        vehicle: vehicle_descriptor(info),
+    }
+}
+
+/// One FeedEntity per trip, carrying both its TripUpdate and its VehiclePosition.
+pub fn feed_entity(
+    dr: &DelayRecord,
+    info: &TripInfo,
+    coordinates: &HashMap<i64, (f64, f64)>,
+) -> FeedEntity {
+    FeedEntity {
+        id: dr.trip_id.clone(),
+        trip_update: Some(TripUpdate {
+            trip: trip_descriptor(dr, info),
+            stop_time_update: stop_time_updates(dr),
+            vehicle: vehicle_descriptor(info),
+            timestamp: timestamp(dr.time),
+            delay: dr.has_realtime.then_some(dr.delay as i32),
+        }),
+        vehicle: Some(vehicle_position(dr, info, coordinates)),
+    }
+}
+
+/// The full feed as of `at`, only taking fetched_json rows up to `known_at` into account.
+/// Trips which arrived at their terminus are left out.
+pub fn feed_message(
+    db: &mut PgConnection,
+    at: OffsetDateTime,
+    known_at: i64,
+) -> QueryResult<FeedMessage> {
+    use crate::schema::{delay_records, stations, trip_summaries};
+
+    let delay_records: Vec<DelayRecord> = delay_records::table
+        .select((
+            delay_records::fetched_json_id,
+            delay_records::trip_id,
+            delay_records::time,
+            delay_records::previous_station,
+            delay_records::next_station,
+            delay_records::percentage_segment,
+            delay_records::delay,
+            delay_records::arrival_delay,
+            delay_records::departure_delay,
+            delay_records::has_realtime,
+            delay_records::trip_state,
+            delay_records::direction,
+        ))
+        .filter(delay_records::time.gt(at - ACTIVE_WINDOW))
+        .filter(delay_records::time.le(at))
+        .filter(delay_records::fetched_json_id.le(known_at))
+        .distinct_on(delay_records::trip_id)
+        .order_by((delay_records::trip_id, delay_records::time.desc()))
+        .load::<DelayRecord>(db)?
+        .into_iter()
+        .filter(|dr| dr.trip_state != TripState::AtTerminus)
+        .collect();
+
+    let trip_infos: HashMap<String, TripInfo> = trip_summaries::table
+        .select((
+            trip_summaries::trip_id,
+            trip_summaries::line,
+            trip_summaries::train_number,
+            trip_summaries::cancelled,
+        ))
+        .filter(trip_summaries::trip_id.eq_any(delay_records.iter().map(|dr| &dr.trip_id)))
+        .load::<(String, Option<String>, Option<String>, bool)>(db)?
+        .into_iter()
+        .map(|(trip_id, line, train_number, cancelled)| {
+            (
+                trip_id,
+                TripInfo {
+                    line,
+                    train_number,
+                    cancelled,
+                },
+            )
+        })
+        .collect();
+
+    let coordinates: HashMap<i64, (f64, f64)> = stations::table
+        .select((stations::id, stations::latitude, stations::longitude))
+        .load::<(i64, Option<f64>, Option<f64>)>(db)?
+        .into_iter()
+        .filter_map(|(id, lat, lon)| Some((id, (lat?, lon?))))
+        .collect();
+
+    Ok(FeedMessage {
+        header: FeedHeader {
+            gtfs_realtime_version: GTFS_REALTIME_VERSION.to_string(),
+            incrementality: Some(Incrementality::FullDataset as i32),
+            timestamp: timestamp(at),
+        },
+        entity: delay_records
+            .iter()
+            .map(|dr| {
+                feed_entity(
+                    dr,
+                    trip_infos.get(&dr.trip_id).unwrap_or(&TripInfo::default()),
+                    &coordinates,
+                )
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    fn delay_record(trip_state: TripState, percentage_segment: f64) -> DelayRecord {
+        DelayRecord {
+            fetched_json_id: 1,
+            trip_id: "RE1_3709_20231115T0900".to_string(),
+            time: OffsetDateTime::from_unix_timestamp(1_700_035_200).unwrap(),
+            previous_station: 1,
+            next_station: 2,
+            percentage_segment,
+            delay: 120,
+            arrival_delay: Some(120),
+            departure_delay: Some(60),
+            has_realtime: true,
+            trip_state,
+            direction: Some(Direction::Westwards),
+        }
+    }
+
+    fn info() -> TripInfo {
+        TripInfo {
+            line: Some("RE 1".to_string()),
+            train_number: Some("3709".to_string()),
+            cancelled: false,
+        }
+    }
+
+    #[test]
+    fn en_route() {
+        let coordinates = HashMap::from([(1, (52.0, 13.0)), (2, (52.5, 12.0))]);
+        let entity = feed_entity(
+            &delay_record(TripState::EnRoute, 0.5),
+            &info(),
+            &coordinates,
+        );
+
+        let trip_update = entity.trip_update.unwrap();
+        assert_eq!(trip_update.trip.route_id.as_deref(), Some("RE1"));
+        assert_eq!(trip_update.delay, Some(120));
+        assert_eq!(
+            trip_update
+                .stop_time_update
+                .iter()
+                .map(|u| (
+                    u.stop_id.as_deref().unwrap(),
+                    u.arrival.as_ref().and_then(|a| a.delay),
+                    u.departure.as_ref().and_then(|d| d.delay)
+                ))
+                .collect::<Vec<_>>(),
+            vec![("1", None, Some(60)), ("2", Some(120), None)]
+        );
+
+        let vehicle = entity.vehicle.unwrap();
+        assert_eq!(vehicle.current_status(), VehicleStopStatus::InTransitTo);
+        assert_eq!(vehicle.stop_id.as_deref(), Some("2"));
+        assert_eq!(
+            vehicle.position,
+            Some(Position {
+                latitude: 52.25,
+                longitude: 12.5
+            })
+        );
+    }
+
+    #[test]
+    fn waiting_in_station() {
+        let entity = feed_entity(
+            &delay_record(TripState::EnRoute, 1.0),
+            &info(),
+            &HashMap::new(),
+        );
+
+        let trip_update = entity.trip_update.unwrap();
+        assert_eq!(trip_update.stop_time_update.len(), 1);
+        assert_eq!(
+            trip_update.stop_time_update[0].stop_id.as_deref(),
+            Some("2")
+        );
+        let vehicle = entity.vehicle.unwrap();
+        assert_eq!(vehicle.current_status(), VehicleStopStatus::StoppedAt);
+        assert_eq!(vehicle.position, None);
+    }
+
+    #[test]
+    fn without_realtime() {
+        let mut dr = delay_record(TripState::AtOrigin, 0.0);
+        dr.has_realtime = false;
+        let entity = feed_entity(&dr, &info(), &HashMap::new());
+
+        let trip_update = entity.trip_update.unwrap();
+        assert_eq!(trip_update.delay, None);
+        assert_eq!(
+            trip_update.stop_time_update[0].schedule_relationship(),
+            StopScheduleRelationship::NoData
+        );
+        assert_eq!(entity.vehicle.unwrap().stop_id.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn protobuf_and_json() {
+        let feed = FeedMessage {
+            header: FeedHeader {
+                gtfs_realtime_version: GTFS_REALTIME_VERSION.to_string(),
+                incrementality: Some(Incrementality::FullDataset as i32),
+                timestamp: Some(1_700_035_200),
+            },
+            entity: vec![feed_entity(
+                &delay_record(TripState::EnRoute, 0.5),
+                &info(),
+                &HashMap::new(),
+            )],
+        };
+
+        let bytes = feed.encode_to_vec();
+        assert_eq!(FeedMessage::decode(&bytes[..]).unwrap(), feed);
+
+        let json = serde_json::to_value(&feed).unwrap();
+        assert_eq!(json["header"]["incrementality"], "FULL_DATASET");
+        assert_eq!(
+            json["entity"][0]["vehicle"]["current_status"],
+            "IN_TRANSIT_TO"
+        );
+        assert!(json["entity"][0]["vehicle"].get("position").is_none());
+    }
+}
@@ -0,0 +1,278 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! What the crawler and the servers report about themselves, so /healthz, /readyz and
+//! /api/status can tell whether everything is still running.
+
+use crate::metrics::METRICS;
+use serde::Serialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use time::{Duration, OffsetDateTime};
+use utoipa::ToSchema;
+
+/// Shared between the crawler, the websocket server and the webserver.
+pub struct Health {
+    started_at: OffsetDateTime,
+    /// The crawler counts as stale, if it didn't fetch the trips overview for this long.
+    stale_after: Duration,
+    crawler: Mutex<CrawlerHealth>,
+    websocket_subscribers: AtomicUsize,
+    sse_subscribers: AtomicUsize,
+}
+
+#[derive(Default, Clone)]
+struct CrawlerHealth {
+    caches_ready_at: Option<OffsetDateTime>,
+    last_overview_fetch: Option<OffsetDateTime>,
+    last_trip_fetch: Option<OffsetDateTime>,
+    active_trips: usize,
+}
+
+/// The kinds of live subscribers we count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriberKind {
+    Websocket,
+    Sse,
+}
+
+impl SubscriberKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SubscriberKind::Websocket => "websocket",
+            SubscriberKind::Sse => "sse",
+        }
+    }
+}
+
+/// Counts as a subscriber as long as it's alive.
+pub struct Subscription {
+    health: Arc<Health>,
+    kind: SubscriberKind,
+    /// How many DelayRecord's were broadcasted before we subscribed.
+    broadcasts_before: u64,
+    received: u64,
+}
+
+impl Subscription {
+    /// Account for a DelayRecord received from the bus.
+    pub fn received(&mut self) {
+        self.received += 1;
+        METRICS.bus_queue_depth.dec();
+    }
+
+    /// Account for a DelayRecord sent to the subscriber.
+    pub fn sent(&self) {
+        METRICS
+            .messages_sent
+            .with_label_values(&[self.kind.as_str()])
+            .inc();
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.health
+            .subscribers(self.kind)
+            .fetch_sub(1, Ordering::Relaxed);
+        METRICS
+            .subscribers
+            .with_label_values(&[self.kind.as_str()])
+            .dec();
+        // Whatever we didn't receive is dropped with our receiver.
+        let unread = METRICS
+            .bus_broadcasts
+            .get()
+            .saturating_sub(self.broadcasts_before + self.received);
+        METRICS.bus_queue_depth.sub(unread as i64);
+    }
+}
+
+/// The status of the whole server, as sent by /api/status.
+#[derive(Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct Status {
+    pub version: &'static str,
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub started_at: OffsetDateTime,
+    /// Whether the database can be queried.
+    pub database: bool,
+    /// When the cache tables were brought up to date after startup. Until then, the crawler
+    /// doesn't run.
+    #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub caches_ready_at: Option<OffsetDateTime>,
+    /// When the overview of the running trips was fetched and understood the last time.
+    #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub last_overview_fetch: Option<OffsetDateTime>,
+    /// When a single trip was fetched and understood the last time.
+    #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub last_trip_fetch: Option<OffsetDateTime>,
+    /// Number of trips in the latest trips overview.
+    pub active_trips: usize,
+    /// Whether the crawler didn't fetch the trips overview for too long.
+    pub crawler_stale: bool,
+    pub websocket_subscribers: usize,
+    pub sse_subscribers: usize,
+}
+
+impl Status {
+    /// Why the server isn't ready to serve up to date data, if it isn't.
+    pub fn not_ready_reasons(&self) -> Vec<&'static str> {
+        let mut reasons = Vec::new();
+        if !self.database {
+            reasons.push("database unreachable");
+        }
+        if self.caches_ready_at.is_none() {
+            reasons.push("caches not ready");
+        }
+        if self.crawler_stale {
+            reasons.push("crawler stale");
+        }
+        reasons
+    }
+}
+
+impl Health {
+    pub fn new(stale_after: Duration) -> Health {
+        Health {
+            started_at: OffsetDateTime::now_utc(),
+            stale_after,
+            crawler: Mutex::new(CrawlerHealth::default()),
+            websocket_subscribers: AtomicUsize::new(0),
+            sse_subscribers: AtomicUsize::new(0),
+        }
+    }
+
+    fn crawler(&self) -> std::sync::MutexGuard<'_, CrawlerHealth> {
+        self.crawler.lock().expect("Poisoned crawler health")
+    }
+
+    fn subscribers(&self, kind: SubscriberKind) -> &AtomicUsize {
+        match kind {
+            SubscriberKind::Websocket => &self.websocket_subscribers,
+            SubscriberKind::Sse => &self.sse_subscribers,
+        }
+    }
+
+    pub fn caches_ready(&self) {
+        self.crawler().caches_ready_at = Some(OffsetDateTime::now_utc());
+    }
+
+    /// Until the cache tables are brought up to date after startup, everything derived from them
+    /// might be incomplete.
+    pub fn caches_are_ready(&self) -> bool {
+        self.crawler().caches_ready_at.is_some()
+    }
+
+    pub fn overview_fetched(&self, fetched_at: OffsetDateTime, active_trips: usize) {
+        let mut crawler = self.crawler();
+        crawler.last_overview_fetch = Some(fetched_at);
+        crawler.active_trips = active_trips;
+    }
+
+    pub fn trip_fetched(&self, fetched_at: OffsetDateTime) {
+        self.crawler().last_trip_fetch = Some(fetched_at);
+    }
+
+    pub fn subscribe(self: &Arc<Self>, kind: SubscriberKind) -> Subscription {
+        self.subscribers(kind).fetch_add(1, Ordering::Relaxed);
+        METRICS
+            .subscribers
+            .with_label_values(&[kind.as_str()])
+            .inc();
+        METRICS
+            .subscriber_connections
+            .with_label_values(&[kind.as_str()])
+            .inc();
+        Subscription {
+            health: self.clone(),
+            kind,
+            broadcasts_before: METRICS.bus_broadcasts.get(),
+            received: 0,
+        }
+    }
+
+    /// Live subscribers of all kinds.
+    pub fn subscriber_count(&self) -> usize {
+        self.websocket_subscribers.load(Ordering::Relaxed)
+            + self.sse_subscribers.load(Ordering::Relaxed)
+    }
+
+    /// The crawler is stale, if it didn't fetch the trips overview for `stale_after`. Before its
+    /// first fetch, the time since startup counts.
+    fn crawler_stale(&self, crawler: &CrawlerHealth, now: OffsetDateTime) -> bool {
+        now - crawler.last_overview_fetch.unwrap_or(self.started_at) > self.stale_after
+    }
+
+    pub fn status(&self, database: bool, now: OffsetDateTime) -> Status {
+        let crawler = self.crawler().clone();
+        Status {
+            version: env!("CARGO_PKG_VERSION"),
+            started_at: self.started_at,
+            database,
+            caches_ready_at: crawler.caches_ready_at,
+            last_overview_fetch: crawler.last_overview_fetch,
+            last_trip_fetch: crawler.last_trip_fetch,
+            active_trips: crawler.active_trips,
+            crawler_stale: self.crawler_stale(&crawler, now),
+            websocket_subscribers: self.websocket_subscribers.load(Ordering::Relaxed),
+            sse_subscribers: self.sse_subscribers.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readiness() {
+        let health = Health::new(Duration::minutes(5));
+        let start = health.started_at;
+
+        let status = health.status(true, start);
+        assert_eq!(status.not_ready_reasons(), vec!["caches not ready"]);
+        assert!(!health.caches_are_ready());
+
+        health.caches_ready();
+        assert!(health.caches_are_ready());
+        assert!(health.status(true, start).not_ready_reasons().is_empty());
+        assert_eq!(
+            health
+                .status(false, start + Duration::minutes(6))
+                .not_ready_reasons(),
+            vec!["database unreachable", "crawler stale"]
+        );
+
+        health.overview_fetched(start + Duration::minutes(4), 3);
+        let status = health.status(true, start + Duration::minutes(6));
+        assert!(status.not_ready_reasons().is_empty());
+        assert_eq!(status.active_trips, 3);
+    }
+
+    #[test]
+    fn subscriber_count() {
+        let health = Arc::new(Health::new(Duration::minutes(5)));
+        let websocket = health.subscribe(SubscriberKind::Websocket);
+        let sse = health.subscribe(SubscriberKind::Sse);
+        let _other_sse = health.subscribe(SubscriberKind::Sse);
+
+        let status = health.status(true, OffsetDateTime::now_utc());
+        assert_eq!(
+            (status.websocket_subscribers, status.sse_subscribers),
+            (1, 2)
+        );
+
+        drop(websocket);
+        drop(sse);
+        let status = health.status(true, OffsetDateTime::now_utc());
+        assert_eq!(
+            (status.websocket_subscribers, status.sse_subscribers),
+            (0, 1)
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Delays binned by time and position on the route, so zoomed out views of the Marey diagram
+//! don't need to download every single DelayEvent.
+
+use crate::models::HeatmapBin;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, Timestamptz};
+use time::OffsetDateTime;
+
+/// Aggregates the delay_events between $1 and $2 into bins of $3 seconds and $4 km. The position
+/// of an event is interpolated between the km of its previous and next station, just like the
+/// client does. Events without realtime data don't have a known delay and are left out, as are
+/// events on segments we don't know the km of.
+const HEATMAP: &str = r#"
+SELECT delay_events.direction
+     , to_timestamp((floor(extract(epoch FROM delay_events.time) / $3) * $3)::double precision)
+         AS time_start
+     , floor((previous.km + (next.km - previous.km) * delay_events.percentage_segment) / $4) * $4
+         AS km_start
+     , COUNT(*) AS count
+     , AVG(delay_events.delay)::double precision AS delay_mean
+     , MAX(delay_events.delay) AS delay_max
+FROM delay_events
+JOIN stations previous ON previous.id = delay_events.previous_station
+JOIN stations next ON next.id = delay_events.next_station
+WHERE delay_events.time >= $1 AND delay_events.time < $2
+  AND delay_events.has_realtime
+  AND previous.km IS NOT NULL AND next.km IS NOT NULL
+GROUP BY delay_events.direction, time_start, km_start
+ORDER BY delay_events.direction, time_start, km_start
+"#;
+
+/// The delays between `from` and `to`, binned per direction, `time_bucket` seconds and
+/// `segment_bucket` km of the route.
+pub fn heatmap(
+    db: &mut PgConnection,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    time_bucket: i64,
+    segment_bucket: f64,
+) -> QueryResult<Vec<HeatmapBin>> {
+    diesel::sql_query(HEATMAP)
+        .bind::<Timestamptz, _>(from)
+        .bind::<Timestamptz, _>(to)
+        .bind::<BigInt, _>(time_bucket)
+        .bind::<Double, _>(segment_bucket)
+        .load(db)
+}
@@ -19,10 +19,25 @@ mod transport_rest_vbb_v6;
 
 mod cache;
 mod cli_utils;
+mod client;
 mod crawler;
+mod export;
+mod gtfs_rt;
+mod health;
+mod heatmap;
+mod metrics;
 mod models;
+mod quarantine;
+mod render;
+mod route;
 mod schema;
+mod segments;
+mod statistics;
+mod trip_changes;
+mod trip_ids;
+mod trip_summaries;
 mod web_api;
+mod web_utils;
 mod ws_api;
 
 #[derive(Parser, Debug)]
@@ -33,6 +48,14 @@ struct CliArgs {
     ws_port: u16,
     #[arg(short, long)]
     listen: std::net::IpAddr,
+    /// Consider the crawler stale and the server not ready, if it didn't fetch the overview of
+    /// running trips for this many seconds.
+    #[arg(long, default_value_t = 300)]
+    stale_after: i64,
+    /// Also serve the built client from this directory, so no nginx is needed in front of the
+    /// server.
+    #[arg(long)]
+    client_dir: Option<std::path::PathBuf>,
     #[command(subcommand)]
     command: Option<CliCommand>,
 }
@@ -42,6 +65,53 @@ enum CliCommand {
     ValidateHafasSchema,
     RunDbMigrations,
     TrainZstdDict,
+    /// List the fetched_json rows that got quarantined, as they couldn't be processed.
+    ListQuarantine,
+    /// Throw away the trip_summaries table and build it again from delay_records.
+    RebuildTripSummaries,
+    /// Measure serialization and compression of the delay_events the web API sends.
+    BenchmarkDelayEvents {
+        /// How many of the latest days of delay_events to use.
+        #[arg(long, default_value_t = 7)]
+        days: i64,
+        /// Use generated delay events instead of the ones in the database, which are the same on
+        /// every run.
+        #[arg(long)]
+        synthetic: bool,
+    },
+    /// Export delay_records, delay_events or trip_summaries in a time range as CSV or Parquet.
+    Export {
+        table: crate::export::ExportTable,
+        #[arg(long, default_value = "csv")]
+        format: crate::export::ExportFormat,
+        /// Start of the time range as unix timestamp.
+        #[arg(long)]
+        from: i64,
+        /// End of the time range as unix timestamp, now by default.
+        #[arg(long)]
+        to: Option<i64>,
+        /// The file to write to, stdout by default.
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Render the Marey diagram or the heatmap of a time range as SVG or PNG.
+    Render {
+        diagram: crate::render::Diagram,
+        #[arg(long, default_value = "svg")]
+        format: crate::render::RenderFormat,
+        /// Start of the time range as unix timestamp, a day before its end by default.
+        #[arg(long)]
+        from: Option<i64>,
+        /// End of the time range as unix timestamp, now by default.
+        #[arg(long)]
+        to: Option<i64>,
+        /// Only show trips going "westwards" or "eastwards".
+        #[arg(long)]
+        direction: Option<crate::models::Direction>,
+        /// The file to write to, stdout by default.
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
 }
 
 fn run_db_migrations(db: &mut PgConnection) {
@@ -102,6 +172,18 @@ fn main() {
 
     let args = CliArgs::parse();
 
+    if let Some(CliCommand::BenchmarkDelayEvents {
+        days,
+        synthetic: true,
+    }) = args.command
+    {
+        crate::cli_utils::benchmark_delay_events(None, days).unwrap_or_else(|e| {
+            error!("{}", e);
+            std::process::exit(1);
+        });
+        std::process::exit(0);
+    }
+
     let db_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
 
     {
@@ -122,30 +204,89 @@ fn main() {
         } else if let Some(CliCommand::TrainZstdDict) = args.command {
             train_zstd_dict(&mut db);
             std::process::exit(0);
+        } else if let Some(CliCommand::ListQuarantine) = args.command {
+            crate::cli_utils::list_quarantine(&mut db).unwrap_or_else(|e| {
+                error!("{}", e);
+                std::process::exit(1);
+            });
+            std::process::exit(0);
+        } else if let Some(CliCommand::RebuildTripSummaries) = args.command {
+            crate::trip_summaries::rebuild_trip_summaries(&mut db).unwrap_or_else(|e| {
+                error!("{}", e);
+                std::process::exit(1);
+            });
+            std::process::exit(0);
+        } else if let Some(CliCommand::BenchmarkDelayEvents { days, .. }) = args.command {
+            crate::cli_utils::benchmark_delay_events(Some(&mut db), days).unwrap_or_else(|e| {
+                error!("{}", e);
+                std::process::exit(1);
+            });
+            std::process::exit(0);
+        } else if let Some(CliCommand::Export {
+            table,
+            format,
+            from,
+            to,
+            output,
+        }) = args.command
+        {
+            crate::cli_utils::export(&mut db, table, format, from, to, output).unwrap_or_else(
+                |e| {
+                    error!("{}", e);
+                    std::process::exit(1);
+                },
+            );
+            std::process::exit(0);
+        } else if let Some(CliCommand::Render {
+            diagram,
+            format,
+            from,
+            to,
+            direction,
+            output,
+        }) = args.command
+        {
+            crate::cli_utils::render(&mut db, diagram, format, from, to, direction, output)
+                .unwrap_or_else(|e| {
+                    error!("{}", e);
+                    std::process::exit(1);
+                });
+            std::process::exit(0);
         }
     }
 
-    let cache_state: cache::CacheState = {
-        let mut db1: PgConnection = PgConnection::establish(&db_url)
-            .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
-        let db2: PgConnection = PgConnection::establish(&db_url)
-            .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
-        crate::cache::update_caches(&mut db1, db2)
-            .unwrap_or_else(|e| panic!("Unable to update cache tables in DB: {}", e))
-    };
+    let health = std::sync::Arc::new(crate::health::Health::new(time::Duration::seconds(
+        args.stale_after,
+    )));
 
     // The spmc bus with which the crawler can communicate with all open websocket threads.
     let bus = bus::Bus::new(10 * 1024);
-    // With this handle we can produce a new channel receiver per new websocket connection.
+    // With these handles we can produce a new channel receiver per new websocket connection or
+    // SSE subscriber.
     let bus_read_handle = bus.read_handle();
+    let sse_bus_read_handle = bus.read_handle();
 
-    // Start crawler
+    // Start crawler, after the cache tables are up to date. The servers already run meanwhile, but
+    // aren't ready yet.
     {
         let db_url = db_url.clone();
+        let health = health.clone();
         std::thread::spawn(move || {
+            let cache_state: cache::CacheState = {
+                let mut db1: PgConnection = PgConnection::establish(&db_url)
+                    .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
+                let db2: PgConnection = PgConnection::establish(&db_url)
+                    .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
+                crate::cache::update_caches(&mut db1, db2).unwrap_or_else(|e| {
+                    error!("Unable to update cache tables in DB: {}", e);
+                    std::process::exit(1);
+                })
+            };
+            health.caches_ready();
+
             let mut db: PgConnection = PgConnection::establish(&db_url)
                 .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
-            crawler::crawler(&mut db, bus, cache_state).unwrap_or_else(|e| {
+            crawler::crawler(&mut db, bus, cache_state, &health).unwrap_or_else(|e| {
                 error!("{}", e);
                 std::process::exit(1);
             });
@@ -154,17 +295,26 @@ fn main() {
 
     // Start websocket server
     {
-        let mut db: PgConnection = PgConnection::establish(&(db_url.clone()))
+        let db: PgConnection = PgConnection::establish(&(db_url.clone()))
             .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
+        let health = health.clone();
         std::thread::spawn(move || {
-            crate::ws_api::websocket_server(&mut db, bus_read_handle, args.listen, args.ws_port)
+            crate::ws_api::websocket_server(db, bus_read_handle, health, args.listen, args.ws_port)
                 .unwrap();
         });
     }
 
     // Start webserver
     {
-        crate::web_api::webserver(&db_url, args.listen, args.port).unwrap();
+        crate::web_api::webserver(
+            &db_url,
+            sse_bus_read_handle,
+            health,
+            args.client_dir,
+            args.listen,
+            args.port,
+        )
+        .unwrap();
     }
 
     // TODO use sd-notify to signal the service manager that all processes are up and running.
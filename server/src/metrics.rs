@@ -0,0 +1,186 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Prometheus metrics of the crawler and the servers, served at /metrics.
+
+use crate::transport_rest_vbb_v6::HafasMsg;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    /// Responses from HAFAS, by the kind of HafasMsg they turned out to be.
+    pub fetches: IntCounterVec,
+    /// Duration of HTTP requests to HAFAS, by endpoint.
+    pub fetch_duration: HistogramVec,
+    /// Size of the responses we stored in fetched_json.
+    pub fetched_bytes: IntCounter,
+    pub delay_records: IntCounter,
+    pub delay_events: IntCounter,
+    /// Rows of fetched_json that couldn't be turned into DelayRecord's or DelayEvent's.
+    pub derivation_failures: IntCounter,
+    /// Currently connected live subscribers, by kind (websocket or sse).
+    pub subscribers: IntGaugeVec,
+    /// Live subscribers that ever connected, by kind.
+    pub subscriber_connections: IntCounterVec,
+    /// DelayRecord's sent to live subscribers, by kind.
+    pub messages_sent: IntCounterVec,
+    /// DelayRecord's the crawler broadcasted to the live subscribers.
+    pub bus_broadcasts: IntCounter,
+    /// DelayRecord's on the bus that weren't received by a subscriber yet, summed over all
+    /// subscribers.
+    pub bus_queue_depth: IntGauge,
+    /// Duration of database queries of the web API, by endpoint. For streamed responses, only until
+    /// the first row arrived.
+    pub db_query_duration: HistogramVec,
+}
+
+impl Metrics {
+    fn new() -> Metrics {
+        let registry = Registry::new_custom(Some("isre1late".to_string()), None)
+            .expect("Invalid metrics prefix");
+        let metrics = Metrics {
+            fetches: IntCounterVec::new(
+                Opts::new("fetches_total", "Responses from HAFAS by kind."),
+                &["kind"],
+            )
+            .unwrap(),
+            fetch_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "fetch_duration_seconds",
+                    "Duration of HTTP requests to HAFAS.",
+                )
+                .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]),
+                &["endpoint"],
+            )
+            .unwrap(),
+            fetched_bytes: IntCounter::new(
+                "fetched_bytes_total",
+                "Bytes of HAFAS responses stored in fetched_json.",
+            )
+            .unwrap(),
+            delay_records: IntCounter::new(
+                "delay_records_total",
+                "DelayRecords derived from fetched trips.",
+            )
+            .unwrap(),
+            delay_events: IntCounter::new(
+                "delay_events_total",
+                "DelayEvents derived from DelayRecords.",
+            )
+            .unwrap(),
+            derivation_failures: IntCounter::new(
+                "derivation_failures_total",
+                "Fetched trips that got quarantined.",
+            )
+            .unwrap(),
+            subscribers: IntGaugeVec::new(
+                Opts::new("subscribers", "Currently connected live subscribers."),
+                &["kind"],
+            )
+            .unwrap(),
+            subscriber_connections: IntCounterVec::new(
+                Opts::new(
+                    "subscriber_connections_total",
+                    "Live subscribers that connected.",
+                ),
+                &["kind"],
+            )
+            .unwrap(),
+            messages_sent: IntCounterVec::new(
+                Opts::new(
+                    "messages_sent_total",
+                    "DelayRecords sent to live subscribers.",
+                ),
+                &["kind"],
+            )
+            .unwrap(),
+            bus_broadcasts: IntCounter::new(
+                "bus_broadcasts_total",
+                "DelayRecords broadcasted to live subscribers.",
+            )
+            .unwrap(),
+            bus_queue_depth: IntGauge::new(
+                "bus_queue_depth",
+                "DelayRecords not yet received by live subscribers.",
+            )
+            .unwrap(),
+            db_query_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "db_query_duration_seconds",
+                    "Duration of database queries of the web API.",
+                )
+                .buckets(vec![
+                    0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+                ]),
+                &["endpoint"],
+            )
+            .unwrap(),
+            registry,
+        };
+        for collector in [
+            Box::new(metrics.fetches.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(metrics.fetch_duration.clone()),
+            Box::new(metrics.fetched_bytes.clone()),
+            Box::new(metrics.delay_records.clone()),
+            Box::new(metrics.delay_events.clone()),
+            Box::new(metrics.derivation_failures.clone()),
+            Box::new(metrics.subscribers.clone()),
+            Box::new(metrics.subscriber_connections.clone()),
+            Box::new(metrics.messages_sent.clone()),
+            Box::new(metrics.bus_broadcasts.clone()),
+            Box::new(metrics.bus_queue_depth.clone()),
+            Box::new(metrics.db_query_duration.clone()),
+        ] {
+            metrics
+                .registry
+                .register(collector)
+                .expect("Metric registered twice");
+        }
+        metrics
+    }
+
+    /// Count a response from HAFAS, `None` meaning that we couldn't deserialize it.
+    pub fn fetched(&self, msg: Option<&HafasMsg>) {
+        self.fetches
+            .with_label_values(&[msg.map_or("invalid", HafasMsg::kind)])
+            .inc();
+    }
+
+    /// All metrics in the Prometheus text format.
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Unable to encode metrics");
+        String::from_utf8(buffer).expect("Metrics aren't valid UTF-8")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let metrics = Metrics::new();
+        metrics.fetched(Some(&HafasMsg::EmptyBody()));
+        metrics.fetched(None);
+        metrics
+            .db_query_duration
+            .with_label_values(&["trips"])
+            .observe(0.02);
+
+        let text = metrics.encode();
+        assert!(text.contains("isre1late_fetches_total{kind=\"empty_body\"} 1"));
+        assert!(text.contains("isre1late_fetches_total{kind=\"invalid\"} 1"));
+        assert!(text.contains("isre1late_db_query_duration_seconds_count{endpoint=\"trips\"} 1"));
+        assert!(text.contains("isre1late_bus_queue_depth 0"));
+    }
+}
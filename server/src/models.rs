@@ -2,14 +2,23 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::route::Route;
 use crate::schema::*;
-use crate::transport_rest_vbb_v6::TripOverview;
+use crate::transport_rest_vbb_v6::{Trip, TripOverview, TripStopover};
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
 use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
 use log::debug;
 use memuse::DynamicUsage;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use time::OffsetDateTime;
+use std::fmt;
+use std::io::Write;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+use utoipa::ToSchema;
 
 #[derive(Queryable, Insertable)]
 #[diesel(table_name = fetched_json)]
@@ -29,30 +38,165 @@ pub struct FetchedJson {
 }
 
 /// Don't take any assumptions about this struct's id field!
-#[derive(Queryable, Insertable, Serialize, Debug, Clone)]
+#[derive(Queryable, Insertable, Serialize, ToSchema, Debug, Clone)]
 #[diesel(table_name = delay_records)]
 pub struct DelayRecordWithID {
     pub id: i64,
     pub fetched_json_id: i64,
     pub trip_id: String,
     #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
     pub time: OffsetDateTime,
     pub previous_station: i64,
     pub next_station: i64,
     pub percentage_segment: f64,
     pub delay: i64,
+    pub arrival_delay: Option<i64>,
+    pub departure_delay: Option<i64>,
+    pub has_realtime: bool,
+    pub trip_state: TripState,
+    pub direction: Option<Direction>,
+}
+
+/// Declares an enum that is stored as text in the database, along with the text of each of its
+/// variants. The text has to match the serde name of the variant.
+macro_rules! text_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident ($what:literal) {
+            $($(#[$variant_meta:meta])* $variant:ident => $text:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, AsExpression,
+            FromSqlRow,
+        )]
+        #[diesel(sql_type = Text)]
+        #[serde(rename_all = "snake_case")]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $text,)*
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($text => Ok($name::$variant),)*
+                    _ => Err(format!(concat!("Unknown ", $what, ": {}"), s)),
+                }
+            }
+        }
+
+        impl ToSql<Text, Pg> for $name {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                out.write_all(self.as_str().as_bytes())?;
+                Ok(IsNull::No)
+            }
+        }
+
+        impl FromSql<Text, Pg> for $name {
+            fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                Ok(std::str::from_utf8(bytes.as_bytes())?.parse()?)
+            }
+        }
+    };
+}
+
+text_enum! {
+    /// Where a trip is on its journey at the time of a DelayRecord.
+    pub enum TripState ("trip_state") {
+        /// Waiting for departure at the first station. The DelayRecord is placed at the beginning
+        /// of the first segment and carries the departure delay.
+        AtOrigin => "at_origin",
+        /// Somewhere inbetween the first departure and the last arrival.
+        EnRoute => "en_route",
+        /// Arrived at the last station. The DelayRecord is placed at the end of the last segment
+        /// and carries the arrival delay.
+        AtTerminus => "at_terminus",
+    }
+}
+
+text_enum! {
+    /// The direction a train is going along the route, see [crate::route::Route].
+    pub enum Direction ("direction") {
+        /// From Cottbus towards Magdeburg, i.e. in the order of the route.
+        Westwards => "westwards",
+        /// From Magdeburg towards Cottbus.
+        Eastwards => "eastwards",
+    }
+}
+
+text_enum! {
+    /// The kinds of changes between two snapshots of a trip, see [crate::trip_changes].
+    pub enum ChangeKind ("change kind") {
+        ArrivalDelay => "arrival_delay",
+        DepartureDelay => "departure_delay",
+        ArrivalPlatform => "arrival_platform",
+        DeparturePlatform => "departure_platform",
+        StopCancelled => "stop_cancelled",
+        StopReinstated => "stop_reinstated",
+        StopAdded => "stop_added",
+        StopRemoved => "stop_removed",
+        TripCancelled => "trip_cancelled",
+        TripReinstated => "trip_reinstated",
+        /// None of the stops has a delay anymore.
+        RealtimeLost => "realtime_lost",
+        RealtimeRestored => "realtime_restored",
+    }
+}
+
+/// One change between two consecutive snapshots of a trip. Delays are given in seconds.
+#[derive(Queryable, Selectable, Insertable, Serialize, ToSchema, Debug, Clone, PartialEq)]
+#[diesel(table_name = trip_changes)]
+pub struct TripChange {
+    pub trip_id: String,
+    pub fetched_json_id: i64,
+    pub previous_fetched_json_id: i64,
+    /// When the snapshot containing the change was fetched.
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub time: OffsetDateTime,
+    /// The stop that changed, or None if the trip as a whole changed.
+    pub stop: Option<i64>,
+    pub kind: ChangeKind,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
 }
 
-#[derive(Queryable, Insertable, Serialize, Debug, Clone, PartialEq)]
+#[derive(Queryable, Insertable, Serialize, ToSchema, Debug, Clone, PartialEq)]
 pub struct DelayRecord {
     pub fetched_json_id: i64,
     pub trip_id: String,
     #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
     pub time: OffsetDateTime,
     pub previous_station: i64,
     pub next_station: i64,
     pub percentage_segment: f64,
+    /// The delay that is relevant for the current position of the train, i.e. the arrival delay
+    /// while moving and the departure delay while waiting in a station. This is 0 if no realtime
+    /// data is available, so check has_realtime before trusting it.
     pub delay: i64,
+    /// The expected arrival delay at next_station, if known.
+    pub arrival_delay: Option<i64>,
+    /// The departure delay at previous_station while moving or at next_station while waiting
+    /// there, if known.
+    pub departure_delay: Option<i64>,
+    /// Whether HAFAS had realtime data for the delay at all.
+    pub has_realtime: bool,
+    pub trip_state: TripState,
+    /// The direction along the route, if both stations are on it.
+    pub direction: Option<Direction>,
 }
 
 impl DelayRecord {
@@ -72,6 +216,8 @@ impl DelayRecord {
             None => Some(None),
             Some(latest_dr) => {
                 if self.delay == latest_dr.delay
+                    && self.has_realtime == latest_dr.has_realtime
+                    && self.trip_state == latest_dr.trip_state
                     && self.previous_station == latest_dr.previous_station
                     && self.next_station == latest_dr.next_station
                 {
@@ -96,10 +242,59 @@ impl From<DelayRecordWithID> for DelayRecord {
             next_station: item.next_station,
             percentage_segment: item.percentage_segment,
             delay: item.delay,
+            arrival_delay: item.arrival_delay,
+            departure_delay: item.departure_delay,
+            has_realtime: item.has_realtime,
+            trip_state: item.trip_state,
+            direction: item.direction,
+        }
+    }
+}
+
+/// Reasons why a snapshot of a trip can't be turned into DelayRecord's or DelayEvent's. Snapshots
+/// failing with one of these get quarantined.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// The ratio by which two delay events get split at a passed station is not within 0 and 1.
+    RatioOutOfRange(f64),
+}
+
+impl std::error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::RatioOutOfRange(r) => write!(f, "ratio out of range: {}", r),
         }
     }
 }
 
+/// A row from fetched_json that was rejected, along with the reason why.
+#[derive(Queryable, Insertable, Serialize, ToSchema, Debug, Clone)]
+#[diesel(table_name = quarantine)]
+pub struct Quarantine {
+    pub fetched_json_id: i64,
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub quarantined_at: OffsetDateTime,
+    pub reason: String,
+}
+
+/// A row from fetched_json, as the crawler stored it.
+#[derive(Queryable, Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct RawFetchedJson {
+    pub id: i64,
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub fetched_at: OffsetDateTime,
+    pub url: String,
+    /// Size of the body in bytes.
+    pub size: i32,
+    /// The body as we received it. It is only included when requesting a single row and if it
+    /// isn't too large.
+    pub body: Option<String>,
+}
+
 /// Convert a TripOverview into a DelayRecord.
 ///
 /// If we can't determine both a previous_station and a next_station, Nothing is returned.
@@ -108,10 +303,12 @@ impl From<DelayRecordWithID> for DelayRecord {
 ///
 /// * `fetched_at` - TripOverviews don't always provide a datetime, so we need a fallback which
 /// should be used from the fetched_at field from the database.
+/// * `route` - Used to determine the direction of the trip.
 pub fn delay_record_from_trip_overview(
     to: TripOverview,
     fetched_json_id: i64,
     fetched_at: OffsetDateTime,
+    route: &Route,
 ) -> Option<DelayRecord> {
     // Sometimes realtimeDataUpdatedAt is null, we just use the time the crawler got the response
     // then.
@@ -125,6 +322,7 @@ pub fn delay_record_from_trip_overview(
         }
     };
     let trip = to.trip;
+    let trip_id = canonical_trip_id(&trip);
 
     let mut previous_station = None;
     let mut next_station = None;
@@ -133,22 +331,31 @@ pub fn delay_record_from_trip_overview(
     let mut next_arrival = None;
 
     let mut delay = None;
+    let mut arrival_delay = None;
+    let mut departure_delay = None;
 
     let mut percentage_segment = -1.0;
 
-    for stopover in trip.stopovers {
+    let mut trip_state = TripState::EnRoute;
+
+    let mut stopovers = trip.stopovers.into_iter().peekable();
+    while let Some(stopover) = stopovers.next() {
         if stopover
             .plannedDeparture
             .map_or(false, |d| current_time > d)
         {
             previous_station = Some(stopover.stop.id);
             previous_departure = stopover.plannedDeparture;
+            departure_delay = stopover.departureDelay;
         } else if stopover.plannedArrival.map_or(false, |a| current_time < a) {
             next_station = Some(stopover.stop.id);
             next_arrival = stopover.plannedArrival;
             delay = stopover.arrivalDelay;
+            arrival_delay = stopover.arrivalDelay;
 
-            // We just assume the passed track linearly by time, for the lack of better data.
+            // We just assume the passed track linearly by time, for the lack of better data. As
+            // the previous departure is before and the next arrival after current_time, this is
+            // always within 0 and 1.
             percentage_segment = match (
                 previous_departure.map(OffsetDateTime::unix_timestamp),
                 next_arrival.map(OffsetDateTime::unix_timestamp),
@@ -159,10 +366,33 @@ pub fn delay_record_from_trip_overview(
 
             break;
         }
+        // Train should be waiting at its origin, so we place it at the beginning of the first
+        // segment.
+        else if previous_station.is_none() {
+            previous_station = Some(stopover.stop.id);
+            next_station = stopovers.peek().map(|so| so.stop.id);
+            delay = stopover.departureDelay;
+            departure_delay = stopover.departureDelay;
+            percentage_segment = 0.0;
+            trip_state = TripState::AtOrigin;
+            break;
+        }
+        // Train should have arrived at its terminus, where only the arrival delay is known.
+        else if stopover.plannedDeparture.is_none() {
+            next_station = Some(stopover.stop.id);
+            delay = stopover.arrivalDelay;
+            arrival_delay = stopover.arrivalDelay;
+            departure_delay = None;
+            percentage_segment = 1.0;
+            trip_state = TripState::AtTerminus;
+            break;
+        }
         // Train should be waiting in the next_station currently
         else {
             next_station = Some(stopover.stop.id);
             delay = stopover.departureDelay;
+            arrival_delay = stopover.arrivalDelay;
+            departure_delay = stopover.departureDelay;
             percentage_segment = 1.0;
             break;
         }
@@ -185,18 +415,21 @@ pub fn delay_record_from_trip_overview(
             .unix_timestamp()
     );
     debug!("percentage_segment: {}", percentage_segment);
-    assert!(percentage_segment >= 0.0);
-    assert!(percentage_segment <= 1.0);
 
     if let (Some(previous_station), Some(next_station)) = (previous_station, next_station) {
         Some(DelayRecord {
             fetched_json_id,
-            trip_id: trip.id,
+            trip_id,
             time: current_time,
             previous_station,
             next_station,
             percentage_segment,
             delay: delay.unwrap_or(0),
+            arrival_delay,
+            departure_delay,
+            has_realtime: delay.is_some(),
+            trip_state,
+            direction: route.direction(previous_station, next_station),
         })
     } else {
         None
@@ -205,29 +438,574 @@ pub fn delay_record_from_trip_overview(
 
 /// The serialisation of an delay event; A time and a span in space where a given trip had a
 /// certain delay.
-#[derive(Queryable, Insertable, Serialize, Debug, Clone, PartialEq)]
+#[derive(Queryable, Selectable, Insertable, Serialize, ToSchema, Debug, Clone, PartialEq)]
 #[diesel(table_name = delay_events)]
 pub struct DelayEvent {
+    /// The id of the fetched_json row the event starts at. This is the link to the raw HAFAS
+    /// response, which can be requested at /api/raw/{from_id}.
     pub from_id: i64,
+    /// The id of the fetched_json row the event ends at, its raw HAFAS response is at
+    /// /api/raw/{to_id}.
     pub to_id: i64,
     pub trip_id: String,
     #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
     pub time: OffsetDateTime,
     pub duration: i64,
     pub previous_station: i64,
     pub next_station: i64,
     pub percentage_segment: f64,
     pub delay: i64,
+    /// Whether the event wasn't observed directly but interpolated from the timetable, because
+    /// more than one station was passed between two DelayRecord's.
+    pub interpolated: bool,
+    /// Whether both DelayRecord's the event is built from had realtime data. If not, the delay is
+    /// not known and shouldn't be shown as punctual.
+    pub has_realtime: bool,
+    pub direction: Option<Direction>,
+}
+
+/// One page of DelayEvent's, ordered by time. If there are more, `next` is the cursor to request
+/// the next page with.
+#[derive(Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct DelayEventsPage {
+    pub delay_events: Vec<DelayEvent>,
+    pub next: Option<String>,
+}
+
+/// Key figures of one trip, derived from its DelayRecord's and its latest TripOverview.
+#[derive(Queryable, Serialize, ToSchema, Debug, Clone, PartialEq)]
+#[diesel(table_name = trip_summaries)]
+pub struct TripSummary {
+    pub trip_id: String,
+    pub line: Option<String>,
+    /// The fahrtNr of the trip, e.g. "3709" for RE 3709.
+    pub train_number: Option<String>,
+    /// The direction along the route, if the trip is on it.
+    pub direction: Option<Direction>,
+    /// The direction as shown on the train.
+    pub headsign: Option<String>,
+    pub origin: String,
+    pub destination: String,
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub planned_departure: OffsetDateTime,
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub first_observation: OffsetDateTime,
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub last_observation: OffsetDateTime,
+    /// The first delay with realtime data we observed.
+    pub start_delay: Option<i64>,
+    /// The last delay with realtime data we observed. If the trip was observed at its terminus,
+    /// this is the final arrival delay.
+    pub final_delay: Option<i64>,
+    pub max_delay: Option<i64>,
+    /// Number of stations the trip was observed departing from, plus its terminus if it was
+    /// observed arriving there.
+    pub stations_covered: i64,
+    pub cancelled: bool,
+    /// Number of stops that were cancelled, while the trip itself took place.
+    pub cancelled_stops: i64,
+    pub last_fetched_json_id: i64,
+}
+
+/// Punctuality at one station in one direction, aggregated over an hour, a day or a week.
+///
+/// A delay counts as within N minutes, if it is less than N minutes. within_0 counts the vehicles
+/// that were on time or early.
+#[derive(Queryable, Serialize, ToSchema, Debug, Clone, PartialEq)]
+#[diesel(table_name = station_punctuality)]
+pub struct StationPunctuality {
+    pub station: i64,
+    pub direction: Direction,
+    /// Either "hour", "day" or "week".
+    pub period: String,
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub period_start: OffsetDateTime,
+    /// Either "arrival" or "departure".
+    pub kind: String,
+    pub count: i64,
+    pub within_0: f64,
+    pub within_3: f64,
+    pub within_6: f64,
+    pub within_15: f64,
+    pub median_delay: f64,
+    pub p90_delay: f64,
+    #[serde(skip)]
+    pub fetched_json_id: i64,
+}
+
+/// Like StationPunctuality, but aggregated over an arbitrary selection of station_visits.
+#[derive(QueryableByName, Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct PunctualitySummary {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub station: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub direction: Direction,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kind: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub count: i64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub within_0: f64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub within_3: f64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub within_6: f64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub within_15: f64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub median_delay: f64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub p90_delay: f64,
+}
+
+/// Delay gained (positive) or lost (negative) on one segment between two consecutive stations,
+/// aggregated over many trips. Running gain is the change in delay between the departure at
+/// from_station and the arrival at to_station, dwell gain the change while stopping at
+/// to_station. All values are in seconds.
+#[derive(QueryableByName, Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct SegmentDelaySummary {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub from_station: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub to_station: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub direction: Direction,
+    /// Start of the hour, day or week, if the summary was requested per period.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub period_start: Option<OffsetDateTime>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub count: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub running_gain_mean: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub running_gain_median: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub running_gain_p90: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub dwell_gain_mean: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub dwell_gain_median: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub dwell_gain_p90: Option<f64>,
+}
+
+/// The DelayEvent's of one direction in a bin of time and route position, see /api/heatmap.
+/// Delays are in seconds.
+#[derive(QueryableByName, Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct HeatmapBin {
+    /// The direction along the route, if both stations are on it.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub direction: Option<Direction>,
+    #[diesel(sql_type = diesel::sql_types::Timestamptz)]
+    #[serde(with = "time::serde::timestamp")]
+    #[schema(value_type = i64)]
+    pub time_start: OffsetDateTime,
+    /// Start of the bin as distance from the first station of the route.
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub km_start: f64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub count: i64,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub delay_mean: f64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub delay_max: i64,
+}
+
+/// A station, either on the route or learnt from the stopovers of a trip.
+#[derive(Queryable, Serialize, ToSchema, Debug, Clone, PartialEq)]
+#[diesel(table_name = stations)]
+pub struct Station {
+    pub id: i64,
+    pub name: String,
+    pub short_name: Option<String>,
+    /// Whether the station should be labelled prominently, e.g. in diagrams.
+    pub important: bool,
+    /// The index of the station on the route, if it is on the route at all.
+    pub position: Option<i32>,
+    /// Distance from the first station of the route along the track.
+    pub km: Option<f64>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Maps the trip id HAFAS gave us to the canonical trip id of the same train run.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = trip_ids)]
+pub struct TripIdMapping {
+    pub hafas_trip_id: String,
+    pub canonical_trip_id: String,
+    /// The first fetched_json row in which we saw this HAFAS trip id.
+    pub fetched_json_id: i64,
+}
+
+/// The last Sunday of March or October, when the clocks change.
+fn last_sunday(year: i32, month: Month) -> Date {
+    let last = Date::from_calendar_date(year, month, 31).expect("Month has no 31 days");
+    last - Duration::days(last.weekday().number_days_from_sunday().into())
+}
+
+/// The UTC offset of Europe/Berlin at a point in time. Summer time lasts from 01:00 UTC on the
+/// last Sunday of March to 01:00 UTC on the last Sunday of October.
+pub fn berlin_offset(time: OffsetDateTime) -> UtcOffset {
+    let year = time.to_offset(UtcOffset::UTC).year();
+    let change = |month| {
+        PrimitiveDateTime::new(last_sunday(year, month), Time::from_hms(1, 0, 0).unwrap())
+            .assume_utc()
+    };
+    let hours = if (change(Month::March)..change(Month::October)).contains(&time) {
+        2
+    } else {
+        1
+    };
+    UtcOffset::from_hms(hours, 0, 0).unwrap()
+}
+
+/// HAFAS trip ids are opaque and can change for the same train run. So we identify a trip by
+/// its line, its train number and its planned departure at the origin in local time instead, e.g.
+/// "RE1_3709_20230701T0900". If HAFAS doesn't tell us the line, we have to stick to its trip id.
+pub fn canonical_trip_id(trip: &Trip) -> String {
+    match trip
+        .line
+        .as_ref()
+        .and_then(|line| line.name.as_ref().zip(line.fahrtNr.as_ref()))
+    {
+        Some((name, fahrt_nr)) => {
+            // Whatever offset HAFAS gives us, the same departure must always result in the same
+            // id.
+            let d = trip
+                .plannedDeparture
+                .to_offset(berlin_offset(trip.plannedDeparture));
+            format!(
+                "{}_{}_{:04}{:02}{:02}T{:02}{:02}",
+                name.replace(char::is_whitespace, ""),
+                fahrt_nr,
+                d.year(),
+                u8::from(d.month()),
+                d.day(),
+                d.hour(),
+                d.minute()
+            )
+        }
+        None => trip.id.clone(),
+    }
 }
 
 /// Serialization of a trip intended for the webclient
-#[derive(Serialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, ToSchema, Debug, Clone, PartialEq)]
 pub struct Stopover {
     pub stop: i64,
     #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
     pub planned_arrival: Option<OffsetDateTime>,
     pub arrival_delay: Option<i64>,
     #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
     pub planned_departure: Option<OffsetDateTime>,
     pub departure_delay: Option<i64>,
 }
+
+impl Stopover {
+    /// The actual arrival time, i.e. the planned arrival plus the arrival delay if known.
+    pub fn arrival(&self) -> Option<OffsetDateTime> {
+        self.planned_arrival
+            .map(|a| a + Duration::seconds(self.arrival_delay.unwrap_or(0)))
+    }
+
+    /// The actual departure time, i.e. the planned departure plus the departure delay if known.
+    pub fn departure(&self) -> Option<OffsetDateTime> {
+        self.planned_departure
+            .map(|d| d + Duration::seconds(self.departure_delay.unwrap_or(0)))
+    }
+}
+
+/// A Stopover along with its actual (or expected) times, as sent by the web API.
+#[derive(Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct StopoverWithTimes {
+    #[serde(flatten)]
+    pub stopover: Stopover,
+    #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub arrival: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub departure: Option<OffsetDateTime>,
+}
+
+impl From<Stopover> for StopoverWithTimes {
+    fn from(stopover: Stopover) -> Self {
+        StopoverWithTimes {
+            arrival: stopover.arrival(),
+            departure: stopover.departure(),
+            stopover,
+        }
+    }
+}
+
+impl From<&TripStopover> for Stopover {
+    fn from(so: &TripStopover) -> Self {
+        Stopover {
+            stop: so.stop.id,
+            planned_arrival: so.plannedArrival,
+            arrival_delay: so.arrivalDelay,
+            planned_departure: so.plannedDeparture,
+            departure_delay: so.departureDelay,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_enums_match_serde() {
+        for kind in [ChangeKind::StopCancelled, ChangeKind::RealtimeRestored] {
+            assert_eq!(serde_json::to_value(kind).unwrap(), kind.as_str());
+            assert_eq!(kind.as_str().parse(), Ok(kind));
+        }
+        for direction in [Direction::Westwards, Direction::Eastwards] {
+            assert_eq!(serde_json::to_value(direction).unwrap(), direction.as_str());
+            assert_eq!(direction.as_str().parse(), Ok(direction));
+        }
+        assert_eq!(
+            serde_json::to_value(TripState::AtTerminus).unwrap(),
+            "at_terminus"
+        );
+        assert_eq!(
+            "somewhere".parse::<TripState>(),
+            Err("Unknown trip_state: somewhere".to_string())
+        );
+    }
+
+    /// A route from Berlin via Potsdam to Magdeburg, which goes westwards like the RE1.
+    fn route() -> Route {
+        Route::new(
+            [
+                (3, "Berlin Hauptbahnhof"),
+                (2, "Potsdam, Hauptbahnhof"),
+                (1, "Magdeburg, Hauptbahnhof"),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(position, (id, name))| Station {
+                id,
+                name: name.to_string(),
+                short_name: None,
+                important: false,
+                position: Some(position as i32),
+                km: None,
+                latitude: None,
+                longitude: None,
+            })
+            .collect(),
+        )
+    }
+
+    /// A TripOverview from Magdeburg via Potsdam to Berlin, with the given delays at Potsdam.
+    fn trip_overview(
+        realtime_data_updated_at: &str,
+        arrival_delay: &str,
+        departure_delay: &str,
+    ) -> TripOverview {
+        serde_json::from_str(&format!(
+            r#"{{
+                "trip": {{
+                    "id": "t1",
+                    "origin": {{ "name": "Magdeburg, Hauptbahnhof" }},
+                    "destination": {{ "name": "Berlin Hauptbahnhof" }},
+                    "departure": null,
+                    "plannedDeparture": "2023-07-01T09:00:00+02:00",
+                    "currentLocation": null,
+                    "departureDelay": null,
+                    "arrivalDelay": null,
+                    "stopovers": [
+                        {{
+                            "stop": {{ "name": "Magdeburg, Hauptbahnhof", "id": "1" }},
+                            "plannedArrival": null,
+                            "arrivalDelay": null,
+                            "plannedDeparture": "2023-07-01T09:00:00+02:00",
+                            "departureDelay": 60
+                        }},
+                        {{
+                            "stop": {{ "name": "Potsdam, Hauptbahnhof", "id": "2" }},
+                            "plannedArrival": "2023-07-01T10:10:00+02:00",
+                            "arrivalDelay": {arrival_delay},
+                            "plannedDeparture": "2023-07-01T10:12:00+02:00",
+                            "departureDelay": {departure_delay}
+                        }},
+                        {{
+                            "stop": {{ "name": "Berlin Hauptbahnhof", "id": "3" }},
+                            "plannedArrival": "2023-07-01T10:40:00+02:00",
+                            "arrivalDelay": 180,
+                            "plannedDeparture": null,
+                            "departureDelay": null
+                        }}
+                    ]
+                }},
+                "realtimeDataUpdatedAt": {realtime_data_updated_at}
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn delay_record_while_moving() {
+        // 10:05, i.e. between Magdeburg and Potsdam.
+        let dr = delay_record_from_trip_overview(
+            trip_overview("1688198700", "120", "60"),
+            1,
+            OffsetDateTime::UNIX_EPOCH,
+            &route(),
+        )
+        .unwrap();
+
+        assert_eq!(dr.trip_state, TripState::EnRoute);
+        assert_eq!(dr.previous_station, 1);
+        assert_eq!(dr.next_station, 2);
+        assert_eq!(dr.direction, Some(Direction::Eastwards));
+        assert_eq!(dr.delay, 120);
+        assert_eq!(dr.arrival_delay, Some(120));
+        assert_eq!(dr.departure_delay, Some(60));
+        assert!(dr.has_realtime);
+    }
+
+    #[test]
+    fn delay_record_without_realtime_data() {
+        let dr = delay_record_from_trip_overview(
+            trip_overview("1688198700", "null", "null"),
+            1,
+            OffsetDateTime::UNIX_EPOCH,
+            &route(),
+        )
+        .unwrap();
+
+        assert_eq!(dr.delay, 0);
+        assert_eq!(dr.arrival_delay, None);
+        assert!(!dr.has_realtime);
+    }
+
+    #[test]
+    fn delay_record_while_waiting_in_station() {
+        // 10:11, i.e. waiting in Potsdam.
+        let dr = delay_record_from_trip_overview(
+            trip_overview("1688199060", "0", "30"),
+            1,
+            OffsetDateTime::UNIX_EPOCH,
+            &route(),
+        )
+        .unwrap();
+
+        assert_eq!(dr.next_station, 2);
+        assert_eq!(dr.percentage_segment, 1.0);
+        assert_eq!(dr.delay, 30);
+        assert_eq!(dr.arrival_delay, Some(0));
+        assert_eq!(dr.departure_delay, Some(30));
+        assert!(dr.has_realtime);
+    }
+
+    #[test]
+    fn delay_record_at_origin() {
+        // 08:50, i.e. waiting in Magdeburg.
+        let dr = delay_record_from_trip_overview(
+            trip_overview("1688194200", "0", "0"),
+            1,
+            OffsetDateTime::UNIX_EPOCH,
+            &route(),
+        )
+        .unwrap();
+
+        assert_eq!(dr.trip_state, TripState::AtOrigin);
+        assert_eq!(dr.previous_station, 1);
+        assert_eq!(dr.next_station, 2);
+        assert_eq!(dr.percentage_segment, 0.0);
+        assert_eq!(dr.delay, 60);
+        assert_eq!(dr.arrival_delay, None);
+        assert_eq!(dr.departure_delay, Some(60));
+    }
+
+    #[test]
+    fn delay_record_at_terminus() {
+        // 10:45, i.e. arrived in Berlin.
+        let dr = delay_record_from_trip_overview(
+            trip_overview("1688201100", "0", "0"),
+            1,
+            OffsetDateTime::UNIX_EPOCH,
+            &route(),
+        )
+        .unwrap();
+
+        assert_eq!(dr.trip_state, TripState::AtTerminus);
+        assert_eq!(dr.previous_station, 2);
+        assert_eq!(dr.next_station, 3);
+        assert_eq!(dr.percentage_segment, 1.0);
+        assert_eq!(dr.delay, 180);
+        assert_eq!(dr.arrival_delay, Some(180));
+        assert_eq!(dr.departure_delay, None);
+    }
+
+    #[test]
+    fn canonical_trip_id_from_line() {
+        use crate::transport_rest_vbb_v6::TripLine;
+
+        let mut trip = trip_overview("1688198700", "120", "60").trip;
+        assert_eq!(canonical_trip_id(&trip), "t1");
+
+        trip.line = Some(TripLine {
+            name: Some("RE 1".to_string()),
+            fahrtNr: Some("3709".to_string()),
+        });
+        assert_eq!(canonical_trip_id(&trip), "RE1_3709_20230701T0900");
+
+        // A new HAFAS trip id for the same train run doesn't change anything.
+        trip.id = "t2".to_string();
+        assert_eq!(canonical_trip_id(&trip), "RE1_3709_20230701T0900");
+    }
+
+    #[test]
+    fn canonical_trip_id_in_local_time() {
+        use crate::transport_rest_vbb_v6::TripLine;
+
+        let mut trip = trip_overview("1688198700", "120", "60").trip;
+        trip.line = Some(TripLine {
+            name: Some("RE 1".to_string()),
+            fahrtNr: Some("3709".to_string()),
+        });
+        trip.plannedDeparture = trip.plannedDeparture.to_offset(UtcOffset::UTC);
+        assert_eq!(canonical_trip_id(&trip), "RE1_3709_20230701T0900");
+    }
+
+    #[test]
+    fn berlin_summer_time() {
+        let time =
+            |s| OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339).unwrap();
+        let hours = |s| berlin_offset(time(s)).whole_hours();
+        assert_eq!(hours("2023-11-15T12:00:00Z"), 1);
+        assert_eq!(hours("2024-03-31T00:59:59Z"), 1);
+        assert_eq!(hours("2024-03-31T01:00:00Z"), 2);
+        assert_eq!(hours("2024-10-27T00:59:59Z"), 2);
+        assert_eq!(hours("2024-10-27T01:00:00Z"), 1);
+    }
+
+    #[test]
+    fn stopover_with_actual_times() {
+        let stopover = Stopover {
+            stop: 1,
+            planned_arrival: None,
+            arrival_delay: None,
+            planned_departure: Some(OffsetDateTime::from_unix_timestamp(1688198700).unwrap()),
+            departure_delay: Some(120),
+        };
+        let json = serde_json::to_value(StopoverWithTimes::from(stopover)).unwrap();
+
+        assert_eq!(json["planned_departure"], 1688198700);
+        assert_eq!(json["departure"], 1688198820);
+        assert_eq!(json["arrival"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Snapshots of trips that we can't make sense of get quarantined, instead of crashing the
+//! crawler or one of the cache workers.
+
+use crate::metrics::METRICS;
+use crate::models::{Quarantine, ValidationError};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use log::warn;
+use time::OffsetDateTime;
+
+/// Put a row from fetched_json into quarantine. Quarantining the same row twice is a no-op.
+pub fn quarantine(
+    db: &mut PgConnection,
+    fetched_json_id: i64,
+    error: &ValidationError,
+) -> QueryResult<()> {
+    use crate::schema::quarantine;
+
+    warn!(
+        "Quarantining fetched_json row {}: {}",
+        fetched_json_id, error
+    );
+    METRICS.derivation_failures.inc();
+
+    diesel::insert_into(quarantine::table)
+        .values(&Quarantine {
+            fetched_json_id,
+            quarantined_at: OffsetDateTime::now_utc(),
+            reason: error.to_string(),
+        })
+        .on_conflict(quarantine::fetched_json_id)
+        .do_nothing()
+        .execute(db)?;
+
+    Ok(())
+}
+
+/// All the quarantined rows, latest first.
+pub fn load_quarantine(db: &mut PgConnection) -> QueryResult<Vec<Quarantine>> {
+    use crate::schema::quarantine;
+
+    quarantine::table
+        .select((
+            quarantine::fetched_json_id,
+            quarantine::quarantined_at,
+            quarantine::reason,
+        ))
+        .order(quarantine::fetched_json_id.desc())
+        .load::<Quarantine>(db)
+}
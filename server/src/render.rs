@@ -0,0 +1,612 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Static renderings of the Marey diagram and the heatmap as SVG or PNG, for embedding them
+//! where there is no browser to run the client, e.g. in reports, chat messages or link previews.
+
+use crate::models::{berlin_offset, DelayEvent, Direction, HeatmapBin, Station};
+use crate::route::Route;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use log::warn;
+use resvg::{tiny_skia, usvg};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
+use time::format_description::FormatItem;
+use time::{Duration, OffsetDateTime, Time, UtcOffset};
+
+/// Longest time range we render, as more wouldn't be readable anyway.
+pub const MAX_RENDER_RANGE: Duration = Duration::WEEK;
+
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 600.0;
+/// Room for the station labels.
+const MARGIN_LEFT: f64 = 170.0;
+/// Room for the title and the time labels.
+const MARGIN_TOP: f64 = 60.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_BOTTOM: f64 = 20.0;
+
+const FONT_FAMILY: &str = "DejaVu Sans, Arial, Helvetica, sans-serif";
+
+/// Same red as the delay areas in the client.
+const DELAY_COLOR: &str = "hsl(0, 72%, 67%)";
+
+/// Roughly how many time buckets and route position buckets the heatmap has.
+const HEATMAP_COLUMNS: i64 = 200;
+const HEATMAP_ROWS: f64 = 40.0;
+
+/// Delays of this many seconds and more get the darkest color in the heatmap.
+const HEATMAP_MAX_DELAY: f64 = 15.0 * 60.0;
+
+/// The diagrams we can render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diagram {
+    Marey,
+    Heatmap,
+}
+
+impl Diagram {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Diagram::Marey => "marey",
+            Diagram::Heatmap => "heatmap",
+        }
+    }
+}
+
+impl FromStr for Diagram {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "marey" => Ok(Diagram::Marey),
+            "heatmap" => Ok(Diagram::Heatmap),
+            _ => Err(format!("Unknown diagram: {}", s)),
+        }
+    }
+}
+
+/// The image formats we can render to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Svg,
+    Png,
+}
+
+impl FromStr for RenderFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "svg" => Ok(RenderFormat::Svg),
+            "png" => Ok(RenderFormat::Png),
+            _ => Err(format!("Unknown format: {}", s)),
+        }
+    }
+}
+
+const TIME_LABEL: &[FormatItem<'static>] = time::macros::format_description!("[hour]:[minute]");
+const DAY_LABEL: &[FormatItem<'static>] =
+    time::macros::format_description!("[weekday repr:short] [day].[month].");
+const TITLE_TIME: &[FormatItem<'static>] =
+    time::macros::format_description!("[year]-[month]-[day] [hour]:[minute]");
+
+/// The distance between two time axis labels, so there are at most 12 of them.
+fn tick_interval(range: Duration) -> Duration {
+    [10, 15, 30, 60, 120, 180, 360, 720, 1440]
+        .into_iter()
+        .map(Duration::minutes)
+        .find(|interval| range / *interval <= 12.0)
+        .unwrap_or(Duration::DAY)
+}
+
+/// Points in time between `from` and `to` to label the time axis at, aligned to local time,
+/// along with their labels. Local midnight is labelled with the day.
+fn time_ticks(from: OffsetDateTime, to: OffsetDateTime) -> Vec<(OffsetDateTime, String)> {
+    let interval = tick_interval(to - from);
+    let mut local = from.to_offset(berlin_offset(from)).date().midnight();
+    let mut ticks = Vec::new();
+    loop {
+        let guess = local.assume_offset(UtcOffset::from_hms(1, 0, 0).unwrap());
+        let tick = local.assume_offset(berlin_offset(guess));
+        if tick > to {
+            break;
+        }
+        if tick >= from {
+            let format = if local.time() == Time::MIDNIGHT {
+                DAY_LABEL
+            } else {
+                TIME_LABEL
+            };
+            ticks.push((tick, local.format(format).unwrap_or_default()));
+        }
+        local += interval;
+    }
+    ticks
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Maps time to the x axis and the position on the route to the y axis. The first station of
+/// the route is at the top.
+struct Axes<'a> {
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    /// Stations on the route of which we know the km.
+    stations: Vec<(&'a Station, f64)>,
+    kms: HashMap<i64, f64>,
+    max_km: f64,
+}
+
+impl<'a> Axes<'a> {
+    fn new(route: &'a Route, from: OffsetDateTime, to: OffsetDateTime) -> Axes<'a> {
+        let stations: Vec<(&Station, f64)> = route
+            .stations()
+            .iter()
+            .filter(|s| s.position.is_some())
+            .filter_map(|s| Some((s, s.km?)))
+            .collect();
+        let kms = stations.iter().map(|(s, km)| (s.id, *km)).collect();
+        let max_km = stations
+            .iter()
+            .map(|(_, km)| *km)
+            .fold(0.0, f64::max)
+            .max(1.0);
+        Axes {
+            from,
+            to,
+            stations,
+            kms,
+            max_km,
+        }
+    }
+
+    fn x(&self, time: OffsetDateTime) -> f64 {
+        let range = (self.to - self.from).as_seconds_f64().max(1.0);
+        MARGIN_LEFT
+            + (time - self.from).as_seconds_f64() / range * (WIDTH - MARGIN_LEFT - MARGIN_RIGHT)
+    }
+
+    fn y(&self, km: f64) -> f64 {
+        MARGIN_TOP + km / self.max_km * (HEIGHT - MARGIN_TOP - MARGIN_BOTTOM)
+    }
+
+    /// Where a DelayEvent is on the route, interpolated between its stations like the client
+    /// does.
+    fn km(&self, delay_event: &DelayEvent) -> Option<f64> {
+        let previous = self.kms.get(&delay_event.previous_station)?;
+        let next = self.kms.get(&delay_event.next_station)?;
+        Some(previous + (next - previous) * delay_event.percentage_segment)
+    }
+
+    /// The start of the SVG document with the title, the station lines and the time lines. The
+    /// plot itself should be clipped to `url(#plot)`.
+    fn frame(&self, title: &str) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="{FONT_FAMILY}">"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{WIDTH}" height="{HEIGHT}" fill="white"/>"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<clipPath id="plot"><rect x="{MARGIN_LEFT}" y="{MARGIN_TOP}" width="{}" height="{}"/></clipPath>"#,
+            WIDTH - MARGIN_LEFT - MARGIN_RIGHT,
+            HEIGHT - MARGIN_TOP - MARGIN_BOTTOM
+        );
+        let local = |time: OffsetDateTime| {
+            time.to_offset(berlin_offset(time))
+                .format(TITLE_TIME)
+                .unwrap_or_default()
+        };
+        let _ = writeln!(
+            svg,
+            r#"<text x="10" y="22" font-size="16" font-weight="bold">{} ({} – {})</text>"#,
+            escape(title),
+            local(self.from),
+            local(self.to)
+        );
+
+        svg.push_str("<g id=\"time-lines\" stroke=\"#ccc\" stroke-width=\"1\">\n");
+        for (time, label) in time_ticks(self.from, self.to) {
+            let x = self.x(time);
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{MARGIN_TOP}" x2="{x:.1}" y2="{}"/><text x="{x:.1}" y="{}" font-size="11" text-anchor="middle" stroke="none" fill="#333">{}</text>"##,
+                HEIGHT - MARGIN_BOTTOM,
+                MARGIN_TOP - 8.0,
+                escape(&label)
+            );
+        }
+        svg.push_str("</g>\n");
+
+        svg.push_str("<g id=\"station-lines\">\n");
+        for (station, km) in &self.stations {
+            let y = self.y(*km);
+            let (color, weight) = if station.important {
+                ("#666", "bold")
+            } else {
+                ("#ddd", "normal")
+            };
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{MARGIN_LEFT}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="{color}" stroke-width="1"/><text x="{}" y="{:.1}" font-size="10" font-weight="{weight}" text-anchor="end" fill="#333">{}</text>"##,
+                WIDTH - MARGIN_RIGHT,
+                MARGIN_LEFT - 6.0,
+                y + 3.5,
+                escape(station.short_name.as_deref().unwrap_or(&station.name))
+            );
+        }
+        svg.push_str("</g>\n");
+        svg
+    }
+}
+
+/// One line per trip through time and space, with the delay as red area behind it.
+fn marey_svg(axes: &Axes, delay_events: &[DelayEvent]) -> String {
+    let mut trips: BTreeMap<&str, Vec<&DelayEvent>> = BTreeMap::new();
+    for delay_event in delay_events {
+        trips
+            .entry(&delay_event.trip_id)
+            .or_default()
+            .push(delay_event);
+    }
+
+    let mut svg = axes.frame("How late is RE1?");
+    svg.push_str("<g id=\"trip-paths\" clip-path=\"url(#plot)\">\n");
+    for (trip_id, mut delay_events) in trips {
+        delay_events.sort_by_key(|delay_event| delay_event.time);
+        let points: Vec<(OffsetDateTime, f64, i64)> = delay_events
+            .iter()
+            .filter_map(|de| Some((de.time, axes.y(axes.km(de)?), de.delay.max(0))))
+            .collect();
+        if points.len() < 2 {
+            continue;
+        }
+        let planned = points
+            .iter()
+            .map(|(time, y, _)| format!("{:.1},{:.1}", axes.x(*time), y))
+            .collect::<Vec<_>>();
+        let delayed = points
+            .iter()
+            .rev()
+            .map(|(time, y, delay)| {
+                format!("{:.1},{:.1}", axes.x(*time + Duration::seconds(*delay)), y)
+            })
+            .collect::<Vec<_>>();
+        let _ = writeln!(
+            svg,
+            r#"<g><title>{}</title><path d="M{} L{} Z" fill="{DELAY_COLOR}" stroke="none"/><path d="M{}" fill="none" stroke="black" stroke-width="1"/></g>"#,
+            escape(trip_id),
+            planned.join(" L"),
+            delayed.join(" L"),
+            planned.join(" L")
+        );
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// Merge the bins of both directions, if we don't draw the directions separately.
+fn merge_directions(bins: Vec<HeatmapBin>) -> Vec<HeatmapBin> {
+    let mut merged: BTreeMap<(OffsetDateTime, i64), HeatmapBin> = BTreeMap::new();
+    for bin in bins {
+        // The km of a bin is a multiple of the bucket size, so this doesn't split bins.
+        let key = (bin.time_start, (bin.km_start * 1000.0).round() as i64);
+        match merged.get_mut(&key) {
+            Some(existing) => {
+                let count = existing.count + bin.count;
+                existing.delay_mean = (existing.delay_mean * existing.count as f64
+                    + bin.delay_mean * bin.count as f64)
+                    / count as f64;
+                existing.delay_max = existing.delay_max.max(bin.delay_max);
+                existing.count = count;
+                existing.direction = None;
+            }
+            None => {
+                merged.insert(key, bin);
+            }
+        }
+    }
+    merged.into_values().collect()
+}
+
+/// Lighter for punctual, darker for delayed.
+fn heatmap_color(delay: f64) -> String {
+    let share = (delay / HEATMAP_MAX_DELAY).clamp(0.0, 1.0);
+    format!("hsl(0, 72%, {:.0}%)", 95.0 - share * 55.0)
+}
+
+/// The mean delay per bin of time and route position.
+fn heatmap_svg(
+    axes: &Axes,
+    bins: &[HeatmapBin],
+    time_bucket: Duration,
+    segment_bucket: f64,
+) -> String {
+    let mut svg = axes.frame("How late is RE1? Mean delay");
+    svg.push_str("<g id=\"bins\" clip-path=\"url(#plot)\">\n");
+    for bin in bins {
+        let (x1, x2) = (axes.x(bin.time_start), axes.x(bin.time_start + time_bucket));
+        let (y1, y2) = (axes.y(bin.km_start), axes.y(bin.km_start + segment_bucket));
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x1:.1}" y="{y1:.1}" width="{:.1}" height="{:.1}" fill="{}"><title>{:.0}s mean, {}s max, {} events</title></rect>"#,
+            x2 - x1,
+            y2 - y1,
+            heatmap_color(bin.delay_mean),
+            bin.delay_mean,
+            bin.delay_max,
+            bin.count
+        );
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+fn load_delay_events(
+    db: &mut PgConnection,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    direction: Option<Direction>,
+) -> QueryResult<Vec<DelayEvent>> {
+    use crate::schema::delay_events;
+
+    let mut query = delay_events::table
+        .select(DelayEvent::as_select())
+        .filter(delay_events::time.ge(from))
+        .filter(delay_events::time.lt(to))
+        .into_boxed();
+    if let Some(direction) = direction {
+        query = query.filter(delay_events::direction.eq(direction));
+    }
+    query.load(db)
+}
+
+/// Everything a diagram is drawn from. Loaded from the db first, so it can be rendered without
+/// holding on to a db connection.
+pub struct DiagramData {
+    route: Route,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    plot: Plot,
+}
+
+enum Plot {
+    Marey(Vec<DelayEvent>),
+    Heatmap {
+        bins: Vec<HeatmapBin>,
+        time_bucket: i64,
+        segment_bucket: f64,
+    },
+}
+
+/// Load the data of a diagram of the time range between `from` and `to`, optionally only
+/// showing trips in one direction.
+pub fn load(
+    db: &mut PgConnection,
+    diagram: Diagram,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    direction: Option<Direction>,
+) -> QueryResult<DiagramData> {
+    let route = crate::route::load_route(db)?;
+    let plot = match diagram {
+        Diagram::Marey => Plot::Marey(load_delay_events(db, from, to, direction)?),
+        Diagram::Heatmap => {
+            let time_bucket = ((to - from).whole_seconds() / HEATMAP_COLUMNS).max(60);
+            let segment_bucket = (Axes::new(&route, from, to).max_km / HEATMAP_ROWS).max(0.1);
+            let bins = crate::heatmap::heatmap(db, from, to, time_bucket, segment_bucket)?;
+            let bins = match direction {
+                Some(direction) => bins
+                    .into_iter()
+                    .filter(|bin| bin.direction == Some(direction))
+                    .collect(),
+                None => merge_directions(bins),
+            };
+            Plot::Heatmap {
+                bins,
+                time_bucket,
+                segment_bucket,
+            }
+        }
+    };
+    Ok(DiagramData {
+        route,
+        from,
+        to,
+        plot,
+    })
+}
+
+impl DiagramData {
+    pub fn render(&self, format: RenderFormat) -> Result<Vec<u8>, Box<dyn Error>> {
+        let axes = Axes::new(&self.route, self.from, self.to);
+        let svg = match &self.plot {
+            Plot::Marey(delay_events) => marey_svg(&axes, delay_events),
+            Plot::Heatmap {
+                bins,
+                time_bucket,
+                segment_bucket,
+            } => heatmap_svg(
+                &axes,
+                bins,
+                Duration::seconds(*time_bucket),
+                *segment_bucket,
+            ),
+        };
+        match format {
+            RenderFormat::Svg => Ok(svg.into_bytes()),
+            RenderFormat::Png => rasterize(&svg),
+        }
+    }
+}
+
+/// Render a diagram of the time range between `from` and `to`, optionally only showing trips
+/// in one direction.
+pub fn render(
+    db: &mut PgConnection,
+    diagram: Diagram,
+    format: RenderFormat,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    direction: Option<Direction>,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    load(db, diagram, from, to, direction)?.render(format)
+}
+
+/// Loading the fonts takes a while, so we only do it once. Besides the system fonts, we use the
+/// ones in the directory given by the RENDER_FONTS_DIR environment variable, e.g. for a systemd
+/// service without any system fonts.
+static FONTS: LazyLock<Arc<usvg::fontdb::Database>> = LazyLock::new(|| {
+    let mut fonts = usvg::fontdb::Database::new();
+    fonts.load_system_fonts();
+    if let Some(dir) = std::env::var_os("RENDER_FONTS_DIR") {
+        fonts.load_fonts_dir(dir);
+    }
+    if fonts.is_empty() {
+        warn!("No system fonts found, PNGs will be rendered without labels.");
+    }
+    Arc::new(fonts)
+});
+
+/// Render an SVG document to PNG.
+fn rasterize(svg: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let options = usvg::Options {
+        fontdb: FONTS.clone(),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)?;
+    let size = tree.size().to_int_size();
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("Invalid image size")?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    Ok(pixmap.encode_png()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339).unwrap()
+    }
+
+    #[test]
+    fn ticks_in_local_time() {
+        let ticks = time_ticks(time("2023-11-15T06:50:00Z"), time("2023-11-15T10:00:00Z"));
+        let expected = [
+            ("2023-11-15T07:00:00Z", "08:00"),
+            ("2023-11-15T07:30:00Z", "08:30"),
+            ("2023-11-15T08:00:00Z", "09:00"),
+            ("2023-11-15T08:30:00Z", "09:30"),
+            ("2023-11-15T09:00:00Z", "10:00"),
+            ("2023-11-15T09:30:00Z", "10:30"),
+            ("2023-11-15T10:00:00Z", "11:00"),
+        ]
+        .map(|(t, label)| (time(t), label.to_string()));
+        assert_eq!(ticks, expected);
+
+        let ticks = time_ticks(time("2023-11-14T12:00:00Z"), time("2023-11-16T12:00:00Z"));
+        assert!(ticks.contains(&(time("2023-11-14T23:00:00Z"), "Wed 15.11.".to_string())));
+    }
+
+    fn station(id: i64, name: &str, km: f64) -> Station {
+        Station {
+            id,
+            name: name.to_string(),
+            short_name: None,
+            important: true,
+            position: Some(id as i32),
+            km: Some(km),
+            latitude: None,
+            longitude: None,
+        }
+    }
+
+    fn delay_event(time: OffsetDateTime, next_station: i64, delay: i64) -> DelayEvent {
+        DelayEvent {
+            from_id: 1,
+            to_id: 2,
+            trip_id: "1|2|3".to_string(),
+            time,
+            duration: 60,
+            previous_station: next_station - 1,
+            next_station,
+            percentage_segment: 0.5,
+            delay,
+            interpolated: false,
+            has_realtime: true,
+            direction: Some(Direction::Westwards),
+        }
+    }
+
+    #[test]
+    fn marey_diagram() {
+        let route = Route::new(vec![
+            station(0, "Cottbus", 0.0),
+            station(1, "Frankfurt & Oder", 80.0),
+            station(2, "Magdeburg", 200.0),
+        ]);
+        let from = time("2023-11-15T07:00:00Z");
+        let axes = Axes::new(&route, from, from + Duration::hours(2));
+        let svg = marey_svg(
+            &axes,
+            &[
+                delay_event(from + Duration::minutes(10), 1, 120),
+                delay_event(from + Duration::minutes(50), 2, 300),
+            ],
+        );
+
+        assert!(svg.contains(">Frankfurt &amp; Oder</text>"));
+        assert!(svg.contains("<title>1|2|3</title>"));
+        // Halfway to Frankfurt and halfway to Magdeburg.
+        assert!(svg.contains(&format!(
+            "M{:.1},{:.1} L",
+            axes.x(from + Duration::minutes(10)),
+            axes.y(40.0)
+        )));
+        assert!(svg.contains(&format!(
+            "{:.1},{:.1}",
+            axes.x(from + Duration::minutes(55)),
+            axes.y(140.0)
+        )));
+
+        let png = rasterize(&svg).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn merged_directions() {
+        let bin = |direction, count, delay_mean, delay_max| HeatmapBin {
+            direction: Some(direction),
+            time_start: time("2023-11-15T07:00:00Z"),
+            km_start: 5.0,
+            count,
+            delay_mean,
+            delay_max,
+        };
+        let merged = merge_directions(vec![
+            bin(Direction::Eastwards, 1, 60.0, 60),
+            bin(Direction::Westwards, 3, 120.0, 300),
+        ]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].count, 4);
+        assert_eq!(merged[0].delay_mean, 105.0);
+        assert_eq!(merged[0].delay_max, 300);
+        assert_eq!(merged[0].direction, None);
+    }
+}
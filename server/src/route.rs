@@ -0,0 +1,240 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The route model of the line. The stations table holds the stations of the route in their
+//! order, which is configured in the migration creating it. Names and coordinates of stations are
+//! learnt from the stopovers HAFAS sends us, and the kilometre positions along the route are then
+//! calculated from the coordinates, unless they are configured.
+
+use crate::models::{Direction, Station};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use log::info;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// Upserts all the stations occuring in the stopovers of the fetched_json rows selected by
+/// `{source}`. Coordinates are only taken if we don't know them already.
+const LEARN_STATIONS: &str = r#"
+INSERT INTO stations (id, name, latitude, longitude)
+SELECT DISTINCT ON ((so #>> '{stop,id}')::bigint)
+       (so #>> '{stop,id}')::bigint
+     , so #>> '{stop,name}'
+     , (so #>> '{stop,location,latitude}')::double precision
+     , (so #>> '{stop,location,longitude}')::double precision
+FROM ({source}) source
+CROSS JOIN LATERAL jsonb_array_elements(source.body::jsonb #> '{trip,stopovers}') so
+WHERE so #>> '{stop,id}' IS NOT NULL
+ORDER BY (so #>> '{stop,id}')::bigint, source.id DESC
+ON CONFLICT (id) DO UPDATE SET
+    latitude = COALESCE(stations.latitude, EXCLUDED.latitude)
+  , longitude = COALESCE(stations.longitude, EXCLUDED.longitude)
+"#;
+
+/// How many of the latest trip snapshots we look at on startup to learn stations.
+const LEARN_FROM_LATEST: i64 = 1000;
+
+/// All known stations, along with the order of the ones on the route.
+#[derive(Debug, Clone, Default)]
+pub struct Route {
+    stations: Vec<Station>,
+    positions: HashMap<i64, i32>,
+}
+
+impl Route {
+    pub fn new(stations: Vec<Station>) -> Route {
+        let positions = stations
+            .iter()
+            .filter_map(|station| Some((station.id, station.position?)))
+            .collect();
+        Route {
+            stations,
+            positions,
+        }
+    }
+
+    /// All stations, the ones on the route first and in order.
+    pub fn stations(&self) -> &[Station] {
+        &self.stations
+    }
+
+    /// The index of a station on the route, if it is on the route.
+    pub fn position(&self, station: i64) -> Option<i32> {
+        self.positions.get(&station).copied()
+    }
+
+    /// Whether we know the station at all, even if it isn't on the route.
+    pub fn knows(&self, station: i64) -> bool {
+        self.stations.iter().any(|s| s.id == station)
+    }
+
+    /// The direction of a trip going from one station to another. Going along the order of the
+    /// route is westwards. If one of the stations isn't on the route, we can't tell.
+    pub fn direction(&self, from: i64, to: i64) -> Option<Direction> {
+        let from = self.positions.get(&from)?;
+        let to = self.positions.get(&to)?;
+        match from.cmp(to) {
+            std::cmp::Ordering::Less => Some(Direction::Westwards),
+            std::cmp::Ordering::Greater => Some(Direction::Eastwards),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// Distance between two coordinates in km, using the haversine formula.
+fn distance_km((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Calculate the km positions of the stations on the route that don't have one yet, from the
+/// distances between consecutive stations. The first station is at km 0. The chain breaks at
+/// stations without coordinates. Returns the stations that got a km position.
+fn missing_km(route_stations: &[Station]) -> Vec<(i64, f64)> {
+    let mut result = Vec::new();
+    let mut previous: Option<(f64, (f64, f64))> = None;
+    for (i, station) in route_stations.iter().enumerate() {
+        let coordinates = station.latitude.zip(station.longitude);
+        let km = match (station.km, previous, coordinates) {
+            (Some(km), _, _) => Some(km),
+            (None, _, Some(_)) if i == 0 => Some(0.0),
+            (None, Some((previous_km, from)), Some(to)) => {
+                Some(previous_km + distance_km(from, to))
+            }
+            (None, _, _) => None,
+        };
+        if station.km.is_none() {
+            if let Some(km) = km {
+                result.push((station.id, km));
+            }
+        }
+        previous = km.zip(coordinates);
+    }
+    result
+}
+
+/// Load all stations from the db.
+pub fn load_route(db: &mut PgConnection) -> QueryResult<Route> {
+    use crate::schema::stations;
+
+    stations::table
+        .order((stations::position.asc().nulls_last(), stations::id.asc()))
+        .load::<Station>(db)
+        .map(Route::new)
+}
+
+/// Learn stations from the stopovers of one fetched_json row, e.g. because the crawler came
+/// across a station it doesn't know. Returns the updated route.
+pub fn learn_stations(db: &mut PgConnection, fetched_json_id: i64) -> QueryResult<Route> {
+    diesel::sql_query(LEARN_STATIONS.replace(
+        "{source}",
+        "SELECT id, body FROM fetched_json WHERE id = $1",
+    ))
+    .bind::<BigInt, _>(fetched_json_id)
+    .execute(db)?;
+    update_km(db)?;
+    load_route(db)
+}
+
+/// Store the km positions that can be calculated from coordinates.
+fn update_km(db: &mut PgConnection) -> QueryResult<usize> {
+    use crate::schema::stations;
+
+    let route = load_route(db)?;
+    let on_route: Vec<Station> = route
+        .stations
+        .into_iter()
+        .filter(|s| s.position.is_some())
+        .collect();
+    let mut updated = 0;
+    for (id, km) in missing_km(&on_route) {
+        updated += diesel::update(stations::table.find(id))
+            .set(stations::km.eq(km))
+            .execute(db)?;
+    }
+    Ok(updated)
+}
+
+/// Learn stations from the latest trip snapshots and load the route. This runs on startup.
+pub fn update_route(db: &mut PgConnection) -> Result<Route, Box<dyn Error>> {
+    diesel::sql_query(LEARN_STATIONS.replace(
+        "{source}",
+        "SELECT id, body FROM fetched_json WHERE url LIKE '%/trips/%' ORDER BY id DESC LIMIT $1",
+    ))
+    .bind::<BigInt, _>(LEARN_FROM_LATEST)
+    .execute(db)?;
+    let updated = update_km(db)?;
+    if updated > 0 {
+        info!("Calculated the km positions of {} stations.", updated);
+    }
+    Ok(load_route(db)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn station(id: i64, position: i32, coordinates: Option<(f64, f64)>) -> Station {
+        Station {
+            id,
+            name: id.to_string(),
+            short_name: None,
+            important: false,
+            position: Some(position),
+            km: None,
+            latitude: coordinates.map(|c| c.0),
+            longitude: coordinates.map(|c| c.1),
+        }
+    }
+
+    #[test]
+    fn direction_along_route() {
+        let route = Route::new(vec![
+            station(10, 0, None),
+            station(20, 1, None),
+            station(30, 2, None),
+        ]);
+
+        assert_eq!(route.direction(10, 20), Some(Direction::Westwards));
+        assert_eq!(route.direction(30, 10), Some(Direction::Eastwards));
+        assert_eq!(route.direction(20, 20), None);
+        assert_eq!(route.direction(20, 40), None);
+    }
+
+    #[test]
+    fn km_positions_from_coordinates() {
+        // Berlin Hbf, Potsdam Hbf, a station without coordinates and Brandenburg Hbf, which can't
+        // be placed because of that.
+        let stations = vec![
+            station(1, 0, Some((52.5251, 13.3694))),
+            station(2, 1, Some((52.3917, 13.0669))),
+            station(3, 2, None),
+            station(4, 3, Some((52.4113, 12.5626))),
+        ];
+        let km = missing_km(&stations);
+
+        assert_eq!(km.len(), 2);
+        assert_eq!(km[0], (1, 0.0));
+        assert_eq!(km[1].0, 2);
+        assert!((km[1].1 - 25.3).abs() < 0.5, "{}", km[1].1);
+    }
+
+    #[test]
+    fn configured_km_positions_are_kept() {
+        let mut stations = vec![
+            station(1, 0, Some((52.5251, 13.3694))),
+            station(2, 1, Some((52.3917, 13.0669))),
+        ];
+        stations[0].km = Some(100.0);
+        let km = missing_km(&stations);
+
+        assert_eq!(km.len(), 1);
+        assert!((km[0].1 - 125.3).abs() < 0.5, "{}", km[0].1);
+    }
+}
@@ -12,6 +12,9 @@ diesel::table! {
         next_station -> Int8,
         percentage_segment -> Float8,
         delay -> Int8,
+        interpolated -> Bool,
+        has_realtime -> Bool,
+        direction -> Nullable<Text>,
     }
 }
 
@@ -25,6 +28,11 @@ diesel::table! {
         next_station -> Int8,
         percentage_segment -> Float8,
         delay -> Int8,
+        arrival_delay -> Nullable<Int8>,
+        departure_delay -> Nullable<Int8>,
+        has_realtime -> Bool,
+        trip_state -> Text,
+        direction -> Nullable<Text>,
     }
 }
 
@@ -37,6 +45,136 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    quarantine (id) {
+        id -> Int8,
+        fetched_json_id -> Int8,
+        quarantined_at -> Timestamptz,
+        reason -> Text,
+    }
+}
+
+diesel::table! {
+    segment_delays (trip_id, from_station) {
+        trip_id -> Text,
+        from_station -> Int8,
+        to_station -> Int8,
+        direction -> Text,
+        planned_departure -> Timestamptz,
+        departure_delay -> Nullable<Int8>,
+        arrival_delay -> Nullable<Int8>,
+        next_departure_delay -> Nullable<Int8>,
+        running_gain -> Nullable<Int8>,
+        dwell_gain -> Nullable<Int8>,
+        fetched_json_id -> Int8,
+    }
+}
+
+diesel::table! {
+    station_punctuality (station, direction, period, period_start, kind) {
+        station -> Int8,
+        direction -> Text,
+        period -> Text,
+        period_start -> Timestamptz,
+        kind -> Text,
+        count -> Int8,
+        within_0 -> Float8,
+        within_3 -> Float8,
+        within_6 -> Float8,
+        within_15 -> Float8,
+        median_delay -> Float8,
+        p90_delay -> Float8,
+        fetched_json_id -> Int8,
+    }
+}
+
+diesel::table! {
+    stations (id) {
+        id -> Int8,
+        name -> Text,
+        short_name -> Nullable<Text>,
+        important -> Bool,
+        position -> Nullable<Int4>,
+        km -> Nullable<Float8>,
+        latitude -> Nullable<Float8>,
+        longitude -> Nullable<Float8>,
+    }
+}
+
+diesel::table! {
+    station_visits (trip_id, station) {
+        trip_id -> Text,
+        station -> Int8,
+        direction -> Text,
+        planned_arrival -> Nullable<Timestamptz>,
+        arrival_delay -> Nullable<Int8>,
+        planned_departure -> Nullable<Timestamptz>,
+        departure_delay -> Nullable<Int8>,
+        fetched_json_id -> Int8,
+    }
+}
+
+diesel::table! {
+    trip_changes (id) {
+        id -> Int8,
+        trip_id -> Text,
+        fetched_json_id -> Int8,
+        previous_fetched_json_id -> Int8,
+        time -> Timestamptz,
+        stop -> Nullable<Int8>,
+        kind -> Text,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    trip_ids (hafas_trip_id) {
+        hafas_trip_id -> Text,
+        canonical_trip_id -> Text,
+        fetched_json_id -> Int8,
+    }
+}
+
+diesel::table! {
+    trip_summaries (trip_id) {
+        trip_id -> Text,
+        line -> Nullable<Text>,
+        train_number -> Nullable<Text>,
+        direction -> Nullable<Text>,
+        headsign -> Nullable<Text>,
+        origin -> Text,
+        destination -> Text,
+        planned_departure -> Timestamptz,
+        first_observation -> Timestamptz,
+        last_observation -> Timestamptz,
+        start_delay -> Nullable<Int8>,
+        final_delay -> Nullable<Int8>,
+        max_delay -> Nullable<Int8>,
+        stations_covered -> Int8,
+        cancelled -> Bool,
+        cancelled_stops -> Int8,
+        last_fetched_json_id -> Int8,
+    }
+}
+
 diesel::joinable!(delay_records -> fetched_json (fetched_json_id));
+diesel::joinable!(quarantine -> fetched_json (fetched_json_id));
+diesel::joinable!(segment_delays -> fetched_json (fetched_json_id));
+diesel::joinable!(station_visits -> fetched_json (fetched_json_id));
+diesel::joinable!(trip_ids -> fetched_json (fetched_json_id));
+diesel::joinable!(trip_summaries -> fetched_json (last_fetched_json_id));
 
-diesel::allow_tables_to_appear_in_same_query!(delay_events, delay_records, fetched_json,);
+diesel::allow_tables_to_appear_in_same_query!(
+    delay_events,
+    delay_records,
+    fetched_json,
+    quarantine,
+    segment_delays,
+    station_punctuality,
+    station_visits,
+    stations,
+    trip_changes,
+    trip_ids,
+    trip_summaries,
+);
@@ -0,0 +1,144 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Where do trains actually get delayed? For every trip we compare the delays at consecutive
+//! stations from station_visits and store how much delay was gained on the way and while
+//! stopping in segment_delays. Those can then be aggregated per segment and time window.
+
+use crate::models::{Direction, SegmentDelaySummary};
+use crate::statistics::TIME_ZONE;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Text, Timestamptz};
+use log::info;
+use std::error::Error;
+use time::OffsetDateTime;
+
+/// Upserts the segment_delays of all trips matching `{filter}`, which is a WHERE clause on
+/// station_visits that has to select whole trips.
+const UPSERT_SEGMENT_DELAYS: &str = r#"
+INSERT INTO segment_delays
+          ( trip_id
+          , from_station
+          , to_station
+          , direction
+          , planned_departure
+          , departure_delay
+          , arrival_delay
+          , next_departure_delay
+          , running_gain
+          , dwell_gain
+          , fetched_json_id
+          )
+SELECT trip_id
+     , from_station
+     , to_station
+     , direction
+     , planned_departure
+     , departure_delay
+     , arrival_delay
+     , next_departure_delay
+     , arrival_delay - departure_delay
+     , next_departure_delay - arrival_delay
+     , fetched_json_id
+FROM
+  ( SELECT trip_id
+         , station AS from_station
+         , LEAD(station) OVER w AS to_station
+         , direction
+         , planned_departure
+         , departure_delay
+         , LEAD(arrival_delay) OVER w AS arrival_delay
+         , LEAD(departure_delay) OVER w AS next_departure_delay
+         , fetched_json_id
+    FROM station_visits
+    {filter}
+    WINDOW w AS (PARTITION BY trip_id ORDER BY COALESCE(planned_departure, planned_arrival))
+  ) segments
+WHERE to_station IS NOT NULL AND planned_departure IS NOT NULL
+ON CONFLICT (trip_id, from_station) DO UPDATE SET
+    to_station = EXCLUDED.to_station
+  , direction = EXCLUDED.direction
+  , planned_departure = EXCLUDED.planned_departure
+  , departure_delay = EXCLUDED.departure_delay
+  , arrival_delay = EXCLUDED.arrival_delay
+  , next_departure_delay = EXCLUDED.next_departure_delay
+  , running_gain = EXCLUDED.running_gain
+  , dwell_gain = EXCLUDED.dwell_gain
+  , fetched_json_id = EXCLUDED.fetched_json_id
+"#;
+
+/// Aggregates segment_delays of trips departing in a time range. If a period ("hour", "day" or
+/// "week") is given, there is one row per segment and period, otherwise one per segment.
+const SEGMENT_DELAY_SUMMARY: &str = r#"
+SELECT from_station
+     , to_station
+     , direction
+     , date_trunc($6, planned_departure, $7) AS period_start
+     , COUNT(*) AS count
+     , AVG(running_gain)::double precision AS running_gain_mean
+     , percentile_cont(0.5) WITHIN GROUP (ORDER BY running_gain) AS running_gain_median
+     , percentile_cont(0.9) WITHIN GROUP (ORDER BY running_gain) AS running_gain_p90
+     , AVG(dwell_gain)::double precision AS dwell_gain_mean
+     , percentile_cont(0.5) WITHIN GROUP (ORDER BY dwell_gain) AS dwell_gain_median
+     , percentile_cont(0.9) WITHIN GROUP (ORDER BY dwell_gain) AS dwell_gain_p90
+FROM segment_delays
+WHERE planned_departure >= $1 AND planned_departure < $2
+  AND ($3 IS NULL OR from_station = $3)
+  AND ($4 IS NULL OR to_station = $4)
+  AND ($5 IS NULL OR direction = $5)
+GROUP BY from_station, to_station, direction, period_start
+ORDER BY period_start, running_gain_mean DESC NULLS LAST
+"#;
+
+/// Refresh the segment_delays of one trip, e.g. after its station_visits got refreshed.
+pub fn refresh_segment_delays(db: &mut PgConnection, trip_id: &str) -> QueryResult<usize> {
+    diesel::sql_query(UPSERT_SEGMENT_DELAYS.replace("{filter}", "WHERE trip_id = $1"))
+        .bind::<Text, _>(trip_id)
+        .execute(db)
+}
+
+/// Refresh the segment_delays of all trips whose station_visits changed since the last update.
+pub fn update_segment_delays(db: &mut PgConnection) -> Result<(), Box<dyn Error>> {
+    use crate::schema::segment_delays;
+
+    let latest_fetched_json_id: i64 = segment_delays::table
+        .select(diesel::dsl::max(segment_delays::fetched_json_id))
+        .first::<Option<i64>>(db)?
+        .unwrap_or(0);
+
+    let updated = diesel::sql_query(UPSERT_SEGMENT_DELAYS.replace(
+        "{filter}",
+        "WHERE trip_id IN (SELECT trip_id FROM station_visits WHERE fetched_json_id > $1)",
+    ))
+    .bind::<BigInt, _>(latest_fetched_json_id)
+    .execute(db)?;
+
+    if updated > 0 {
+        info!("Updated {} entries in segment_delays table.", updated);
+    }
+
+    Ok(())
+}
+
+/// Delay gained per segment for trips departing between `from` and `to`, optionally per period.
+pub fn segment_delay_summary(
+    db: &mut PgConnection,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    from_station: Option<i64>,
+    to_station: Option<i64>,
+    direction: Option<Direction>,
+    period: Option<String>,
+) -> QueryResult<Vec<SegmentDelaySummary>> {
+    diesel::sql_query(SEGMENT_DELAY_SUMMARY)
+        .bind::<Timestamptz, _>(from)
+        .bind::<Timestamptz, _>(to)
+        .bind::<Nullable<BigInt>, _>(from_station)
+        .bind::<Nullable<BigInt>, _>(to_station)
+        .bind::<Nullable<Text>, _>(direction)
+        .bind::<Nullable<Text>, _>(period)
+        .bind::<Text, _>(TIME_ZONE)
+        .load(db)
+}
@@ -0,0 +1,290 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Punctuality statistics per station.
+//!
+//! For every trip we extract the delays it actually had at each of its stations into
+//! station_visits. Those get aggregated per station, direction and hour, day or week into
+//! station_punctuality. Both tables are derived from trip_summaries and fetched_json, and are
+//! refreshed incrementally.
+
+use crate::models::{Direction, PunctualitySummary};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, BigInt, Integer, Nullable, Text, Timestamptz};
+use log::info;
+use std::error::Error;
+use time::OffsetDateTime;
+
+/// The time zone we use to determine hours, days and weeks.
+pub const TIME_ZONE: &str = "Europe/Berlin";
+
+/// The minutes of delay behind within_0, within_3, within_6 and within_15.
+const WITHIN_MINUTES: [i64; 4] = [0, 3, 6, 15];
+
+/// The largest delay in seconds that still counts as within the given number of minutes.
+///
+/// A delay counts as within N minutes if it is less than N minutes, so a vehicle that is exactly
+/// six minutes late is not within_6. As that would leave within_0 empty, a delay counts as within
+/// 0 minutes if the vehicle was on time or early.
+fn max_delay_within(minutes: i64) -> i64 {
+    if minutes == 0 {
+        0
+    } else {
+        minutes * 60 - 1
+    }
+}
+
+/// The within_* columns aggregated over the delays in seconds of `delay`.
+fn within_columns(delay: &str) -> String {
+    WITHIN_MINUTES
+        .iter()
+        .map(|&minutes| {
+            format!(
+                "AVG(({delay} <= {})::int)::double precision AS within_{minutes}",
+                max_delay_within(minutes)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n     , ")
+}
+
+/// Upserts the station_visits of all trip_summaries matching `{filter}`. Delays are only taken
+/// into account if they lie before the last observation of the trip, as they would be a forecast
+/// otherwise. Trips without a direction aren't on the route and are skipped.
+const UPSERT_STATION_VISITS: &str = r#"
+INSERT INTO station_visits
+          ( trip_id
+          , station
+          , direction
+          , planned_arrival
+          , arrival_delay
+          , planned_departure
+          , departure_delay
+          , fetched_json_id
+          )
+SELECT DISTINCT ON (ts.trip_id, (so #>> '{stop,id}')::bigint)
+       ts.trip_id
+     , (so #>> '{stop,id}')::bigint
+     , ts.direction
+     , (so ->> 'plannedArrival')::timestamptz
+     , CASE WHEN (so ->> 'plannedArrival')::timestamptz
+                 + make_interval(secs => COALESCE((so ->> 'arrivalDelay')::bigint, 0))
+                 <= ts.last_observation
+            THEN (so ->> 'arrivalDelay')::bigint
+       END
+     , (so ->> 'plannedDeparture')::timestamptz
+     , CASE WHEN (so ->> 'plannedDeparture')::timestamptz
+                 + make_interval(secs => COALESCE((so ->> 'departureDelay')::bigint, 0))
+                 <= ts.last_observation
+            THEN (so ->> 'departureDelay')::bigint
+       END
+     , ts.last_fetched_json_id
+FROM trip_summaries ts
+CROSS JOIN LATERAL
+  ( SELECT body::jsonb #> '{trip,stopovers}' AS stopovers
+    FROM fetched_json
+    WHERE id = ts.last_fetched_json_id
+  ) latest
+CROSS JOIN LATERAL jsonb_array_elements(latest.stopovers) so
+WHERE {filter}
+  AND ts.direction IS NOT NULL
+  AND NOT COALESCE((so ->> 'cancelled')::boolean, false)
+ON CONFLICT (trip_id, station) DO UPDATE SET
+    direction = EXCLUDED.direction
+  , planned_arrival = EXCLUDED.planned_arrival
+  , arrival_delay = EXCLUDED.arrival_delay
+  , planned_departure = EXCLUDED.planned_departure
+  , departure_delay = EXCLUDED.departure_delay
+  , fetched_json_id = EXCLUDED.fetched_json_id
+"#;
+
+/// Recomputes all the station_punctuality rows that are affected by station_visits newer than
+/// $1. As station_visits are mostly recent, we only look at the weeks they touch.
+const UPSERT_STATION_PUNCTUALITY: &str = r#"
+WITH new_visits AS
+  ( SELECT * FROM station_visits WHERE fetched_json_id > $1 )
+, since AS
+  ( SELECT date_trunc('week', MIN(LEAST(planned_arrival, planned_departure)), $2) AS t
+    FROM new_visits
+  )
+, visits AS
+  ( SELECT station, direction, 'arrival' AS kind, planned_arrival AS planned
+         , arrival_delay AS delay, fetched_json_id
+    FROM station_visits
+    WHERE arrival_delay IS NOT NULL AND planned_arrival >= (SELECT t FROM since)
+    UNION ALL
+    SELECT station, direction, 'departure', planned_departure, departure_delay, fetched_json_id
+    FROM station_visits
+    WHERE departure_delay IS NOT NULL AND planned_departure >= (SELECT t FROM since)
+  )
+, bucketed AS
+  ( SELECT v.*, p.period, date_trunc(p.period, v.planned, $2) AS period_start
+    FROM visits v
+    CROSS JOIN (VALUES ('hour'), ('day'), ('week')) AS p(period)
+  )
+, touched AS
+  ( SELECT DISTINCT station, direction, period, period_start, kind
+    FROM bucketed
+    WHERE fetched_json_id > $1
+  )
+INSERT INTO station_punctuality
+          ( station
+          , direction
+          , period
+          , period_start
+          , kind
+          , count
+          , within_0
+          , within_3
+          , within_6
+          , within_15
+          , median_delay
+          , p90_delay
+          , fetched_json_id
+          )
+SELECT b.station
+     , b.direction
+     , b.period
+     , b.period_start
+     , b.kind
+     , COUNT(*)
+     , {within}
+     , percentile_cont(0.5) WITHIN GROUP (ORDER BY b.delay)
+     , percentile_cont(0.9) WITHIN GROUP (ORDER BY b.delay)
+     , MAX(b.fetched_json_id)
+FROM bucketed b
+JOIN touched t USING (station, direction, period, period_start, kind)
+GROUP BY b.station, b.direction, b.period, b.period_start, b.kind
+ON CONFLICT (station, direction, period, period_start, kind) DO UPDATE SET
+    count = EXCLUDED.count
+  , within_0 = EXCLUDED.within_0
+  , within_3 = EXCLUDED.within_3
+  , within_6 = EXCLUDED.within_6
+  , within_15 = EXCLUDED.within_15
+  , median_delay = EXCLUDED.median_delay
+  , p90_delay = EXCLUDED.p90_delay
+  , fetched_json_id = EXCLUDED.fetched_json_id
+"#;
+
+/// Aggregates station_visits in a given time range, optionally only on certain weekdays (ISO,
+/// e.g. 1 is monday) and at certain hours of the day.
+const PUNCTUALITY_SUMMARY: &str = r#"
+WITH visits AS
+  ( SELECT station, direction, 'arrival' AS kind, planned_arrival AS planned
+         , arrival_delay AS delay
+    FROM station_visits
+    WHERE arrival_delay IS NOT NULL AND planned_arrival >= $1 AND planned_arrival < $2
+    UNION ALL
+    SELECT station, direction, 'departure', planned_departure, departure_delay
+    FROM station_visits
+    WHERE departure_delay IS NOT NULL AND planned_departure >= $1 AND planned_departure < $2
+  )
+SELECT station
+     , direction
+     , kind
+     , COUNT(*) AS count
+     , {within}
+     , percentile_cont(0.5) WITHIN GROUP (ORDER BY delay) AS median_delay
+     , percentile_cont(0.9) WITHIN GROUP (ORDER BY delay) AS p90_delay
+FROM visits
+WHERE ($3 IS NULL OR station = $3)
+  AND ($4 IS NULL OR direction = $4)
+  AND ($5 IS NULL OR EXTRACT(ISODOW FROM planned AT TIME ZONE $7)::int = ANY($5))
+  AND ($6 IS NULL OR EXTRACT(HOUR FROM planned AT TIME ZONE $7)::int = ANY($6))
+GROUP BY station, direction, kind
+ORDER BY station, direction, kind
+"#;
+
+/// Refresh the station_visits of one trip, e.g. after its trip summary got refreshed.
+pub fn refresh_station_visits(db: &mut PgConnection, trip_id: &str) -> QueryResult<usize> {
+    diesel::sql_query(UPSERT_STATION_VISITS.replace("{filter}", "ts.trip_id = $1"))
+        .bind::<Text, _>(trip_id)
+        .execute(db)
+}
+
+/// Refresh the station_visits of all trips whose summary changed since the last update.
+fn update_station_visits(db: &mut PgConnection) -> QueryResult<usize> {
+    use crate::schema::station_visits;
+
+    let latest_fetched_json_id: i64 = station_visits::table
+        .select(diesel::dsl::max(station_visits::fetched_json_id))
+        .first::<Option<i64>>(db)?
+        .unwrap_or(0);
+
+    diesel::sql_query(UPSERT_STATION_VISITS.replace("{filter}", "ts.last_fetched_json_id > $1"))
+        .bind::<BigInt, _>(latest_fetched_json_id)
+        .execute(db)
+}
+
+/// Recompute all station_punctuality rows affected by station_visits that changed since the last
+/// update.
+pub fn update_station_punctuality(db: &mut PgConnection) -> QueryResult<usize> {
+    use crate::schema::station_punctuality;
+
+    let latest_fetched_json_id: i64 = station_punctuality::table
+        .select(diesel::dsl::max(station_punctuality::fetched_json_id))
+        .first::<Option<i64>>(db)?
+        .unwrap_or(0);
+
+    diesel::sql_query(UPSERT_STATION_PUNCTUALITY.replace("{within}", &within_columns("b.delay")))
+        .bind::<BigInt, _>(latest_fetched_json_id)
+        .bind::<Text, _>(TIME_ZONE)
+        .execute(db)
+}
+
+/// Bring station_visits and station_punctuality up to date with trip_summaries.
+pub fn update_statistics(db: &mut PgConnection) -> Result<(), Box<dyn Error>> {
+    let visits = update_station_visits(db)?;
+    let punctuality = update_station_punctuality(db)?;
+    if visits > 0 || punctuality > 0 {
+        info!(
+            "Updated {} entries in station_visits and {} entries in station_punctuality.",
+            visits, punctuality
+        );
+    }
+    Ok(())
+}
+
+/// Punctuality per station, direction and kind over a time range. Only visits on the given
+/// weekdays and in the given hours of the day are taken into account, if those are set.
+pub fn punctuality_summary(
+    db: &mut PgConnection,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    station: Option<i64>,
+    direction: Option<Direction>,
+    weekdays: Option<Vec<i32>>,
+    hours: Option<Vec<i32>>,
+) -> QueryResult<Vec<PunctualitySummary>> {
+    diesel::sql_query(PUNCTUALITY_SUMMARY.replace("{within}", &within_columns("delay")))
+        .bind::<Timestamptz, _>(from)
+        .bind::<Timestamptz, _>(to)
+        .bind::<Nullable<BigInt>, _>(station)
+        .bind::<Nullable<Text>, _>(direction)
+        .bind::<Nullable<Array<Integer>>, _>(weekdays)
+        .bind::<Nullable<Array<Integer>>, _>(hours)
+        .bind::<Text, _>(TIME_ZONE)
+        .load(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn six_minutes_late_is_not_within_6() {
+        assert!(6 * 60 > max_delay_within(6));
+        assert!(6 * 60 - 1 <= max_delay_within(6));
+        assert!(6 * 60 <= max_delay_within(15));
+    }
+
+    #[test]
+    fn within_0_means_on_time() {
+        assert_eq!(max_delay_within(0), 0);
+        assert!(within_columns("delay").starts_with("AVG((delay <= 0)::int)"));
+        assert!(within_columns("b.delay")
+            .contains("AVG((b.delay <= 359)::int)::double precision AS within_6"));
+    }
+}
@@ -45,6 +45,21 @@ pub enum HafasMsg {
     TripOverviewNotRE1(),
 }
 
+impl HafasMsg {
+    /// Short name of the variant, as used in metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HafasMsg::TripOverview(_) => "trip_overview",
+            HafasMsg::TripsOverview(_) => "trips_overview",
+            HafasMsg::TransportRestErr(_) => "transport_rest_error",
+            HafasMsg::HafasErr(_) => "hafas_error",
+            HafasMsg::EmptyBody() => "empty_body",
+            HafasMsg::BadGatewayError() => "bad_gateway",
+            HafasMsg::TripOverviewNotRE1() => "not_re1",
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct TransportRestErr {
     pub message: String,
@@ -87,6 +102,14 @@ pub struct Trip {
     pub stopovers: Vec<TripStopover>,
     pub departureDelay: Option<i64>,
     pub arrivalDelay: Option<i64>,
+    pub line: Option<TripLine>,
+    pub cancelled: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TripLine {
+    pub name: Option<String>,
+    pub fahrtNr: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -114,6 +137,9 @@ pub struct TripStopover {
     #[serde(with = "time::serde::rfc3339::option")]
     pub plannedDeparture: Option<OffsetDateTime>,
     pub departureDelay: Option<i64>,
+    pub arrivalPlatform: Option<String>,
+    pub departurePlatform: Option<String>,
+    pub cancelled: Option<bool>,
 }
 
 #[serde_as]
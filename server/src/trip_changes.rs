@@ -0,0 +1,403 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! How did the forecast of a trip evolve? We compare every snapshot of a trip with the one before
+//! and store what changed in the trip_changes table, e.g. that the expected delay at a stop went
+//! up or that a stop got cancelled.
+
+use crate::models::{canonical_trip_id, ChangeKind, SelectFetchedJson, TripChange};
+use crate::transport_rest_vbb_v6::{deserialize, HafasMsg, Trip, TripOverview};
+use diesel::pg::{PgConnection, PgRowByRowLoadingMode};
+use diesel::prelude::*;
+use log::info;
+use std::collections::HashMap;
+use std::error::Error;
+use time::{Duration, OffsetDateTime};
+
+/// How many snapshots we look at to find the previous one that can be deserialized.
+const PREVIOUS_SNAPSHOT_CANDIDATES: i64 = 10;
+
+/// Trips we haven't seen for this long are forgotten while updating the table, so we don't keep
+/// the whole history in memory.
+const FORGET_AFTER: Duration = Duration::DAY;
+
+/// The state of one stop of a trip, as far as we compare it.
+#[derive(Debug, Clone, PartialEq)]
+struct StopSnapshot {
+    stop: i64,
+    arrival_delay: Option<i64>,
+    departure_delay: Option<i64>,
+    arrival_platform: Option<String>,
+    departure_platform: Option<String>,
+    cancelled: bool,
+}
+
+/// What we remember of a snapshot of a trip to compare it with the next one.
+#[derive(Debug, Clone, PartialEq)]
+struct TripSnapshot {
+    fetched_json_id: i64,
+    fetched_at: OffsetDateTime,
+    cancelled: bool,
+    stops: Vec<StopSnapshot>,
+}
+
+impl TripSnapshot {
+    fn new(fetched_json_id: i64, fetched_at: OffsetDateTime, trip: &Trip) -> TripSnapshot {
+        TripSnapshot {
+            fetched_json_id,
+            fetched_at,
+            cancelled: trip.cancelled.unwrap_or(false),
+            stops: trip
+                .stopovers
+                .iter()
+                .map(|so| StopSnapshot {
+                    stop: so.stop.id,
+                    arrival_delay: so.arrivalDelay,
+                    departure_delay: so.departureDelay,
+                    arrival_platform: so.arrivalPlatform.clone(),
+                    departure_platform: so.departurePlatform.clone(),
+                    cancelled: so.cancelled.unwrap_or(false),
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether HAFAS knows the delay of at least one stop.
+    fn has_realtime(&self) -> bool {
+        self.stops
+            .iter()
+            .any(|s| s.arrival_delay.is_some() || s.departure_delay.is_some())
+    }
+}
+
+/// One change, without the information which snapshots it is about.
+#[derive(Debug, Clone, PartialEq)]
+struct Change {
+    stop: Option<i64>,
+    kind: ChangeKind,
+    old_value: Option<String>,
+    new_value: Option<String>,
+}
+
+impl Change {
+    fn new(stop: Option<i64>, kind: ChangeKind) -> Change {
+        Change {
+            stop,
+            kind,
+            old_value: None,
+            new_value: None,
+        }
+    }
+
+    /// A change of a value, if it changed at all.
+    fn value<T: ToString + PartialEq>(
+        stop: i64,
+        kind: ChangeKind,
+        old: &Option<T>,
+        new: &Option<T>,
+    ) -> Option<Change> {
+        (old != new).then(|| Change {
+            stop: Some(stop),
+            kind,
+            old_value: old.as_ref().map(T::to_string),
+            new_value: new.as_ref().map(T::to_string),
+        })
+    }
+}
+
+/// Everything that changed from one snapshot of a trip to the next. Delays are only compared if
+/// both snapshots have realtime data, otherwise we only note that it got lost or restored.
+fn diff(old: &TripSnapshot, new: &TripSnapshot) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    match (old.cancelled, new.cancelled) {
+        (false, true) => changes.push(Change::new(None, ChangeKind::TripCancelled)),
+        (true, false) => changes.push(Change::new(None, ChangeKind::TripReinstated)),
+        _ => {}
+    }
+    let compare_delays = match (old.has_realtime(), new.has_realtime()) {
+        (true, false) => {
+            changes.push(Change::new(None, ChangeKind::RealtimeLost));
+            false
+        }
+        (false, true) => {
+            changes.push(Change::new(None, ChangeKind::RealtimeRestored));
+            false
+        }
+        (realtime, _) => realtime,
+    };
+
+    let old_stops: HashMap<i64, &StopSnapshot> = old.stops.iter().map(|s| (s.stop, s)).collect();
+    for new_stop in &new.stops {
+        let stop = new_stop.stop;
+        let Some(old_stop) = old_stops.get(&stop) else {
+            changes.push(Change::new(Some(stop), ChangeKind::StopAdded));
+            continue;
+        };
+        match (old_stop.cancelled, new_stop.cancelled) {
+            (false, true) => changes.push(Change::new(Some(stop), ChangeKind::StopCancelled)),
+            (true, false) => changes.push(Change::new(Some(stop), ChangeKind::StopReinstated)),
+            _ => {}
+        }
+        if compare_delays {
+            changes.extend(Change::value(
+                stop,
+                ChangeKind::ArrivalDelay,
+                &old_stop.arrival_delay,
+                &new_stop.arrival_delay,
+            ));
+            changes.extend(Change::value(
+                stop,
+                ChangeKind::DepartureDelay,
+                &old_stop.departure_delay,
+                &new_stop.departure_delay,
+            ));
+        }
+        changes.extend(Change::value(
+            stop,
+            ChangeKind::ArrivalPlatform,
+            &old_stop.arrival_platform,
+            &new_stop.arrival_platform,
+        ));
+        changes.extend(Change::value(
+            stop,
+            ChangeKind::DeparturePlatform,
+            &old_stop.departure_platform,
+            &new_stop.departure_platform,
+        ));
+    }
+    for old_stop in &old.stops {
+        if !new.stops.iter().any(|s| s.stop == old_stop.stop) {
+            changes.push(Change::new(Some(old_stop.stop), ChangeKind::StopRemoved));
+        }
+    }
+
+    changes
+}
+
+/// The TripChange's from one snapshot of a trip to the next.
+fn trip_changes(trip_id: &str, old: &TripSnapshot, new: &TripSnapshot) -> Vec<TripChange> {
+    diff(old, new)
+        .into_iter()
+        .map(|change| TripChange {
+            trip_id: trip_id.to_string(),
+            fetched_json_id: new.fetched_json_id,
+            previous_fetched_json_id: old.fetched_json_id,
+            time: new.fetched_at,
+            stop: change.stop,
+            kind: change.kind,
+            old_value: change.old_value,
+            new_value: change.new_value,
+        })
+        .collect()
+}
+
+/// The latest snapshot of a trip before the given fetched_json row, under any of its HAFAS trip
+/// ids.
+fn previous_snapshot(
+    db: &mut PgConnection,
+    trip_id: &str,
+    before: i64,
+) -> QueryResult<Option<TripSnapshot>> {
+    use crate::schema::fetched_json;
+
+    let urls: Vec<String> = crate::trip_ids::hafas_trip_ids(db, trip_id)?
+        .iter()
+        .map(|hafas_trip_id| crate::crawler::trips_url(hafas_trip_id))
+        .collect();
+    let candidates = fetched_json::table
+        .filter(fetched_json::url.eq_any(urls))
+        .filter(fetched_json::id.lt(before))
+        .order(fetched_json::id.desc())
+        .limit(PREVIOUS_SNAPSHOT_CANDIDATES)
+        .load::<SelectFetchedJson>(db)?;
+    Ok(candidates
+        .into_iter()
+        .find_map(|fj| match deserialize(&fj.body) {
+            Ok(HafasMsg::TripOverview(TripOverview { trip, .. })) => {
+                Some(TripSnapshot::new(fj.id, fj.fetched_at, &trip))
+            }
+            _ => None,
+        }))
+}
+
+fn insert_trip_changes(db: &mut PgConnection, changes: &[TripChange]) -> QueryResult<usize> {
+    use crate::schema::trip_changes;
+
+    // PostgreSQL doesn't allow more than 65535 parameters per statement
+    let chunk_size = 1024;
+    let mut inserted = 0;
+    for chunk in changes.chunks(chunk_size) {
+        inserted += diesel::insert_into(trip_changes::table)
+            .values(chunk)
+            .execute(db)?;
+    }
+    Ok(inserted)
+}
+
+/// Compare a freshly fetched snapshot of a trip with the one before and store what changed.
+pub fn record_trip_changes(
+    db: &mut PgConnection,
+    fetched_json_id: i64,
+    fetched_at: OffsetDateTime,
+    trip: &Trip,
+) -> QueryResult<usize> {
+    use crate::schema::trip_changes;
+
+    let trip_id = canonical_trip_id(trip);
+    let Some(previous) = previous_snapshot(db, &trip_id, fetched_json_id)? else {
+        return Ok(0);
+    };
+    let changes = trip_changes(
+        &trip_id,
+        &previous,
+        &TripSnapshot::new(fetched_json_id, fetched_at, trip),
+    );
+    diesel::delete(trip_changes::table.filter(trip_changes::fetched_json_id.eq(fetched_json_id)))
+        .execute(db)?;
+    insert_trip_changes(db, &changes)
+}
+
+/// Compare all snapshots of trips that were fetched since the last update. This needs a second
+/// db connection, as we stream the snapshots with the first one.
+pub fn update_trip_changes(
+    db1: &mut PgConnection,
+    db2: &mut PgConnection,
+) -> Result<(), Box<dyn Error>> {
+    use crate::schema::{fetched_json, trip_changes};
+
+    let latest_fetched_json_id: i64 = trip_changes::table
+        .select(diesel::dsl::max(trip_changes::fetched_json_id))
+        .first::<Option<i64>>(db1)?
+        .unwrap_or(0);
+
+    let snapshots = fetched_json::table
+        .filter(fetched_json::id.gt(latest_fetched_json_id))
+        .filter(fetched_json::url.like("%/trips/%"))
+        .order(fetched_json::id.asc())
+        .load_iter::<SelectFetchedJson, PgRowByRowLoadingMode>(db1)?;
+
+    let mut latest: HashMap<String, TripSnapshot> = HashMap::new();
+    let mut changes: Vec<TripChange> = Vec::new();
+    let mut updated = 0;
+    for fj in snapshots {
+        let fj = fj?;
+        let Ok(HafasMsg::TripOverview(TripOverview { trip, .. })) = deserialize(&fj.body) else {
+            continue;
+        };
+        let trip_id = canonical_trip_id(&trip);
+        let snapshot = TripSnapshot::new(fj.id, fj.fetched_at, &trip);
+        let previous = match latest.remove(&trip_id) {
+            Some(previous) => Some(previous),
+            None => previous_snapshot(db2, &trip_id, fj.id)?,
+        };
+        if let Some(previous) = previous {
+            changes.extend(trip_changes(&trip_id, &previous, &snapshot));
+        }
+        latest.insert(trip_id, snapshot);
+
+        if changes.len() >= 1024 {
+            updated += insert_trip_changes(db2, &changes)?;
+            changes.clear();
+            latest.retain(|_, s| s.fetched_at > fj.fetched_at - FORGET_AFTER);
+        }
+    }
+    updated += insert_trip_changes(db2, &changes)?;
+
+    if updated > 0 {
+        info!("Updated {} entries in trip_changes table.", updated);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop(stop: i64, delay: Option<i64>) -> StopSnapshot {
+        StopSnapshot {
+            stop,
+            arrival_delay: delay,
+            departure_delay: delay,
+            arrival_platform: Some("1".to_string()),
+            departure_platform: Some("1".to_string()),
+            cancelled: false,
+        }
+    }
+
+    fn snapshot(fetched_json_id: i64, stops: Vec<StopSnapshot>) -> TripSnapshot {
+        TripSnapshot {
+            fetched_json_id,
+            fetched_at: OffsetDateTime::UNIX_EPOCH + Duration::minutes(fetched_json_id),
+            cancelled: false,
+            stops,
+        }
+    }
+
+    #[test]
+    fn unchanged_trip() {
+        let old = snapshot(1, vec![stop(1, Some(60)), stop(2, Some(60))]);
+        let new = snapshot(2, vec![stop(1, Some(60)), stop(2, Some(60))]);
+
+        assert_eq!(trip_changes("t", &old, &new), vec![]);
+    }
+
+    #[test]
+    fn delay_and_platform_changes() {
+        let old = snapshot(1, vec![stop(1, Some(60)), stop(2, Some(60))]);
+        let mut new = snapshot(2, vec![stop(1, Some(60)), stop(2, Some(180))]);
+        new.stops[1].departure_delay = Some(60);
+        new.stops[0].departure_platform = Some("2".to_string());
+
+        let changes = trip_changes("t", &old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].stop, Some(1));
+        assert_eq!(changes[0].kind, ChangeKind::DeparturePlatform);
+        assert_eq!(changes[0].new_value.as_deref(), Some("2"));
+        assert_eq!(changes[1].stop, Some(2));
+        assert_eq!(changes[1].kind, ChangeKind::ArrivalDelay);
+        assert_eq!(changes[1].old_value.as_deref(), Some("60"));
+        assert_eq!(changes[1].new_value.as_deref(), Some("180"));
+        assert_eq!(changes[1].fetched_json_id, 2);
+        assert_eq!(changes[1].previous_fetched_json_id, 1);
+        assert_eq!(changes[1].time, new.fetched_at);
+    }
+
+    #[test]
+    fn cancellations_and_rerouting() {
+        let old = snapshot(1, vec![stop(1, Some(60)), stop(2, Some(60)), stop(3, None)]);
+        let mut new = snapshot(2, vec![stop(1, Some(60)), stop(2, Some(60)), stop(4, None)]);
+        new.cancelled = true;
+        new.stops[1].cancelled = true;
+
+        let kinds: Vec<(Option<i64>, ChangeKind)> = diff(&old, &new)
+            .into_iter()
+            .map(|c| (c.stop, c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (None, ChangeKind::TripCancelled),
+                (Some(2), ChangeKind::StopCancelled),
+                (Some(4), ChangeKind::StopAdded),
+                (Some(3), ChangeKind::StopRemoved),
+            ]
+        );
+    }
+
+    #[test]
+    fn realtime_lost_and_restored() {
+        let with_realtime = snapshot(1, vec![stop(1, Some(60)), stop(2, Some(120))]);
+        let without_realtime = snapshot(2, vec![stop(1, None), stop(2, None)]);
+
+        let lost = diff(&with_realtime, &without_realtime);
+        assert_eq!(lost, vec![Change::new(None, ChangeKind::RealtimeLost)]);
+        let restored = diff(&without_realtime, &with_realtime);
+        assert_eq!(
+            restored,
+            vec![Change::new(None, ChangeKind::RealtimeRestored)]
+        );
+    }
+}
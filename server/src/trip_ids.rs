@@ -0,0 +1,51 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The trip_ids table remembers which HAFAS trip ids belong to which canonical trip id, see
+//! [crate::models::canonical_trip_id].
+
+use crate::models::TripIdMapping;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+/// Remember the given mappings. Mappings of HAFAS trip ids we already know are left untouched.
+pub fn record_trip_ids(db: &mut PgConnection, mappings: &[TripIdMapping]) -> QueryResult<usize> {
+    use crate::schema::trip_ids;
+
+    // PostgreSQL doesn't allow more than 65535 parameters per statement
+    let chunk_size = 1024;
+    let mut inserted = 0;
+    for chunk in mappings.chunks(chunk_size) {
+        inserted += diesel::insert_into(trip_ids::table)
+            .values(chunk)
+            .on_conflict(trip_ids::hafas_trip_id)
+            .do_nothing()
+            .execute(db)?;
+    }
+    Ok(inserted)
+}
+
+/// All the HAFAS trip ids we saw for a canonical trip id, in the order we first saw them.
+pub fn hafas_trip_ids(db: &mut PgConnection, canonical_trip_id: &str) -> QueryResult<Vec<String>> {
+    use crate::schema::trip_ids;
+
+    trip_ids::table
+        .select(trip_ids::hafas_trip_id)
+        .filter(trip_ids::canonical_trip_id.eq(canonical_trip_id))
+        .order(trip_ids::fetched_json_id.asc())
+        .load(db)
+}
+
+/// The canonical trip id of a trip id that may also be a HAFAS trip id. Trip ids we don't know
+/// as HAFAS trip ids are assumed to be canonical already.
+pub fn resolve_trip_id(db: &mut PgConnection, trip_id: &str) -> QueryResult<String> {
+    use crate::schema::trip_ids;
+
+    Ok(trip_ids::table
+        .find(trip_id)
+        .select(trip_ids::canonical_trip_id)
+        .first::<String>(db)
+        .optional()?
+        .unwrap_or_else(|| trip_id.to_string()))
+}
@@ -0,0 +1,138 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The trip_summaries table holds key figures for every trip. It is derived from delay_records
+//! and the latest fetched_json row of each trip, so it can be rebuilt at any time.
+
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use log::info;
+use std::error::Error;
+
+/// Upserts the summaries of all trips matching `{filter}`, which is a WHERE clause on
+/// delay_records.
+const UPSERT_TRIP_SUMMARIES: &str = r#"
+INSERT INTO trip_summaries
+          ( trip_id
+          , line
+          , train_number
+          , direction
+          , headsign
+          , origin
+          , destination
+          , planned_departure
+          , first_observation
+          , last_observation
+          , start_delay
+          , final_delay
+          , max_delay
+          , stations_covered
+          , cancelled
+          , cancelled_stops
+          , last_fetched_json_id
+          )
+SELECT dr.trip_id
+     , latest.trip #>> '{line,name}'
+     , latest.trip #>> '{line,fahrtNr}'
+     , dr.direction
+     , latest.trip #>> '{direction}'
+     , latest.trip #>> '{origin,name}'
+     , latest.trip #>> '{destination,name}'
+     , (latest.trip #>> '{plannedDeparture}')::timestamptz
+     , dr.first_observation
+     , dr.last_observation
+     , dr.start_delay
+     , dr.final_delay
+     , dr.max_delay
+     , dr.stations_covered
+     , COALESCE((latest.trip #>> '{cancelled}')::boolean, false)
+     , ( SELECT COUNT(*)
+         FROM jsonb_array_elements(latest.trip -> 'stopovers') so
+         WHERE COALESCE((so ->> 'cancelled')::boolean, false)
+       )
+     , dr.last_fetched_json_id
+FROM
+  ( SELECT trip_id
+         , (ARRAY_AGG(direction ORDER BY time DESC) FILTER (WHERE direction IS NOT NULL))[1]
+             AS direction
+         , MIN(time) AS first_observation
+         , MAX(time) AS last_observation
+         , (ARRAY_AGG(delay ORDER BY time ASC) FILTER (WHERE has_realtime))[1] AS start_delay
+         , (ARRAY_AGG(delay ORDER BY time DESC) FILTER (WHERE has_realtime))[1] AS final_delay
+         , MAX(delay) FILTER (WHERE has_realtime) AS max_delay
+         , COUNT(DISTINCT previous_station)
+             + CASE WHEN BOOL_OR(trip_state = 'at_terminus') THEN 1 ELSE 0 END
+             AS stations_covered
+         , MAX(fetched_json_id) AS last_fetched_json_id
+    FROM delay_records
+    {filter}
+    GROUP BY trip_id
+  ) dr
+CROSS JOIN LATERAL
+  ( SELECT body::jsonb -> 'trip' AS trip
+    FROM fetched_json
+    WHERE id = dr.last_fetched_json_id
+  ) latest
+ON CONFLICT (trip_id) DO UPDATE SET
+    line = EXCLUDED.line
+  , train_number = EXCLUDED.train_number
+  , direction = EXCLUDED.direction
+  , headsign = EXCLUDED.headsign
+  , origin = EXCLUDED.origin
+  , destination = EXCLUDED.destination
+  , planned_departure = EXCLUDED.planned_departure
+  , first_observation = EXCLUDED.first_observation
+  , last_observation = EXCLUDED.last_observation
+  , start_delay = EXCLUDED.start_delay
+  , final_delay = EXCLUDED.final_delay
+  , max_delay = EXCLUDED.max_delay
+  , stations_covered = EXCLUDED.stations_covered
+  , cancelled = EXCLUDED.cancelled
+  , cancelled_stops = EXCLUDED.cancelled_stops
+  , last_fetched_json_id = EXCLUDED.last_fetched_json_id
+"#;
+
+fn upsert_query(filter: &str) -> String {
+    UPSERT_TRIP_SUMMARIES.replace("{filter}", filter)
+}
+
+/// Refresh the summary of one trip, e.g. after the crawler got a new DelayRecord for it.
+pub fn refresh_trip_summary(db: &mut PgConnection, trip_id: &str) -> QueryResult<usize> {
+    diesel::sql_query(upsert_query("WHERE trip_id = $1"))
+        .bind::<Text, _>(trip_id)
+        .execute(db)
+}
+
+/// Refresh the summaries of all trips that got new DelayRecord's since the summaries were last
+/// updated. On an empty trip_summaries table, this builds all of them.
+pub fn update_trip_summaries(db: &mut PgConnection) -> Result<(), Box<dyn Error>> {
+    use crate::schema::trip_summaries;
+
+    let latest_fetched_json_id: i64 = trip_summaries::table
+        .select(diesel::dsl::max(trip_summaries::last_fetched_json_id))
+        .first::<Option<i64>>(db)?
+        .unwrap_or(0);
+
+    let updated = diesel::sql_query(upsert_query(
+        "WHERE trip_id IN (SELECT trip_id FROM delay_records WHERE fetched_json_id > $1)",
+    ))
+    .bind::<BigInt, _>(latest_fetched_json_id)
+    .execute(db)?;
+
+    if updated > 0 {
+        info!("Updated {} entries in trip_summaries table.", updated);
+    }
+
+    Ok(())
+}
+
+/// Throw away all trip summaries and build them again from delay_records.
+pub fn rebuild_trip_summaries(db: &mut PgConnection) -> Result<(), Box<dyn Error>> {
+    info!("Rebuilding trip_summaries table...");
+    db.transaction(|db| {
+        diesel::delete(crate::schema::trip_summaries::table).execute(db)?;
+        update_trip_summaries(db)
+    })
+}
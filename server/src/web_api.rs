@@ -2,75 +2,427 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use crate::models::{DelayEvent, Stopover};
+use crate::cache::MAX_DELAY_RECORD_GAP;
+use crate::export::{ExportFormat, ExportTable};
+use crate::health::{Health, Status as ServerStatus, SubscriberKind};
+use crate::metrics::METRICS;
+use crate::models::{
+    DelayEvent, DelayEventsPage, DelayRecord, DelayRecordWithID, Direction, HeatmapBin,
+    PunctualitySummary, Quarantine, RawFetchedJson, SegmentDelaySummary, Station,
+    StationPunctuality, Stopover, StopoverWithTimes, TripChange, TripSummary,
+};
+use crate::render::{Diagram, RenderFormat, MAX_RENDER_RANGE};
 use crate::transport_rest_vbb_v6::{HafasMsg, TripOverview};
+use crate::web_utils::{
+    send_json_array, Cached, ChunkWriter, Compression, Conditional, Validators,
+};
+use crate::ws_api::HistoricDelayRecords;
+use bus::BusReadHandle;
+use diesel::pg::PgRowByRowLoadingMode;
+use diesel::BoolExpressionMethods;
 use diesel::ExpressionMethods;
+use diesel::OptionalExtension;
 use diesel::QueryDsl;
+use diesel::QueryResult;
 use diesel::RunQueryDsl;
-use log::info;
-use rocket::http::Status;
+use diesel::SelectableHelper;
+use log::{debug, error, info};
+use rocket::http::{ContentType, Header, Status};
+use rocket::request::{FromRequest, Outcome};
+use rocket::response::stream::{ByteStream, Event, EventStream};
 use rocket::serde::json::Json;
 use rocket::tokio;
-use rocket::{get, routes};
+use rocket::{get, routes, Request, Responder, State};
 use rocket_sync_db_pools::{database, diesel};
+use std::error::Error;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
 use time::{Duration, OffsetDateTime};
+use utoipa::OpenApi;
 
 #[database("isre1late")]
 struct DbConn(diesel::PgConnection);
 
-async fn load_delay_events(
+impl DbConn {
+    /// Like `run`, but records how long `f` took in the db_query_duration metric. Waiting for a
+    /// connection from the pool doesn't count, so `f` should do nothing but querying.
+    async fn timed_run<F, R>(&self, endpoint: &'static str, f: F) -> R
+    where
+        F: FnOnce(&mut diesel::PgConnection) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.run(move |db| {
+            let _timer = query_timer(endpoint);
+            f(db)
+        })
+        .await
+    }
+}
+
+/// The pool behind DbConn, for responses that take a connection from it only while querying,
+/// instead of holding one for as long as they are being sent.
+struct DbPool(rocket_sync_db_pools::ConnectionPool<DbConn, diesel::PgConnection>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for DbPool {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match DbConn::pool(request.rocket()) {
+            Some(pool) => Outcome::Success(DbPool(pool.clone())),
+            None => Outcome::Error((Status::InternalServerError, ())),
+        }
+    }
+}
+
+/// Start timing a query for the db_query_duration metric. The duration is recorded when the timer
+/// is dropped. Streamed responses stop it once the first row arrived, so neither sending the rest
+/// to a slow client nor serializing it counts.
+fn query_timer(endpoint: &str) -> prometheus::HistogramTimer {
+    METRICS
+        .db_query_duration
+        .with_label_values(&[endpoint])
+        .start_timer()
+}
+
+/// Lets every SSE subscriber get its own receiver of the DelayRecord's the crawler broadcasts.
+struct DelayRecordBus(Mutex<BusReadHandle<DelayRecord>>);
+
+/// Request guard of the endpoints that read the cache tables. They answer with 503 Service
+/// Unavailable until the cache tables are up to date after startup, as they would serve incomplete
+/// data until then.
+struct CachesReady;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CachesReady {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.rocket().state::<Arc<Health>>() {
+            Some(health) if health.caches_are_ready() => Outcome::Success(CachesReady),
+            _ => Outcome::Error((Status::ServiceUnavailable, ())),
+        }
+    }
+}
+
+/// How many chunks of a streamed response may be buffered, if the client reads slower than we
+/// load from the db.
+const STREAM_BUFFER: usize = 16;
+
+/// Parse an optional time range given as unix timestamps. By default, the range ends now and
+/// starts `default_range` before its end.
+fn time_range(
+    from: Option<i64>,
+    to: Option<i64>,
+    default_range: Duration,
+) -> Result<(OffsetDateTime, OffsetDateTime), Status> {
+    let to = match to {
+        Some(to) => OffsetDateTime::from_unix_timestamp(to).map_err(|_| Status::BadRequest)?,
+        None => OffsetDateTime::now_utc(),
+    };
+    let from = match from {
+        Some(from) => OffsetDateTime::from_unix_timestamp(from).map_err(|_| Status::BadRequest)?,
+        None => to - default_range,
+    };
+    Ok((from, to))
+}
+
+/// Stream all delay_events since `from` as a JSON array. The events are serialized while they are
+/// loaded from the db, so we never hold the whole (potentially large) array in memory. We only
+/// answer once the first row arrived, so a failing query still results in an error status.
+async fn stream_delay_events(
     conn: DbConn,
     from: OffsetDateTime,
-) -> Result<Json<Vec<DelayEvent>>, Status> {
-    conn.run(move |db| {
-        use crate::schema::delay_events;
+) -> Result<(ContentType, ByteStream![Vec<u8>]), Status> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<u8>>(STREAM_BUFFER);
+    let (started_tx, started_rx) = tokio::sync::oneshot::channel::<()>();
+    tokio::spawn(async move {
+        let result = conn
+            .run(move |db| -> Result<(), Box<dyn Error + Send + Sync>> {
+                use crate::schema::delay_events;
 
-        info!("Sending DelayEvents coming from {}", from);
+                info!("Sending DelayEvents coming from {}", from);
 
-        delay_events::dsl::delay_events
-            .select((
-                delay_events::from_id,
-                delay_events::to_id,
-                delay_events::trip_id,
-                delay_events::time,
-                delay_events::duration,
-                delay_events::previous_station,
-                delay_events::next_station,
-                delay_events::percentage_segment,
-                delay_events::delay,
-            ))
-            .filter(delay_events::time.gt(from))
-            .load::<DelayEvent>(db)
-    })
-    .await
-    .map(Json)
-    .map_err(|_| rocket::http::Status::InternalServerError)
+                let timer = query_timer("stream_delay_events");
+                let mut events = delay_events::dsl::delay_events
+                    .select(DelayEvent::as_select())
+                    .filter(delay_events::time.gt(from))
+                    .load_iter::<DelayEvent, PgRowByRowLoadingMode>(db)?;
+                let first = events.next().transpose()?;
+                timer.observe_duration();
+                let _ = started_tx.send(());
+                send_json_array(first.into_iter().map(Ok).chain(events), &tx)
+            })
+            .await;
+        if let Err(e) = result {
+            error!("Unable to stream DelayEvents: {}", e);
+        }
+    });
+    // The sender is dropped without sending, if the query failed.
+    started_rx
+        .await
+        .map_err(|_| rocket::http::Status::InternalServerError)?;
+    Ok((
+        ContentType::JSON,
+        ByteStream! {
+            while let Some(chunk) = rx.recv().await {
+                yield chunk;
+            }
+        },
+    ))
 }
 
 /// Load delay_events for the last 24 hours
+#[utoipa::path(responses(
+    (status = 200, description = "DelayEvent's ordered by time", body = Vec<DelayEvent>),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
 #[get("/api/delay_events/day")]
-async fn delay_events_day(conn: DbConn) -> Result<Json<Vec<DelayEvent>>, Status> {
+async fn delay_events_day(
+    _ready: CachesReady,
+    conn: DbConn,
+) -> Result<(ContentType, ByteStream![Vec<u8>]), Status> {
     let from = OffsetDateTime::now_utc() - Duration::DAY;
-    load_delay_events(conn, from).await
+    stream_delay_events(conn, from).await
 }
 
 /// Load delay_events for the last 7 days
+#[utoipa::path(responses(
+    (status = 200, description = "DelayEvent's ordered by time", body = Vec<DelayEvent>),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
 #[get("/api/delay_events/week")]
-async fn delay_events_week(conn: DbConn) -> Result<Json<Vec<DelayEvent>>, Status> {
+async fn delay_events_week(
+    _ready: CachesReady,
+    conn: DbConn,
+) -> Result<(ContentType, ByteStream![Vec<u8>]), Status> {
     let from = OffsetDateTime::now_utc() - Duration::WEEK;
-    load_delay_events(conn, from).await
+    stream_delay_events(conn, from).await
 }
 
-/// Detailed information about one Trip, identified by its trip_id.
-#[get("/api/trip/<trip_id>")]
-async fn trip(conn: DbConn, trip_id: String) -> Result<Json<Vec<Stopover>>, Status> {
-    conn.run(move |db| {
+/// Maximum time range of /api/delay_events.
+const MAX_DELAY_EVENTS_RANGE: Duration = Duration::days(31);
+
+/// Maximum amount of delay events per page of /api/delay_events.
+const MAX_DELAY_EVENTS_PAGE: i64 = 10000;
+
+/// A time range that ended at least this long ago doesn't get new delay_events anymore, so
+/// responses covering it can be cached. On top of the gap between the DelayRecord's an event is
+/// built from, we leave the crawler some time to catch up.
+const CLOSED_RANGE_AGE: Duration = MAX_DELAY_RECORD_GAP.saturating_add(Duration::HOUR);
+
+/// The cursor of /api/delay_events points at the last delay event of a page, by its time (as
+/// unix timestamp in microseconds) and its id, e.g. "1700000000000000_42".
+fn delay_events_cursor(time: OffsetDateTime, id: i64) -> String {
+    format!("{}_{}", time.unix_timestamp_nanos() / 1000, id)
+}
+
+fn parse_delay_events_cursor(cursor: &str) -> Option<(OffsetDateTime, i64)> {
+    let (micros, id) = cursor.split_once('_')?;
+    let nanos = micros.parse::<i128>().ok()?.checked_mul(1000)?;
+    let time = OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?;
+    Some((time, id.parse().ok()?))
+}
+
+/// DelayEvent's in an arbitrary time range of at most 31 days, given as unix timestamps. By
+/// default, this is the last day. The events can be filtered by trip, by a station at either end
+/// of their segment and by line. Results are paginated, pass the `next` cursor of a page as
+/// `after` to get the following one. The raw HAFAS responses an event is built from can be
+/// requested with its from_id and to_id at /api/raw/{fetched_json_id}. Responses for time ranges
+/// that ended more than a few hours ago can be cached, all others can be revalidated with their
+/// ETag.
+#[utoipa::path(responses(
+    (status = 200, description = "One page of DelayEvent's", body = DelayEventsPage),
+    (status = 304, description = "The cached page is still valid"),
+    (status = 400, description = "Invalid time range, cursor or limit"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/delay_events?<from>&<to>&<trip_id>&<station>&<line>&<after>&<limit>")]
+#[allow(clippy::too_many_arguments)]
+async fn delay_events(
+    _ready: CachesReady,
+    conn: DbConn,
+    from: Option<i64>,
+    to: Option<i64>,
+    trip_id: Option<String>,
+    station: Option<i64>,
+    line: Option<String>,
+    after: Option<String>,
+    limit: Option<i64>,
+    conditional: Conditional,
+) -> Result<Cached<Json<DelayEventsPage>>, Status> {
+    let (from, to) = time_range(from, to, Duration::DAY)?;
+    if from >= to || to - from > MAX_DELAY_EVENTS_RANGE {
+        return Err(Status::BadRequest);
+    }
+    let after = match after {
+        Some(after) => Some(parse_delay_events_cursor(&after).ok_or(Status::BadRequest)?),
+        None => None,
+    };
+    let limit = limit
+        .unwrap_or(MAX_DELAY_EVENTS_PAGE)
+        .clamp(1, MAX_DELAY_EVENTS_PAGE);
+
+    let immutable = to <= OffsetDateTime::now_utc() - CLOSED_RANGE_AGE;
+    let validators = conn
+        .timed_run("delay_events", move |db| {
+            delay_events_validators(db, from, to, immutable)
+        })
+        .await
+        .map_err(|_| rocket::http::Status::InternalServerError)?;
+    if let Some(validators) = validators.as_ref().filter(|v| conditional.is_fresh(v)) {
+        return Ok(Cached::NotModified(validators.clone()));
+    }
+
+    let page = conn
+        .timed_run("delay_events", move |db| {
+            use crate::schema::delay_events;
+            use crate::schema::trip_summaries;
+
+            let mut query = delay_events::table
+                .select((delay_events::id, DelayEvent::as_select()))
+                .filter(delay_events::time.ge(from))
+                .filter(delay_events::time.lt(to))
+                .into_boxed();
+            if let Some(trip_id) = trip_id {
+                query = query.filter(delay_events::trip_id.eq(trip_id));
+            }
+            if let Some(station) = station {
+                query = query.filter(
+                    delay_events::previous_station
+                        .eq(station)
+                        .or(delay_events::next_station.eq(station)),
+                );
+            }
+            if let Some(line) = line {
+                query = query.filter(
+                    delay_events::trip_id.eq_any(
+                        trip_summaries::table
+                            .select(trip_summaries::trip_id)
+                            .filter(trip_summaries::line.eq(line)),
+                    ),
+                );
+            }
+            if let Some((time, id)) = after {
+                query = query.filter(
+                    delay_events::time
+                        .gt(time)
+                        .or(delay_events::time.eq(time).and(delay_events::id.gt(id))),
+                );
+            }
+
+            // We fetch one more than requested to know whether there is a next page.
+            let mut rows = query
+                .order((delay_events::time.asc(), delay_events::id.asc()))
+                .limit(limit + 1)
+                .load::<(i64, DelayEvent)>(db)?;
+            let next = if rows.len() as i64 > limit {
+                rows.truncate(limit as usize);
+                rows.last()
+                    .map(|(id, de)| delay_events_cursor(de.time, *id))
+            } else {
+                None
+            };
+
+            Ok(DelayEventsPage {
+                delay_events: rows.into_iter().map(|(_, de)| de).collect(),
+                next,
+            })
+        })
+        .await
+        .map_err(|_: diesel::result::Error| rocket::http::Status::InternalServerError)?;
+    Ok(Cached::Fresh(Json(page), validators))
+}
+
+/// Validators for the delay_events in a time range. The ETag changes whenever an event in the
+/// range is added, or the table got regenerated. Returns None for an empty range.
+fn delay_events_validators(
+    db: &mut diesel::PgConnection,
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    immutable: bool,
+) -> QueryResult<Option<Validators>> {
+    use crate::schema::{delay_events, fetched_json};
+    use diesel::dsl::count_star;
+
+    let (count, max_id, max_to_id) = delay_events::table
+        .filter(delay_events::time.ge(from))
+        .filter(delay_events::time.lt(to))
+        .select((
+            count_star(),
+            diesel::dsl::max(delay_events::id),
+            diesel::dsl::max(delay_events::to_id),
+        ))
+        .first::<(i64, Option<i64>, Option<i64>)>(db)?;
+    let (Some(max_id), Some(max_to_id)) = (max_id, max_to_id) else {
+        return Ok(None);
+    };
+    let last_modified = fetched_json::table
+        .find(max_to_id)
+        .select(fetched_json::fetched_at)
+        .first::<OffsetDateTime>(db)
+        .optional()?
+        .unwrap_or(to);
+    Ok(Some(Validators {
+        etag: format!("\"{}-{}\"", count, max_id),
+        last_modified: Some(last_modified),
+        immutable,
+    }))
+}
+
+/// The id of the latest fetched_json row fetched at or before `at`, so we can
+/// tell what we knew at that moment. Without `at`, everything we know is taken into account.
+fn known_at(db: &mut diesel::PgConnection, at: Option<OffsetDateTime>) -> QueryResult<i64> {
+    use crate::schema::fetched_json;
+
+    match at {
+        Some(at) => Ok(fetched_json::table
+            .select(diesel::dsl::max(fetched_json::id))
+            .filter(fetched_json::fetched_at.le(at))
+            .first::<Option<i64>>(db)?
+            .unwrap_or(0)),
+        None => Ok(i64::MAX),
+    }
+}
+
+/// Parse an optional unix timestamp.
+fn timestamp(t: Option<i64>) -> Result<Option<OffsetDateTime>, Status> {
+    t.map(OffsetDateTime::from_unix_timestamp)
+        .transpose()
+        .map_err(|_| Status::BadRequest)
+}
+
+/// The latest known stopovers of one Trip, identified by its canonical trip_id. HAFAS trip ids
+/// are accepted as well. With `at` (a unix timestamp), the stopovers as known at that moment are
+/// returned instead.
+#[utoipa::path(responses(
+    (status = 200, description = "Stopovers in order", body = Vec<StopoverWithTimes>),
+    (status = 404, description = "Unknown trip"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/trip/<trip_id>?<at>")]
+async fn trip(
+    _ready: CachesReady,
+    conn: DbConn,
+    trip_id: String,
+    at: Option<i64>,
+) -> Result<Json<Vec<StopoverWithTimes>>, Status> {
+    let at = timestamp(at)?;
+    conn.timed_run("trip", move |db| {
         use crate::schema::fetched_json;
 
+        let canonical_trip_id = crate::trip_ids::resolve_trip_id(db, &trip_id)?;
+        let mut urls: Vec<String> = crate::trip_ids::hafas_trip_ids(db, &canonical_trip_id)?
+            .iter()
+            .map(|hafas_trip_id| crate::crawler::trips_url(hafas_trip_id))
+            .collect();
+        urls.push(crate::crawler::trips_url(&trip_id));
+
         fetched_json::dsl::fetched_json
             .select(fetched_json::body)
-            .filter(fetched_json::url.eq(crate::crawler::trips_url(&trip_id)))
-            .then_order_by(fetched_json::fetched_at.asc())
+            .filter(fetched_json::url.eq_any(urls))
+            .filter(fetched_json::id.le(known_at(db, at)?))
+            .then_order_by(fetched_json::fetched_at.desc())
             .limit(1)
             .load::<String>(db)
     })
@@ -79,28 +431,941 @@ async fn trip(conn: DbConn, trip_id: String) -> Result<Json<Vec<Stopover>>, Stat
     .and_then(|json_strs| {
         let json_str = json_strs.first().ok_or(rocket::http::Status::NotFound)?;
         match crate::transport_rest_vbb_v6::deserialize(json_str) {
-            Ok(HafasMsg::TripOverview(TripOverview { trip, .. })) => {
-                let mut stopovers = Vec::new();
-                for so in trip.stopovers {
-                    stopovers.push(Stopover {
-                        stop: so.stop.id,
-                        planned_arrival: so.plannedArrival,
-                        arrival_delay: so.arrivalDelay,
-                        planned_departure: so.plannedDeparture,
-                        departure_delay: so.departureDelay,
-                    })
-                }
-
-                Ok(stopovers)
-            }
+            Ok(HafasMsg::TripOverview(TripOverview { trip, .. })) => Ok(trip
+                .stopovers
+                .iter()
+                .map(|so| StopoverWithTimes::from(Stopover::from(so)))
+                .collect()),
             _ => Err(rocket::http::Status::InternalServerError),
         }
     })
     .map(Json)
 }
 
+/// All DelayRecord's of one Trip, identified by its canonical or a HAFAS trip_id. With `at` (a
+/// unix timestamp), only the ones known at that moment are returned.
+#[utoipa::path(responses(
+    (status = 200, description = "DelayRecord's ordered by time", body = Vec<DelayRecordWithID>),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/trip/<trip_id>/records?<at>")]
+async fn trip_records(
+    _ready: CachesReady,
+    conn: DbConn,
+    trip_id: String,
+    at: Option<i64>,
+) -> Result<Json<Vec<DelayRecordWithID>>, Status> {
+    let at = timestamp(at)?;
+    conn.timed_run("trip_records", move |db| {
+        use crate::schema::delay_records;
+
+        let canonical_trip_id = crate::trip_ids::resolve_trip_id(db, &trip_id)?;
+        delay_records::table
+            .filter(delay_records::trip_id.eq(canonical_trip_id))
+            .filter(delay_records::fetched_json_id.le(known_at(db, at)?))
+            .order((delay_records::time.asc(), delay_records::id.asc()))
+            .load::<DelayRecordWithID>(db)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// All DelayEvent's of one Trip, identified by its canonical or a HAFAS trip_id. With `at` (a unix
+/// timestamp), only the ones known at that moment are returned.
+#[utoipa::path(responses(
+    (status = 200, description = "DelayEvent's ordered by time", body = Vec<DelayEvent>),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/trip/<trip_id>/events?<at>")]
+async fn trip_events(
+    _ready: CachesReady,
+    conn: DbConn,
+    trip_id: String,
+    at: Option<i64>,
+) -> Result<Json<Vec<DelayEvent>>, Status> {
+    let at = timestamp(at)?;
+    conn.timed_run("trip_events", move |db| {
+        use crate::schema::delay_events;
+
+        let canonical_trip_id = crate::trip_ids::resolve_trip_id(db, &trip_id)?;
+        delay_events::table
+            .select(DelayEvent::as_select())
+            .filter(delay_events::trip_id.eq(canonical_trip_id))
+            .filter(delay_events::to_id.le(known_at(db, at)?))
+            .order((delay_events::time.asc(), delay_events::id.asc()))
+            .load::<DelayEvent>(db)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// How the forecast of one Trip evolved: everything that changed from one of its snapshots to the
+/// next, e.g. delays, platforms and cancellations. The trip is identified by its canonical or a
+/// HAFAS trip_id. With `at` (a unix timestamp), only the changes known at that moment are returned.
+#[utoipa::path(responses(
+    (status = 200, description = "Changes ordered by time", body = Vec<TripChange>),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/trip/<trip_id>/changes?<at>")]
+async fn trip_changes(
+    _ready: CachesReady,
+    conn: DbConn,
+    trip_id: String,
+    at: Option<i64>,
+) -> Result<Json<Vec<TripChange>>, Status> {
+    let at = timestamp(at)?;
+    conn.timed_run("trip_changes", move |db| {
+        use crate::schema::trip_changes;
+
+        let canonical_trip_id = crate::trip_ids::resolve_trip_id(db, &trip_id)?;
+        trip_changes::table
+            .select(TripChange::as_select())
+            .filter(trip_changes::trip_id.eq(canonical_trip_id))
+            .filter(trip_changes::fetched_json_id.le(known_at(db, at)?))
+            .order(trip_changes::id.asc())
+            .load::<TripChange>(db)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// All known stations. The ones on the route come first, in the order of the route from Cottbus
+/// to Magdeburg.
+#[utoipa::path(responses(
+    (status = 200, body = Vec<Station>),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/stations")]
+async fn stations(_ready: CachesReady, conn: DbConn) -> Result<Json<Vec<Station>>, Status> {
+    conn.timed_run("stations", |db| {
+        crate::route::load_route(db).map(|route| route.stations().to_vec())
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// All the fetched_json rows that were quarantined, as they couldn't be processed.
+#[utoipa::path(responses(
+    (status = 200, body = Vec<Quarantine>),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/quarantine")]
+async fn quarantine(_ready: CachesReady, conn: DbConn) -> Result<Json<Vec<Quarantine>>, Status> {
+    conn.timed_run("quarantine", crate::quarantine::load_quarantine)
+        .await
+        .map(Json)
+        .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// Bodies larger than this aren't returned by /api/raw/<fetched_json_id>.
+const MAX_RAW_BODY_SIZE: i32 = 1024 * 1024;
+
+/// Maximum time range of /api/raw.
+const MAX_RAW_RANGE: Duration = Duration::DAY;
+
+/// Maximum amount of rows returned by /api/raw.
+const MAX_RAW_ROWS: i64 = 1000;
+
+/// One fetched_json row including its raw body, e.g. the source of a DelayEvent as referenced by
+/// its from_id and to_id. The body is left out if it's larger than 1 MiB.
+#[utoipa::path(responses(
+    (status = 200, body = RawFetchedJson),
+    (status = 404, description = "Unknown fetched_json row"),
+))]
+#[get("/api/raw/<fetched_json_id>")]
+async fn raw(conn: DbConn, fetched_json_id: i64) -> Result<Json<RawFetchedJson>, Status> {
+    conn.timed_run("raw", move |db| {
+        use crate::schema::fetched_json;
+        use diesel::dsl::sql;
+        use diesel::sql_types::{Integer, Nullable, Text};
+
+        fetched_json::table
+            .select((
+                fetched_json::id,
+                fetched_json::fetched_at,
+                fetched_json::url,
+                sql::<Integer>("octet_length(body)"),
+                sql::<Nullable<Text>>(&format!(
+                    "CASE WHEN octet_length(body) <= {MAX_RAW_BODY_SIZE} THEN body END"
+                )),
+            ))
+            .find(fetched_json_id)
+            .first::<RawFetchedJson>(db)
+            .optional()
+    })
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .map(Json)
+    .ok_or(Status::NotFound)
+}
+
+/// The fetched_json rows in a time range of at most one day (by default the last hour), without
+/// their bodies. With `url`, only rows whose url starts with it are returned. At most 1000 rows
+/// are returned, ordered by the time they were fetched.
+#[utoipa::path(responses(
+    (status = 200, body = Vec<RawFetchedJson>),
+    (status = 400, description = "Invalid time range"),
+))]
+#[get("/api/raw?<url>&<from>&<to>&<limit>")]
+async fn raw_index(
+    conn: DbConn,
+    url: Option<String>,
+    from: Option<i64>,
+    to: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Json<Vec<RawFetchedJson>>, Status> {
+    let (from, to) = time_range(from, to, Duration::HOUR)?;
+    if from >= to || to - from > MAX_RAW_RANGE {
+        return Err(Status::BadRequest);
+    }
+    let limit = limit.unwrap_or(MAX_RAW_ROWS).clamp(0, MAX_RAW_ROWS);
+
+    conn.timed_run("raw_index", move |db| {
+        use crate::schema::fetched_json;
+        use diesel::dsl::sql;
+        use diesel::sql_types::{Integer, Nullable, Text};
+        use diesel::TextExpressionMethods;
+
+        let mut query = fetched_json::table
+            .select((
+                fetched_json::id,
+                fetched_json::fetched_at,
+                fetched_json::url,
+                sql::<Integer>("octet_length(body)"),
+                sql::<Nullable<Text>>("NULL"),
+            ))
+            .filter(fetched_json::fetched_at.ge(from))
+            .filter(fetched_json::fetched_at.lt(to))
+            .into_boxed();
+        if let Some(url) = url {
+            let pattern = url
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            query = query.filter(fetched_json::url.like(format!("{pattern}%")));
+        }
+        query
+            .order((fetched_json::fetched_at.asc(), fetched_json::id.asc()))
+            .limit(limit)
+            .load::<RawFetchedJson>(db)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| Status::InternalServerError)
+}
+
+/// Maximum amount of trips returned by /api/trips.
+const MAX_TRIPS: i64 = 1000;
+
+/// Summaries of trips, filtered by their planned departure (as unix timestamps), their train
+/// number and whether they were cancelled. By default, the trips of the last 7 days are returned.
+#[utoipa::path(responses(
+    (status = 200, description = "Trips ordered by planned departure", body = Vec<TripSummary>),
+    (status = 400, description = "Invalid time range or limit"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/trips?<from>&<to>&<train_number>&<cancelled>&<limit>")]
+async fn trips(
+    _ready: CachesReady,
+    conn: DbConn,
+    from: Option<i64>,
+    to: Option<i64>,
+    train_number: Option<String>,
+    cancelled: Option<bool>,
+    limit: Option<i64>,
+) -> Result<Json<Vec<TripSummary>>, Status> {
+    let (from, to) = time_range(from, to, Duration::WEEK)?;
+    let limit = limit.unwrap_or(MAX_TRIPS).clamp(0, MAX_TRIPS);
+
+    conn.timed_run("trips", move |db| {
+        use crate::schema::trip_summaries;
+
+        let mut query = trip_summaries::table
+            .filter(trip_summaries::planned_departure.ge(from))
+            .filter(trip_summaries::planned_departure.lt(to))
+            .into_boxed();
+        if let Some(train_number) = train_number {
+            query = query.filter(trip_summaries::train_number.eq(train_number));
+        }
+        if let Some(cancelled) = cancelled {
+            query = query.filter(trip_summaries::cancelled.eq(cancelled));
+        }
+        query
+            .order(trip_summaries::planned_departure.asc())
+            .limit(limit)
+            .load::<TripSummary>(db)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// Maximum amount of rows returned by /api/stats/stations.
+const MAX_STATION_PUNCTUALITY: i64 = 10000;
+
+/// Punctuality per station, direction and kind (arrival or departure), aggregated per hour, day
+/// (the default) or week. Periods are selected by their start, by default the last 7 days.
+#[utoipa::path(responses(
+    (status = 200, body = Vec<StationPunctuality>),
+    (status = 400, description = "Invalid direction, period or time range"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/stats/stations?<station>&<direction>&<period>&<from>&<to>")]
+async fn station_punctuality(
+    _ready: CachesReady,
+    conn: DbConn,
+    station: Option<i64>,
+    direction: Option<&str>,
+    period: Option<String>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Json<Vec<StationPunctuality>>, Status> {
+    let direction: Option<Direction> = direction
+        .map(str::parse)
+        .transpose()
+        .map_err(|_| Status::BadRequest)?;
+    let period = period.unwrap_or_else(|| "day".to_string());
+    if !["hour", "day", "week"].contains(&period.as_str()) {
+        return Err(Status::BadRequest);
+    }
+    let (from, to) = time_range(from, to, Duration::WEEK)?;
+
+    conn.timed_run("station_punctuality", move |db| {
+        use crate::schema::station_punctuality;
+
+        let mut query = station_punctuality::table
+            .filter(station_punctuality::period.eq(period))
+            .filter(station_punctuality::period_start.ge(from))
+            .filter(station_punctuality::period_start.lt(to))
+            .into_boxed();
+        if let Some(station) = station {
+            query = query.filter(station_punctuality::station.eq(station));
+        }
+        if let Some(direction) = direction {
+            query = query.filter(station_punctuality::direction.eq(direction));
+        }
+        query
+            .order((
+                station_punctuality::period_start.asc(),
+                station_punctuality::station.asc(),
+                station_punctuality::direction.asc(),
+                station_punctuality::kind.asc(),
+            ))
+            .limit(MAX_STATION_PUNCTUALITY)
+            .load::<StationPunctuality>(db)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// Punctuality per station, direction and kind over the whole time range (by default the last 7
+/// days). Can be narrowed down to ISO weekdays (1 is monday) and hours of the day in local time,
+/// e.g. `?weekdays=1&weekdays=2&hours=7&hours=8` for monday and tuesday mornings.
+#[utoipa::path(
+    responses(
+        (status = 200, body = Vec<PunctualitySummary>),
+        (status = 400, description = "Invalid direction, time range, weekday or hour"),
+        (status = 503, description = "The cache tables aren't up to date yet"),
+    ),
+    params(
+        ("weekdays" = Option<Vec<i32>>, Query),
+        ("hours" = Option<Vec<i32>>, Query),
+    )
+)]
+#[get("/api/stats/stations/summary?<station>&<direction>&<from>&<to>&<weekdays>&<hours>")]
+#[allow(clippy::too_many_arguments)]
+async fn station_punctuality_summary(
+    _ready: CachesReady,
+    conn: DbConn,
+    station: Option<i64>,
+    direction: Option<&str>,
+    from: Option<i64>,
+    to: Option<i64>,
+    weekdays: Vec<i32>,
+    hours: Vec<i32>,
+) -> Result<Json<Vec<PunctualitySummary>>, Status> {
+    let direction: Option<Direction> = direction
+        .map(str::parse)
+        .transpose()
+        .map_err(|_| Status::BadRequest)?;
+    if weekdays.iter().any(|d| !(1..=7).contains(d)) || hours.iter().any(|h| !(0..24).contains(h)) {
+        return Err(Status::BadRequest);
+    }
+    let (from, to) = time_range(from, to, Duration::WEEK)?;
+    let weekdays = Some(weekdays).filter(|w| !w.is_empty());
+    let hours = Some(hours).filter(|h| !h.is_empty());
+
+    conn.timed_run("station_punctuality_summary", move |db| {
+        crate::statistics::punctuality_summary(db, from, to, station, direction, weekdays, hours)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// How much delay trains gain (or lose, if negative) per segment between two consecutive
+/// stations, split into running and dwell time. Covers trips departing in the time range, by
+/// default the last 7 days. With a period of "hour", "day" or "week", there is one entry per
+/// segment and period. Sorted by mean running gain, so the worst segments come first.
+#[utoipa::path(responses(
+    (status = 200, body = Vec<SegmentDelaySummary>),
+    (status = 400, description = "Invalid direction, period or time range"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/stats/segments?<from_station>&<to_station>&<direction>&<period>&<from>&<to>")]
+#[allow(clippy::too_many_arguments)]
+async fn segment_delays(
+    _ready: CachesReady,
+    conn: DbConn,
+    from_station: Option<i64>,
+    to_station: Option<i64>,
+    direction: Option<&str>,
+    period: Option<String>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Json<Vec<SegmentDelaySummary>>, Status> {
+    let direction: Option<Direction> = direction
+        .map(str::parse)
+        .transpose()
+        .map_err(|_| Status::BadRequest)?;
+    if period
+        .as_deref()
+        .is_some_and(|p| !["hour", "day", "week"].contains(&p))
+    {
+        return Err(Status::BadRequest);
+    }
+    let (from, to) = time_range(from, to, Duration::WEEK)?;
+
+    conn.timed_run("segment_delays", move |db| {
+        crate::segments::segment_delay_summary(
+            db,
+            from,
+            to,
+            from_station,
+            to_station,
+            direction,
+            period,
+        )
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// Default size of the time buckets of /api/heatmap in seconds.
+const DEFAULT_HEATMAP_TIME_BUCKET: i64 = 15 * 60;
+
+/// Default size of the route position buckets of /api/heatmap in km.
+const DEFAULT_HEATMAP_SEGMENT_BUCKET: f64 = 5.0;
+
+/// /api/heatmap refuses to split its time range into more time buckets than this.
+const MAX_HEATMAP_TIME_BUCKETS: i64 = 10_000;
+
+/// Check the bucket sizes of /api/heatmap, falling back to the defaults.
+fn heatmap_buckets(
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+    time_bucket: Option<i64>,
+    segment_bucket: Option<f64>,
+) -> Result<(i64, f64), Status> {
+    let time_bucket = time_bucket.unwrap_or(DEFAULT_HEATMAP_TIME_BUCKET);
+    let segment_bucket = segment_bucket.unwrap_or(DEFAULT_HEATMAP_SEGMENT_BUCKET);
+    if time_bucket < 60
+        || (to - from).whole_seconds() / time_bucket > MAX_HEATMAP_TIME_BUCKETS
+        || !segment_bucket.is_finite()
+        || segment_bucket < 0.1
+    {
+        return Err(Status::BadRequest);
+    }
+    Ok((time_bucket, segment_bucket))
+}
+
+/// Mean and maximum delay per direction, time bucket and route position bucket, for drawing
+/// zoomed out Marey diagrams without downloading every DelayEvent. The time range is given as
+/// unix timestamps, by default it's the last 7 days. Buckets are 15 minutes (time_bucket, in
+/// seconds, at least 60) by 5 km (segment_bucket, at least 0.1) by default.
+#[utoipa::path(responses(
+    (status = 200, body = Vec<HeatmapBin>),
+    (status = 400, description = "Invalid time range or bucket sizes"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/heatmap?<from>&<to>&<time_bucket>&<segment_bucket>")]
+async fn heatmap(
+    _ready: CachesReady,
+    conn: DbConn,
+    from: Option<i64>,
+    to: Option<i64>,
+    time_bucket: Option<i64>,
+    segment_bucket: Option<f64>,
+) -> Result<Json<Vec<HeatmapBin>>, Status> {
+    let (from, to) = time_range(from, to, Duration::WEEK)?;
+    let (time_bucket, segment_bucket) = heatmap_buckets(from, to, time_bucket, segment_bucket)?;
+
+    conn.timed_run("heatmap", move |db| {
+        crate::heatmap::heatmap(db, from, to, time_bucket, segment_bucket)
+    })
+    .await
+    .map(Json)
+    .map_err(|_| rocket::http::Status::InternalServerError)
+}
+
+/// Maximum time range of /api/export.
+const MAX_EXPORT_RANGE: Duration = Duration::days(31);
+
+/// Render the Marey diagram or the heatmap of a time range of at most 7 days as SVG or PNG, e.g.
+/// /api/render/marey.svg or /api/render/heatmap.png. The time range is given as unix timestamps,
+/// by default it's the last 24 hours. With a direction ("westwards" or "eastwards"), only trips
+/// going that way are shown.
+#[utoipa::path(responses(
+    (status = 200, content((String = "image/svg+xml"), (Vec<u8> = "image/png"))),
+    (status = 400, description = "Invalid time range or direction"),
+    (status = 404, description = "Unknown diagram or format"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/render/<file>?<from>&<to>&<direction>")]
+async fn render(
+    _ready: CachesReady,
+    conn: DbConn,
+    file: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+    direction: Option<&str>,
+) -> Result<(ContentType, Vec<u8>), Status> {
+    let (diagram, format) = file.split_once('.').ok_or(Status::NotFound)?;
+    let diagram: Diagram = diagram.parse().map_err(|_| Status::NotFound)?;
+    let format: RenderFormat = format.parse().map_err(|_| Status::NotFound)?;
+    let direction: Option<Direction> = direction
+        .map(str::parse)
+        .transpose()
+        .map_err(|_| Status::BadRequest)?;
+    let (from, to) = time_range(from, to, Duration::DAY)?;
+    if from >= to || to - from > MAX_RENDER_RANGE {
+        return Err(Status::BadRequest);
+    }
+
+    let data = conn
+        .timed_run("render", move |db| {
+            crate::render::load(db, diagram, from, to, direction)
+        })
+        .await
+        .map_err(|e| {
+            error!("Unable to load the data for {}: {}", diagram.as_str(), e);
+            Status::InternalServerError
+        })?;
+    // Rendering, especially to PNG, takes a while and mustn't block the async runtime.
+    let image = tokio::task::spawn_blocking(move || data.render(format).map_err(|e| e.to_string()))
+        .await
+        .unwrap_or_else(|e| Err(e.to_string()))
+        .map_err(|e| {
+            error!("Unable to render {}: {}", diagram.as_str(), e);
+            Status::InternalServerError
+        })?;
+    let content_type = match format {
+        RenderFormat::Svg => ContentType::SVG,
+        RenderFormat::Png => ContentType::PNG,
+    };
+    Ok((content_type, image))
+}
+
+/// A file download.
+#[derive(Responder)]
+struct Download<R> {
+    body: R,
+    content_type: ContentType,
+    content_disposition: Header<'static>,
+}
+
+/// Export delay_records, delay_events or trip_summaries in a time range of at most 31 days as CSV
+/// (the default) or Parquet. The time range is given as unix timestamps, by default it's the last
+/// 7 days. The file is streamed while it is written.
+///
+/// The column schemas are stable: Columns may be added at the end, but existing ones won't be
+/// renamed, reordered or change their type. Columns are either integers (int64), floating point
+/// numbers (double), booleans, text or timestamps. Timestamps are UTC with microsecond precision,
+/// written as RFC 3339 in CSV files and as `TIMESTAMP(MICROS, UTC)` in Parquet files. Empty CSV
+/// fields and Parquet nulls mean the value is unknown. Delays and durations are in seconds,
+/// stations are identified by their HAFAS id. In all tables, `direction` is the direction along
+/// the route, either `westwards` or `eastwards`.
+///
+/// - `delay_records` (filtered by `time`): id, fetched_json_id, trip_id, time, previous_station,
+///   next_station, percentage_segment, delay, arrival_delay, departure_delay, has_realtime,
+///   trip_state, direction
+/// - `delay_events` (filtered by `time`): id, from_id, to_id, trip_id, time, duration,
+///   previous_station, next_station, percentage_segment, delay, interpolated, has_realtime,
+///   direction
+/// - `trip_summaries` (filtered by `planned_departure`): trip_id, line, train_number, direction,
+///   headsign, origin, destination, planned_departure, first_observation, last_observation,
+///   start_delay, final_delay, max_delay, stations_covered, cancelled, cancelled_stops
+#[utoipa::path(responses(
+    (status = 200, content((String = "text/csv"), (Vec<u8> = "application/vnd.apache.parquet"))),
+    (status = 400, description = "Invalid format or time range"),
+    (status = 404, description = "Unknown table"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/export/<table>?<format>&<from>&<to>")]
+async fn export(
+    _ready: CachesReady,
+    conn: DbConn,
+    table: &str,
+    format: Option<&str>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Download<ByteStream![Vec<u8>]>, Status> {
+    let table: ExportTable = table.parse().map_err(|_| Status::NotFound)?;
+    let format: ExportFormat = format
+        .unwrap_or("csv")
+        .parse()
+        .map_err(|_| Status::BadRequest)?;
+    let (from, to) = time_range(from, to, Duration::WEEK)?;
+    if from >= to || to - from > MAX_EXPORT_RANGE {
+        return Err(Status::BadRequest);
+    }
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<u8>>(STREAM_BUFFER);
+    tokio::spawn(async move {
+        let result = conn
+            .run(move |db| {
+                let timer = query_timer("export");
+                let mut rows =
+                    crate::export::rows(db, table, from, to).map_err(|e| e.to_string())?;
+                let first = rows.next().transpose().map_err(|e| e.to_string())?;
+                timer.observe_duration();
+
+                let mut out = ChunkWriter::new(tx);
+                let rows = first.into_iter().map(Ok).chain(rows);
+                crate::export::write_rows(table, format, rows, &mut out)
+                    .and_then(|_| Ok(out.flush()?))
+                    .map_err(|e| e.to_string())
+            })
+            .await;
+        if let Err(e) = result {
+            error!("Unable to export {}: {}", table.as_str(), e);
+        }
+    });
+    // Nothing is sent before the first chunk is full or the export is done, so if there is no
+    // chunk at all, the export failed.
+    let first = rx.recv().await.ok_or(Status::InternalServerError)?;
+
+    let content_type = match format {
+        ExportFormat::Csv => ContentType::CSV,
+        ExportFormat::Parquet => ContentType::new("application", "vnd.apache.parquet"),
+    };
+    let filename = format!(
+        "{}_{}_{}.{}",
+        table.as_str(),
+        from.unix_timestamp(),
+        to.unix_timestamp(),
+        format.extension()
+    );
+    Ok(Download {
+        body: ByteStream! {
+            yield first;
+            while let Some(chunk) = rx.recv().await {
+                yield chunk;
+            }
+        },
+        content_type,
+        content_disposition: Header::new(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        ),
+    })
+}
+
+/// A GTFS-Realtime feed with a TripUpdate and a VehiclePosition for every active trip, see
+/// [crate::gtfs_rt]. It's encoded as protobuf by default, or as JSON for debugging with
+/// `format=json`. With `at` (a unix timestamp), the feed as it would have been at that moment is
+/// returned instead.
+#[utoipa::path(responses(
+    (status = 200, content((Vec<u8> = "application/x-protobuf"), (Object = "application/json"))),
+    (status = 400, description = "Invalid format"),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/gtfs-rt?<format>&<at>")]
+async fn gtfs_rt(
+    _ready: CachesReady,
+    conn: DbConn,
+    format: Option<&str>,
+    at: Option<i64>,
+) -> Result<(ContentType, Vec<u8>), Status> {
+    let json = match format {
+        None | Some("protobuf") => false,
+        Some("json") => true,
+        Some(_) => return Err(Status::BadRequest),
+    };
+    let at = timestamp(at)?;
+    let feed = conn
+        .timed_run("gtfs_rt", move |db| {
+            let known_at = known_at(db, at)?;
+            crate::gtfs_rt::feed_message(db, at.unwrap_or_else(OffsetDateTime::now_utc), known_at)
+        })
+        .await
+        .map_err(|e| {
+            error!("Unable to build GTFS-Realtime feed: {}", e);
+            Status::InternalServerError
+        })?;
+    if json {
+        serde_json::to_vec_pretty(&feed)
+            .map(|body| (ContentType::JSON, body))
+            .map_err(|_| Status::InternalServerError)
+    } else {
+        Ok((
+            ContentType::new("application", "x-protobuf"),
+            prost::Message::encode_to_vec(&feed),
+        ))
+    }
+}
+
+/// How many live DelayRecord's an SSE subscriber may fall behind, before we drop it.
+const SSE_LIVE_BUFFER: usize = 1024;
+
+/// How often the thread reading the bus for an SSE subscriber checks whether the stream is gone.
+const SSE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Live DelayRecord's as Server-Sent Events, one JSON encoded DelayRecord per event. Like the
+/// websocket, the stream starts with the DelayRecord's of the last `historic` seconds (one hour
+/// by default, at most 31 days).
+#[utoipa::path(responses(
+    (
+        status = 200,
+        description = "A DelayRecord per event",
+        content_type = "text/event-stream",
+        body = DelayRecord
+    ),
+    (status = 503, description = "The cache tables aren't up to date yet"),
+))]
+#[get("/api/sse/delays?<historic>")]
+fn sse_delays(
+    _ready: CachesReady,
+    pool: DbPool,
+    bus: &State<DelayRecordBus>,
+    health: &State<Arc<Health>>,
+    historic: Option<u64>,
+) -> EventStream![] {
+    let historic = historic
+        .unwrap_or(3600)
+        .min(crate::ws_api::MAX_HISTORIC_SECONDS);
+    let since = OffsetDateTime::now_utc() - Duration::seconds(historic as i64);
+
+    // Subscribe before loading the historic DelayRecord's, so we don't miss any inbetween. The
+    // live ones are buffered until the historic ones are sent.
+    let mut rx = bus.0.lock().expect("Poisoned DelayRecordBus").add_rx();
+    let mut subscription = health.subscribe(SubscriberKind::Sse);
+    let (live_tx, mut live) = tokio::sync::mpsc::channel::<DelayRecord>(SSE_LIVE_BUFFER);
+    std::thread::spawn(move || {
+        use std::sync::mpsc::RecvTimeoutError;
+        use tokio::sync::mpsc::error::TrySendError;
+
+        loop {
+            match rx.recv_timeout(SSE_POLL_INTERVAL) {
+                Ok(delay_record) => match live_tx.try_send(delay_record) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        info!("Dropping SSE subscriber, as it can't keep up.");
+                        break;
+                    }
+                    Err(TrySendError::Closed(_)) => break,
+                },
+                Err(RecvTimeoutError::Timeout) if !live_tx.is_closed() => {}
+                Err(_) => break,
+            }
+        }
+        debug!("SSE subscriber is gone.");
+    });
+
+    EventStream! {
+        let mut historic = HistoricDelayRecords::since(since);
+        loop {
+            // Only take a connection from the pool for loading a page, as the client decides how
+            // fast the pages get sent.
+            let Some(conn) = pool.0.get().await else {
+                error!("Unable to get a db connection for historic DelayRecords.");
+                break;
+            };
+            let (loaded, page) = conn
+                .run(move |db| {
+                    let _timer = query_timer("sse_delays");
+                    let page = historic.next_page(db);
+                    (historic, page)
+                })
+                .await;
+            drop(conn);
+            historic = loaded;
+            match page {
+                Ok(Some(page)) => {
+                    for delay_record in page {
+                        subscription.sent();
+                        yield Event::json(&delay_record);
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Unable to send historic DelayRecords: {}", e);
+                    break;
+                }
+            }
+        }
+        // DelayRecord's waiting in the live channel still count as queued on the bus. Those that
+        // were stored before the last page got loaded were already sent with it.
+        while let Some(delay_record) = live.recv().await {
+            subscription.received();
+            if delay_record.fetched_json_id <= historic.last_fetched_json_id {
+                continue;
+            }
+            subscription.sent();
+            yield Event::json(&delay_record);
+        }
+    }
+}
+
+/// Whether the database can be queried.
+async fn database_reachable(conn: Option<DbConn>) -> bool {
+    match conn {
+        Some(conn) => conn
+            .timed_run("database_reachable", |db| {
+                diesel::sql_query("SELECT 1").execute(db)
+            })
+            .await
+            .is_ok(),
+        None => false,
+    }
+}
+
+/// Liveness probe, succeeds as long as the webserver is running.
+#[utoipa::path(responses((status = 200, body = String)))]
+#[get("/healthz")]
+fn healthz() -> &'static str {
+    "ok"
+}
+
+/// Readiness probe. Fails if the database can't be reached, the cache tables aren't up to date
+/// yet or the crawler didn't fetch the trips overview for too long.
+#[utoipa::path(responses(
+    (status = 200, body = String),
+    (status = 503, description = "The reasons for not being ready", body = String),
+))]
+#[get("/readyz")]
+async fn readyz(conn: Option<DbConn>, health: &State<Arc<Health>>) -> (Status, String) {
+    let status = health.status(database_reachable(conn).await, OffsetDateTime::now_utc());
+    let reasons = status.not_ready_reasons();
+    if reasons.is_empty() {
+        (Status::Ok, "ok".to_string())
+    } else {
+        (Status::ServiceUnavailable, reasons.join(", "))
+    }
+}
+
+/// The state of the database, the crawler and the live subscribers.
+#[utoipa::path(responses((status = 200, body = ServerStatus)))]
+#[get("/api/status")]
+async fn status(conn: Option<DbConn>, health: &State<Arc<Health>>) -> Json<ServerStatus> {
+    Json(health.status(database_reachable(conn).await, OffsetDateTime::now_utc()))
+}
+
+/// Metrics of the crawler, the live subscribers and the database queries, to be scraped by
+/// Prometheus.
+#[utoipa::path(responses(
+    (status = 200, content_type = "text/plain; version=0.0.4", body = String),
+))]
+#[get("/metrics")]
+fn metrics() -> (ContentType, String) {
+    (
+        ContentType::new("text", "plain").with_params(("version", "0.0.4")),
+        METRICS.encode(),
+    )
+}
+
+/// The OpenAPI document of the HTTP endpoints, generated from the handlers and the types they
+/// serialize.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "isre1late",
+        license(name = "GPL-3.0-or-later"),
+        description = "Delays of the RE1 between Magdeburg and Cottbus. Timestamps are unix \
+                       timestamps and delays are in seconds. The websocket at /api/ws/delays \
+                       (on its own port) sends one DelayRecord per message, just like \
+                       /api/sse/delays."
+    ),
+    paths(
+        delay_events,
+        delay_events_day,
+        delay_events_week,
+        trip,
+        trip_records,
+        trip_events,
+        trip_changes,
+        trips,
+        stations,
+        quarantine,
+        raw,
+        raw_index,
+        station_punctuality,
+        station_punctuality_summary,
+        segment_delays,
+        heatmap,
+        export,
+        render,
+        gtfs_rt,
+        sse_delays,
+        healthz,
+        readyz,
+        status,
+        metrics,
+        openapi
+    ),
+    components(schemas(DelayRecord))
+)]
+struct ApiDoc;
+
+/// This document.
+#[utoipa::path(responses((status = 200, body = Object)))]
+#[get("/api/openapi.json")]
+async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// All routes of the HTTP API.
+fn api_routes() -> Vec<rocket::Route> {
+    routes![
+        delay_events,
+        delay_events_day,
+        delay_events_week,
+        trip,
+        trip_records,
+        trip_events,
+        trip_changes,
+        trips,
+        stations,
+        quarantine,
+        raw,
+        raw_index,
+        station_punctuality,
+        station_punctuality_summary,
+        segment_delays,
+        heatmap,
+        export,
+        render,
+        gtfs_rt,
+        sse_delays,
+        healthz,
+        readyz,
+        status,
+        metrics,
+        openapi,
+    ]
+}
+
 pub fn webserver(
     db_url: &str,
+    bus_read_handle: BusReadHandle<DelayRecord>,
+    health: Arc<Health>,
+    client_dir: Option<std::path::PathBuf>,
     listen: std::net::IpAddr,
     port: u16,
 ) -> Result<i32, Box<dyn std::error::Error>> {
@@ -121,11 +1386,442 @@ pub fn webserver(
         "url" => db_url.into(),
     };
     let figment = Figment::from(config).merge(("databases", map!["isre1late" => db_map]));
-    let builder = rocket::custom(&figment)
-        .mount("/", routes![delay_events_day, delay_events_week, trip])
-        .attach(DbConn::fairing());
+    let mut builder = rocket::custom(&figment)
+        .mount("/", api_routes())
+        .manage(DelayRecordBus(Mutex::new(bus_read_handle)))
+        .manage(health)
+        .attach(DbConn::fairing())
+        .attach(Compression);
+    if let Some(client_dir) = client_dir {
+        info!("Serving the client from {}", client_dir.display());
+        let (routes, client_dir) = crate::client::client_routes(client_dir);
+        builder = builder.mount("/", routes).manage(client_dir);
+    }
     rt.block_on(async move {
         let _ = builder.launch().await;
     });
     Ok(0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_events_cursor_roundtrip() {
+        let time = OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_123_456_000).unwrap();
+        let cursor = delay_events_cursor(time, 42);
+
+        assert_eq!(cursor, "1700000000123456_42");
+        assert_eq!(parse_delay_events_cursor(&cursor), Some((time, 42)));
+        assert_eq!(parse_delay_events_cursor("1700000000123456"), None);
+        assert_eq!(parse_delay_events_cursor("x_42"), None);
+        assert_eq!(
+            parse_delay_events_cursor(&format!("{}_42", i128::MAX)),
+            None
+        );
+    }
+
+    #[get("/")]
+    fn guarded(_ready: CachesReady) {}
+
+    #[test]
+    fn caches_ready_guard() {
+        use rocket::local::blocking::Client;
+
+        let health = Arc::new(Health::new(Duration::minutes(5)));
+        let rocket = rocket::build()
+            .manage(health.clone())
+            .mount("/", routes![guarded]);
+        let client = Client::untracked(rocket).unwrap();
+
+        assert_eq!(
+            client.get("/").dispatch().status(),
+            Status::ServiceUnavailable
+        );
+        health.caches_ready();
+        assert_eq!(client.get("/").dispatch().status(), Status::Ok);
+    }
+
+    #[test]
+    fn heatmap_bucket_limits() {
+        let to = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let from = to - Duration::WEEK;
+
+        assert_eq!(heatmap_buckets(from, to, None, None), Ok((900, 5.0)));
+        assert_eq!(
+            heatmap_buckets(from, to, Some(3600), Some(0.5)),
+            Ok((3600, 0.5))
+        );
+        assert!(heatmap_buckets(from, to, Some(59), None).is_err());
+        assert!(heatmap_buckets(from, to, Some(60), None).is_err());
+        assert!(heatmap_buckets(from, to, None, Some(0.0)).is_err());
+        assert!(heatmap_buckets(from, to, None, Some(f64::NAN)).is_err());
+    }
+
+    /// Follow a `$ref` into the components of the OpenAPI document.
+    fn resolve<'a>(
+        spec: &'a serde_json::Value,
+        schema: &'a serde_json::Value,
+    ) -> &'a serde_json::Value {
+        match schema["$ref"].as_str() {
+            Some(reference) => resolve(
+                spec,
+                &spec["components"]["schemas"]
+                    [reference.trim_start_matches("#/components/schemas/")],
+            ),
+            None => schema,
+        }
+    }
+
+    /// The properties of an object schema, including the ones of flattened structs.
+    fn properties<'a>(
+        spec: &'a serde_json::Value,
+        schema: &'a serde_json::Value,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let schema = resolve(spec, schema);
+        match schema["allOf"].as_array() {
+            Some(all) => all.iter().flat_map(|s| properties(spec, s)).collect(),
+            None => schema["properties"]
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Check that `value` matches `schema`. Objects have to have exactly the properties the schema
+    /// describes, so neither side can have fields the other one doesn't know about.
+    fn check(
+        spec: &serde_json::Value,
+        schema: &serde_json::Value,
+        value: &serde_json::Value,
+        path: &str,
+    ) -> Result<(), String> {
+        use serde_json::Value;
+
+        let schema = resolve(spec, schema);
+        if let Some(any) = schema["oneOf"].as_array() {
+            return any
+                .iter()
+                .find_map(|s| check(spec, s, value, path).ok())
+                .ok_or(format!("{path}: {value} matches none of {any:?}"));
+        }
+        if let Some(variants) = schema["enum"].as_array() {
+            if !variants.contains(value) {
+                return Err(format!("{path}: {value} is not one of {variants:?}"));
+            }
+        }
+        let types: Vec<&str> = match &schema["type"] {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        let actual = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(n) if n.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        let matches = types.contains(&actual) || actual == "integer" && types.contains(&"number");
+        if !types.is_empty() && !matches {
+            return Err(format!("{path}: expected {types:?}, got {value}"));
+        }
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .try_for_each(|item| check(spec, &schema["items"], item, &format!("{path}[]"))),
+            Value::Object(fields) => {
+                let properties = properties(spec, schema);
+                let mut names: Vec<&String> = properties.keys().collect();
+                let mut fields_names: Vec<&String> = fields.keys().collect();
+                names.sort();
+                fields_names.sort();
+                if names != fields_names {
+                    return Err(format!(
+                        "{path}: serialized as {fields_names:?}, documented as {names:?}"
+                    ));
+                }
+                fields.iter().try_for_each(|(name, field)| {
+                    check(spec, &properties[name], field, &format!("{path}.{name}"))
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// One serialized value of every type in the OpenAPI document.
+    fn samples() -> Vec<(&'static str, serde_json::Value)> {
+        use crate::models::{ChangeKind, Direction, TripState, ValidationError};
+        use serde_json::to_value;
+
+        let time = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let delay_event = DelayEvent {
+            from_id: 1,
+            to_id: 2,
+            trip_id: "RE1_3709_20231114T2300".to_string(),
+            time,
+            duration: 60,
+            previous_station: 900000003201,
+            next_station: 900000100001,
+            percentage_segment: 0.5,
+            delay: 120,
+            interpolated: false,
+            has_realtime: true,
+            direction: Some(Direction::Westwards),
+        };
+        let delay_record = DelayRecord {
+            fetched_json_id: 1,
+            trip_id: "RE1_3709_20231114T2300".to_string(),
+            time,
+            previous_station: 900000003201,
+            next_station: 900000100001,
+            percentage_segment: 0.5,
+            delay: 120,
+            arrival_delay: Some(120),
+            departure_delay: None,
+            has_realtime: true,
+            trip_state: TripState::EnRoute,
+            direction: None,
+        };
+        let stopover = Stopover {
+            stop: 900000003201,
+            planned_arrival: Some(time),
+            arrival_delay: Some(60),
+            planned_departure: None,
+            departure_delay: None,
+        };
+        let health = Health::new(Duration::minutes(5));
+        health.caches_ready();
+        health.overview_fetched(time, 3);
+        health.trip_fetched(time);
+        vec![
+            ("ChangeKind", to_value(ChangeKind::StopCancelled).unwrap()),
+            ("DelayEvent", to_value(&delay_event).unwrap()),
+            (
+                "DelayEventsPage",
+                to_value(DelayEventsPage {
+                    delay_events: vec![delay_event],
+                    next: Some("1700000000000000_42".to_string()),
+                })
+                .unwrap(),
+            ),
+            ("DelayRecord", to_value(&delay_record).unwrap()),
+            (
+                "DelayRecordWithID",
+                to_value(DelayRecordWithID {
+                    id: 1,
+                    fetched_json_id: delay_record.fetched_json_id,
+                    trip_id: delay_record.trip_id,
+                    time,
+                    previous_station: delay_record.previous_station,
+                    next_station: delay_record.next_station,
+                    percentage_segment: delay_record.percentage_segment,
+                    delay: delay_record.delay,
+                    arrival_delay: delay_record.arrival_delay,
+                    departure_delay: delay_record.departure_delay,
+                    has_realtime: delay_record.has_realtime,
+                    trip_state: TripState::AtTerminus,
+                    direction: Some(Direction::Eastwards),
+                })
+                .unwrap(),
+            ),
+            ("Direction", to_value(Direction::Eastwards).unwrap()),
+            (
+                "HeatmapBin",
+                to_value(HeatmapBin {
+                    direction: Some(Direction::Westwards),
+                    time_start: time,
+                    km_start: 35.0,
+                    count: 12,
+                    delay_mean: 150.5,
+                    delay_max: 420,
+                })
+                .unwrap(),
+            ),
+            (
+                "PunctualitySummary",
+                to_value(PunctualitySummary {
+                    station: 900000003201,
+                    direction: Direction::Westwards,
+                    kind: "arrival".to_string(),
+                    count: 10,
+                    within_0: 0.1,
+                    within_3: 0.5,
+                    within_6: 0.8,
+                    within_15: 1.0,
+                    median_delay: 180.0,
+                    p90_delay: 420.0,
+                })
+                .unwrap(),
+            ),
+            (
+                "Quarantine",
+                to_value(Quarantine {
+                    fetched_json_id: 1,
+                    quarantined_at: time,
+                    reason: ValidationError::RatioOutOfRange(1.5).to_string(),
+                })
+                .unwrap(),
+            ),
+            (
+                "RawFetchedJson",
+                to_value(RawFetchedJson {
+                    id: 1,
+                    fetched_at: time,
+                    url: "https://v6.vbb.transport.rest/trips".to_string(),
+                    size: 2,
+                    body: Some("{}".to_string()),
+                })
+                .unwrap(),
+            ),
+            (
+                "SegmentDelaySummary",
+                to_value(SegmentDelaySummary {
+                    from_station: 900000003201,
+                    to_station: 900000100001,
+                    direction: Direction::Westwards,
+                    period_start: Some(time),
+                    count: 10,
+                    running_gain_mean: Some(12.5),
+                    running_gain_median: Some(10.0),
+                    running_gain_p90: None,
+                    dwell_gain_mean: Some(-3.0),
+                    dwell_gain_median: Some(0.0),
+                    dwell_gain_p90: Some(30.0),
+                })
+                .unwrap(),
+            ),
+            (
+                "Station",
+                to_value(Station {
+                    id: 900000003201,
+                    name: "S+U Berlin Hauptbahnhof".to_string(),
+                    short_name: Some("Berlin Hbf".to_string()),
+                    important: true,
+                    position: Some(7),
+                    km: Some(120.3),
+                    latitude: Some(52.525),
+                    longitude: None,
+                })
+                .unwrap(),
+            ),
+            (
+                "StationPunctuality",
+                to_value(StationPunctuality {
+                    station: 900000003201,
+                    direction: Direction::Westwards,
+                    period: "day".to_string(),
+                    period_start: time,
+                    kind: "departure".to_string(),
+                    count: 10,
+                    within_0: 0.1,
+                    within_3: 0.5,
+                    within_6: 0.8,
+                    within_15: 1.0,
+                    median_delay: 180.0,
+                    p90_delay: 420.0,
+                    fetched_json_id: 1,
+                })
+                .unwrap(),
+            ),
+            ("Status", to_value(health.status(true, time)).unwrap()),
+            ("Stopover", to_value(&stopover).unwrap()),
+            (
+                "StopoverWithTimes",
+                to_value(StopoverWithTimes::from(stopover)).unwrap(),
+            ),
+            (
+                "TripChange",
+                to_value(TripChange {
+                    trip_id: "RE1_3709_20231114T2300".to_string(),
+                    fetched_json_id: 2,
+                    previous_fetched_json_id: 1,
+                    time,
+                    stop: Some(900000003201),
+                    kind: ChangeKind::ArrivalDelay,
+                    old_value: None,
+                    new_value: Some("120".to_string()),
+                })
+                .unwrap(),
+            ),
+            ("TripState", to_value(TripState::AtOrigin).unwrap()),
+            (
+                "TripSummary",
+                to_value(TripSummary {
+                    trip_id: "RE1_3709_20231114T2300".to_string(),
+                    line: Some("RE1".to_string()),
+                    train_number: Some("3709".to_string()),
+                    direction: Some(Direction::Eastwards),
+                    headsign: Some("Frankfurt (Oder)".to_string()),
+                    origin: "Magdeburg, Hauptbahnhof".to_string(),
+                    destination: "Frankfurt (Oder), Bahnhof".to_string(),
+                    planned_departure: time,
+                    first_observation: time,
+                    last_observation: time,
+                    start_delay: Some(0),
+                    final_delay: Some(240),
+                    max_delay: None,
+                    stations_covered: 20,
+                    cancelled: false,
+                    cancelled_stops: 0,
+                    last_fetched_json_id: 2,
+                })
+                .unwrap(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn openapi_matches_serializers() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        let samples = samples();
+
+        assert_eq!(
+            schemas.keys().collect::<Vec<_>>(),
+            samples.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            "Every documented type needs a sample"
+        );
+        for (name, sample) in samples {
+            if let Err(e) = check(&spec, &schemas[name], &sample, name) {
+                panic!("{}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn openapi_covers_all_routes() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        for route in api_routes() {
+            let path = route.uri.path().replace('<', "{").replace('>', "}");
+            assert!(
+                spec["paths"][&path][route.method.as_str().to_lowercase()].is_object(),
+                "{} {} is not documented",
+                route.method,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn export_columns_documented() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let description = spec["paths"]["/api/export/{table}"]["get"]["description"]
+            .as_str()
+            .unwrap()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        for table in ExportTable::ALL {
+            let columns = format!("`{}` (filtered by", table.as_str());
+            assert!(description.contains(&columns), "{}", columns);
+            let columns = table.column_names().join(", ");
+            assert!(description.contains(&columns), "{}", columns);
+        }
+    }
+}
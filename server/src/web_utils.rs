@@ -0,0 +1,474 @@
+// SPDX-FileCopyrightText: 2026 Kerstin Humm <mail@erictapen.name>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Building blocks for the HTTP API that aren't specific to any endpoint: response compression,
+//! caching headers and streaming of large JSON arrays and other files.
+
+use async_compression::tokio::bufread::{BrotliEncoder, GzipEncoder, ZstdEncoder};
+use async_compression::Level;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Status};
+use rocket::request::{FromRequest, Outcome};
+use rocket::response::{self, Responder};
+use rocket::tokio::io::{AsyncBufRead, AsyncRead, BufReader};
+use rocket::tokio::sync::mpsc;
+use rocket::{Request, Response};
+use serde::Serialize;
+use std::convert::Infallible;
+use std::error::Error;
+use std::io::Write;
+use std::pin::Pin;
+use time::format_description::FormatItem;
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+/// Responses with a known size below this many bytes aren't worth compressing.
+const MIN_COMPRESS_SIZE: usize = 1024;
+
+/// Brotli's default quality is too slow to compress large responses on the fly.
+const BROTLI_QUALITY: i32 = 4;
+
+/// How long caches may keep responses that don't change anymore, in seconds.
+const IMMUTABLE_MAX_AGE: u64 = 24 * 60 * 60;
+
+/// Size of the chunks a streamed JSON array is sent in.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The content encodings we can compress responses with, in order of preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Zstd,
+    Brotli,
+    Gzip,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 3] = [Encoding::Zstd, Encoding::Brotli, Encoding::Gzip];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Zstd => "zstd",
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+
+    /// Pick the encoding the client prefers from an Accept-Encoding header, taking quality values
+    /// into account. On a tie, our own order of preference decides.
+    pub fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+        let mut best: Option<(Encoding, f32)> = None;
+        for encoding in Encoding::ALL {
+            let quality = accept_encoding
+                .split(',')
+                .filter_map(|item| {
+                    let mut params = item.split(';').map(str::trim);
+                    let name = params.next()?;
+                    if name != encoding.as_str() && name != "*" {
+                        return None;
+                    }
+                    let quality = params
+                        .find_map(|p| p.strip_prefix("q="))
+                        .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+                    // An explicit entry beats the wildcard.
+                    Some((name != "*", quality))
+                })
+                .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(_, quality)| quality)
+                .unwrap_or(0.0);
+            if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+                best = Some((encoding, quality));
+            }
+        }
+        best.map(|(encoding, _)| encoding)
+    }
+
+    /// Wrap a reader, so that reading from it yields the compressed data.
+    pub fn encode<'a>(
+        &self,
+        reader: impl AsyncBufRead + Send + 'a,
+    ) -> Pin<Box<dyn AsyncRead + Send + 'a>> {
+        match self {
+            Encoding::Zstd => Box::pin(ZstdEncoder::new(reader)),
+            Encoding::Brotli => Box::pin(BrotliEncoder::with_quality(
+                reader,
+                Level::Precise(BROTLI_QUALITY),
+            )),
+            Encoding::Gzip => Box::pin(GzipEncoder::new(reader)),
+        }
+    }
+}
+
+/// Fairing that compresses responses according to the Accept-Encoding header of the request.
+/// Bodies are compressed while they are sent, so this works for streamed responses as well.
+pub struct Compression;
+
+/// Event streams are left alone, as the encoders would hold back events until their buffer is full.
+fn is_compressible(content_type: Option<ContentType>) -> bool {
+    content_type.is_some_and(|ct| {
+        (ct.is_json() || ct.is_svg() || ct.is_javascript() || ct.top() == "text")
+            && !ct.is_event_stream()
+    })
+}
+
+#[rocket::async_trait]
+impl Fairing for Compression {
+    fn info(&self) -> Info {
+        Info {
+            name: "Response compression",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if response.headers().contains("Content-Encoding")
+            || !is_compressible(response.content_type())
+            || response
+                .body()
+                .preset_size()
+                .is_some_and(|size| size < MIN_COMPRESS_SIZE)
+        {
+            return;
+        }
+        let Some(encoding) = request
+            .headers()
+            .get_one("Accept-Encoding")
+            .and_then(Encoding::negotiate)
+        else {
+            return;
+        };
+
+        let body = BufReader::new(response.body_mut().take());
+        response.set_streamed_body(encoding.encode(body));
+        response.set_raw_header("Content-Encoding", encoding.as_str());
+        response.adjoin_raw_header("Vary", "Accept-Encoding");
+    }
+}
+
+/// The format of dates in HTTP headers, e.g. "Sun, 06 Nov 1994 08:49:37 GMT".
+const HTTP_DATE: &[FormatItem<'static>] = time::macros::format_description!(
+    "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+);
+
+pub fn format_http_date(date: OffsetDateTime) -> String {
+    date.to_offset(UtcOffset::UTC)
+        .format(HTTP_DATE)
+        .unwrap_or_default()
+}
+
+fn parse_http_date(date: &str) -> Option<OffsetDateTime> {
+    PrimitiveDateTime::parse(date, HTTP_DATE)
+        .ok()
+        .map(PrimitiveDateTime::assume_utc)
+}
+
+/// Identifies the content of a response, so clients can check whether their copy is still up to
+/// date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validators {
+    /// Including the double quotes.
+    pub etag: String,
+    /// Unknown if the mtime of a file doesn't tell anything, e.g. in the Nix store, where it's
+    /// always 1.
+    pub last_modified: Option<OffsetDateTime>,
+    /// Whether the response doesn't change anymore, e.g. because it covers a time range in the
+    /// past. Only then caches may keep it without revalidating.
+    pub immutable: bool,
+}
+
+/// The conditional headers of a request, to check whether the client already has a response.
+pub struct Conditional {
+    if_none_match: Option<String>,
+    if_modified_since: Option<OffsetDateTime>,
+}
+
+impl Conditional {
+    /// Whether the client's copy is still up to date. If-None-Match takes precedence over
+    /// If-Modified-Since, as HTTP dates only have a precision of one second.
+    pub fn is_fresh(&self, validators: &Validators) -> bool {
+        match &self.if_none_match {
+            Some(if_none_match) => if_none_match.split(',').any(|etag| {
+                let etag = etag.trim();
+                etag == "*" || etag.trim_start_matches("W/") == validators.etag
+            }),
+            None => self
+                .if_modified_since
+                .zip(validators.last_modified)
+                .is_some_and(|(since, last_modified)| {
+                    last_modified.replace_nanosecond(0).ok() <= Some(since)
+                }),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Conditional {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let headers = request.headers();
+        Outcome::Success(Conditional {
+            if_none_match: headers.get_one("If-None-Match").map(str::to_string),
+            if_modified_since: headers
+                .get_one("If-Modified-Since")
+                .and_then(parse_http_date),
+        })
+    }
+}
+
+/// A response along with its caching headers. Immutable responses may be cached, all others have
+/// to be revalidated every time.
+pub enum Cached<R> {
+    /// The client already has this response.
+    NotModified(Validators),
+    Fresh(R, Option<Validators>),
+}
+
+/// Set the Cache-Control header of a response along with its validators, if it has any.
+pub fn set_cache_headers(response: &mut Response<'_>, validators: Option<&Validators>) {
+    match validators {
+        Some(validators) => {
+            response.set_raw_header(
+                "Cache-Control",
+                if validators.immutable {
+                    format!("public, max-age={IMMUTABLE_MAX_AGE}")
+                } else {
+                    "no-cache".to_string()
+                },
+            );
+            response.set_raw_header("ETag", validators.etag.clone());
+            if let Some(last_modified) = validators.last_modified {
+                response.set_raw_header("Last-Modified", format_http_date(last_modified));
+            }
+        }
+        None => {
+            response.set_raw_header("Cache-Control", "no-cache");
+        }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Cached<R> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        match self {
+            Cached::NotModified(validators) => {
+                let mut response = Response::build().status(Status::NotModified).finalize();
+                set_cache_headers(&mut response, Some(&validators));
+                Ok(response)
+            }
+            Cached::Fresh(inner, validators) => {
+                let mut response = inner.respond_to(request)?;
+                set_cache_headers(&mut response, validators.as_ref());
+                Ok(response)
+            }
+        }
+    }
+}
+
+/// Serialize items into a JSON array and send it through a channel in chunks, so a response can
+/// be streamed while the items are still loaded from the db. Stops early if the receiver is gone,
+/// e.g. because the client disconnected. Has to be called from outside of the async runtime.
+pub fn send_json_array<T, E>(
+    items: impl Iterator<Item = Result<T, E>>,
+    tx: &mpsc::Sender<Vec<u8>>,
+) -> Result<(), Box<dyn Error + Send + Sync>>
+where
+    T: Serialize,
+    E: Error + Send + Sync + 'static,
+{
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    chunk.push(b'[');
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            chunk.push(b',');
+        }
+        serde_json::to_writer(&mut chunk, &item?)?;
+        if chunk.len() >= CHUNK_SIZE {
+            let full = std::mem::replace(&mut chunk, Vec::with_capacity(CHUNK_SIZE));
+            if tx.blocking_send(full).is_err() {
+                return Ok(());
+            }
+        }
+    }
+    chunk.push(b']');
+    let _ = tx.blocking_send(chunk);
+    Ok(())
+}
+
+/// Writes into a channel in chunks, so a response can be streamed while it is still being
+/// written, e.g. an export. Writing fails once the receiver is gone, e.g. because the client
+/// disconnected. Has to be used from outside of the async runtime.
+pub struct ChunkWriter {
+    tx: mpsc::Sender<Vec<u8>>,
+    chunk: Vec<u8>,
+}
+
+impl ChunkWriter {
+    pub fn new(tx: mpsc::Sender<Vec<u8>>) -> ChunkWriter {
+        ChunkWriter {
+            tx,
+            chunk: Vec::with_capacity(CHUNK_SIZE),
+        }
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.chunk.extend_from_slice(buf);
+        if self.chunk.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.chunk.is_empty() {
+            return Ok(());
+        }
+        let full = std::mem::replace(&mut self.chunk, Vec::with_capacity(CHUNK_SIZE));
+        self.tx
+            .blocking_send(full)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate_encoding() {
+        assert_eq!(
+            Encoding::negotiate("gzip, deflate, br, zstd"),
+            Some(Encoding::Zstd)
+        );
+        assert_eq!(
+            Encoding::negotiate("gzip, deflate, br"),
+            Some(Encoding::Brotli)
+        );
+        assert_eq!(
+            Encoding::negotiate("gzip;q=1.0, br;q=0.5"),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(Encoding::negotiate("*;q=0.1, br;q=0"), Some(Encoding::Zstd));
+        assert_eq!(Encoding::negotiate("identity"), None);
+        assert_eq!(Encoding::negotiate(""), None);
+    }
+
+    #[test]
+    fn compressible_content_types() {
+        assert!(is_compressible(Some(ContentType::JSON)));
+        assert!(is_compressible(Some(ContentType::CSV)));
+        assert!(!is_compressible(Some(ContentType::EventStream)));
+        assert!(!is_compressible(Some(ContentType::PNG)));
+        assert!(!is_compressible(None));
+    }
+
+    #[test]
+    fn http_date_roundtrip() {
+        let date = OffsetDateTime::from_unix_timestamp(784111777).unwrap();
+        assert_eq!(format_http_date(date), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Some(date));
+    }
+
+    #[test]
+    fn conditional_requests() {
+        let validators = Validators {
+            etag: "\"12-34\"".to_string(),
+            last_modified: Some(
+                OffsetDateTime::from_unix_timestamp(784111777).unwrap()
+                    + time::Duration::milliseconds(500),
+            ),
+            immutable: true,
+        };
+        let conditional =
+            |if_none_match: Option<&str>, if_modified_since: Option<i64>| Conditional {
+                if_none_match: if_none_match.map(str::to_string),
+                if_modified_since: if_modified_since
+                    .map(|t| OffsetDateTime::from_unix_timestamp(t).unwrap()),
+            };
+
+        assert!(conditional(Some("\"12-34\""), None).is_fresh(&validators));
+        assert!(conditional(Some("\"1-2\", W/\"12-34\""), None).is_fresh(&validators));
+        assert!(!conditional(Some("\"12-35\""), Some(784111777)).is_fresh(&validators));
+        assert!(conditional(None, Some(784111777)).is_fresh(&validators));
+        assert!(!conditional(None, Some(784111776)).is_fresh(&validators));
+        assert!(!conditional(None, None).is_fresh(&validators));
+        let validators = Validators {
+            last_modified: None,
+            ..validators
+        };
+        assert!(!conditional(None, Some(784111777)).is_fresh(&validators));
+    }
+
+    #[test]
+    fn cache_headers() {
+        let mut validators = Validators {
+            etag: "\"12-34\"".to_string(),
+            last_modified: Some(OffsetDateTime::from_unix_timestamp(784111777).unwrap()),
+            immutable: false,
+        };
+        let headers = |validators: Option<&Validators>| {
+            let mut response = Response::new();
+            set_cache_headers(&mut response, validators);
+            ["Cache-Control", "ETag"].map(|name| {
+                response
+                    .headers()
+                    .get_one(name)
+                    .map(str::to_string)
+                    .unwrap_or_default()
+            })
+        };
+
+        assert_eq!(headers(None), ["no-cache", ""]);
+        assert_eq!(headers(Some(&validators)), ["no-cache", "\"12-34\""]);
+        validators.immutable = true;
+        assert_eq!(
+            headers(Some(&validators)),
+            ["public, max-age=86400", "\"12-34\""]
+        );
+    }
+
+    #[test]
+    fn json_array_in_chunks() {
+        let items: Vec<u32> = (0..100_000).collect();
+        let (tx, mut rx) = mpsc::channel(1024);
+        send_json_array(items.iter().map(Ok::<_, std::io::Error>), &tx).unwrap();
+        drop(tx);
+
+        let mut chunks = 0;
+        let mut json = Vec::new();
+        while let Ok(chunk) = rx.try_recv() {
+            chunks += 1;
+            json.extend(chunk);
+        }
+        assert!(chunks > 1);
+        assert_eq!(json, serde_json::to_vec(&items).unwrap());
+
+        let (tx, mut rx) = mpsc::channel(1);
+        send_json_array(std::iter::empty::<Result<u32, std::io::Error>>(), &tx).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), b"[]");
+    }
+
+    #[test]
+    fn chunk_writer() {
+        let data: Vec<u8> = (0..200_000).map(|i| i as u8).collect();
+        let (tx, mut rx) = mpsc::channel(1024);
+        let mut writer = ChunkWriter::new(tx);
+        for part in data.chunks(1000) {
+            writer.write_all(part).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let mut chunks = 0;
+        let mut written = Vec::new();
+        while let Ok(chunk) = rx.try_recv() {
+            chunks += 1;
+            written.extend(chunk);
+        }
+        assert!(chunks > 1);
+        assert_eq!(written, data);
+
+        drop(rx);
+        writer.write_all(b"too late").unwrap();
+        assert!(writer.flush().is_err());
+    }
+}
@@ -2,23 +2,404 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::health::{Health, SubscriberKind, Subscription};
+use crate::models::{DelayRecordWithID, Direction};
+use crate::route::Route;
 use crate::DelayRecord;
-use bus::BusReadHandle;
+use bus::{BusReadHandle, BusReader};
+use diesel::BoolExpressionMethods;
+use diesel::ExpressionMethods;
+use diesel::OptionalExtension;
 use diesel::PgConnection;
+use diesel::QueryDsl;
+use diesel::QueryResult;
+use diesel::RunQueryDsl;
 use log::{debug, error, info, warn};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::error::Error;
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use time::OffsetDateTime;
+use tungstenite::protocol::WebSocket;
+use tungstenite::Message;
+
+/// How far back clients can request historic DelayRecord's.
+pub const MAX_HISTORIC_SECONDS: u64 = 3600 * 24 * 31;
+
+/// How long we wait for a DelayRecord from the bus, before checking for messages from the client.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// How long we remember that a trip has no line, before looking it up again.
+const UNKNOWN_LINE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Which DelayRecord's a client wants to get. Given as query parameters when connecting, e.g.
+/// `/api/ws/delays?direction=westwards&min_delay=300`, or later as a JSON message. Fields that
+/// aren't set don't filter anything.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct DelayRecordFilter {
+    /// Only these trips.
+    pub trip_ids: Vec<String>,
+    /// Only trains between these two stations on the route, in any order. Without one of them,
+    /// the range reaches to the end of the route.
+    pub from_station: Option<i64>,
+    pub to_station: Option<i64>,
+    pub direction: Option<Direction>,
+    /// Only trips of this line, e.g. "RE1". Case and spaces don't matter.
+    pub line: Option<String>,
+    /// Only DelayRecord's with a realtime delay of at least this many seconds.
+    pub min_delay: Option<i64>,
+}
+
+impl DelayRecordFilter {
+    /// Whether the DelayRecord passes the filter. `line` is the line of its trip, if known.
+    pub fn matches(&self, delay_record: &DelayRecord, route: &Route, line: Option<&str>) -> bool {
+        if !self.trip_ids.is_empty() && !self.trip_ids.contains(&delay_record.trip_id) {
+            return false;
+        }
+        if self
+            .direction
+            .is_some_and(|direction| delay_record.direction != Some(direction))
+        {
+            return false;
+        }
+        if self
+            .min_delay
+            .is_some_and(|min_delay| !delay_record.has_realtime || delay_record.delay < min_delay)
+        {
+            return false;
+        }
+        if let Some(wanted) = &self.line {
+            let normalize = |line: &str| line.replace(' ', "").to_lowercase();
+            if line.map(normalize) != Some(normalize(wanted)) {
+                return false;
+            }
+        }
+        if self.from_station.is_some() || self.to_station.is_some() {
+            let bound = |station: Option<i64>, default| match station {
+                Some(station) => route.position(station),
+                None => Some(default),
+            };
+            let (Some(from), Some(to)) = (
+                bound(self.from_station, i32::MIN),
+                bound(self.to_station, i32::MAX),
+            ) else {
+                return false;
+            };
+            let range = from.min(to)..=from.max(to);
+            let within = |station| route.position(station).is_some_and(|p| range.contains(&p));
+            if !within(delay_record.previous_station) || !within(delay_record.next_station) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A message from the client to change its subscription. The filter replaces the current one.
+/// With historic, the DelayRecord's of that many seconds that match the new filter are sent
+/// again, so the client may get some of them twice.
+#[derive(Deserialize, Debug, PartialEq)]
+struct SubscriptionMessage {
+    historic: Option<u64>,
+    #[serde(flatten)]
+    filter: DelayRecordFilter,
+}
+
+/// How many DelayRecord's are loaded from the database at once when sending historic ones.
+const HISTORIC_PAGE_SIZE: i64 = 1000;
+
+/// Loads the DelayRecord's since some point in time page by page, in the order they happened. This
+/// way the database connection doesn't have to be held while sending them. Records which don't
+/// change anything compared to their predecessor are skipped.
+pub struct HistoricDelayRecords {
+    /// The time and id of the last loaded DelayRecord.
+    after: (OffsetDateTime, i64),
+    latest_delay_records: HashMap<String, DelayRecord>,
+    /// The highest fetched_json_id of the loaded DelayRecord's. As the crawler stores
+    /// DelayRecord's in the order it fetches them, live ones up to it were already loaded.
+    pub last_fetched_json_id: i64,
+    done: bool,
+}
+
+impl HistoricDelayRecords {
+    pub fn since(since: OffsetDateTime) -> Self {
+        HistoricDelayRecords {
+            after: (since, i64::MAX),
+            latest_delay_records: HashMap::new(),
+            last_fetched_json_id: 0,
+            done: false,
+        }
+    }
+
+    /// The next page of DelayRecord's, or None once all of them are loaded.
+    pub fn next_page(&mut self, db: &mut PgConnection) -> QueryResult<Option<Vec<DelayRecord>>> {
+        use crate::schema::delay_records;
+
+        if self.done {
+            return Ok(None);
+        }
+        let (time, id) = self.after;
+        let rows = delay_records::table
+            .filter(delay_records::time.ge(time))
+            .filter(delay_records::time.gt(time).or(delay_records::id.gt(id)))
+            .order((delay_records::time.asc(), delay_records::id.asc()))
+            .limit(HISTORIC_PAGE_SIZE)
+            .load::<DelayRecordWithID>(db)?;
+        self.done = (rows.len() as i64) < HISTORIC_PAGE_SIZE;
+
+        let mut page = Vec::with_capacity(rows.len());
+        for row in rows {
+            self.after = (row.time, row.id);
+            self.last_fetched_json_id = self.last_fetched_json_id.max(row.fetched_json_id);
+            let delay_record = DelayRecord::from(row);
+            match delay_record.might_be_redundant(&mut self.latest_delay_records) {
+                None => {}
+                Some(None) => page.push(delay_record),
+                Some(Some(prev_delay_record)) => {
+                    page.push(prev_delay_record);
+                    page.push(delay_record);
+                }
+            }
+        }
+        Ok(Some(page))
+    }
+}
+
+fn send_message(websocket: &mut WebSocket<TcpStream>, msg: DelayRecord) -> Result<(), ()> {
+    match websocket.write(Message::Text(
+        serde_json::to_string(&msg)
+            .expect("This shouldn't fail.")
+            .into(),
+    )) {
+        Err(e) => {
+            warn!(
+                "{:?}: Couldn't queue message to subscriber: {}",
+                std::thread::current().id(),
+                e
+            );
+        }
+        Ok(_) => {
+            debug!(
+                "{:?}: Queued message to subscriber successfully.",
+                std::thread::current().id()
+            );
+        }
+    }
+    match websocket.flush() {
+        Err(e) => {
+            warn!(
+                "{:?}: Couldn't send message to subscriber: {}",
+                std::thread::current().id(),
+                e
+            );
+            Err(())
+        }
+        Ok(_) => {
+            debug!(
+                "{:?}: Sent message to subscriber successfully.",
+                std::thread::current().id()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// The lines of the trips that had DelayRecord's since `since`.
+fn load_trip_lines(
+    db: &mut PgConnection,
+    since: OffsetDateTime,
+) -> QueryResult<HashMap<String, Option<String>>> {
+    use crate::schema::{delay_records, trip_summaries};
+
+    Ok(trip_summaries::table
+        .filter(
+            trip_summaries::trip_id.eq_any(
+                delay_records::table
+                    .filter(delay_records::time.gt(since))
+                    .select(delay_records::trip_id),
+            ),
+        )
+        .select((trip_summaries::trip_id, trip_summaries::line))
+        .load::<(String, Option<String>)>(db)?
+        .into_iter()
+        .collect())
+}
+
+/// One websocket client along with what it subscribed to.
+struct Connection {
+    websocket: WebSocket<TcpStream>,
+    rx: BusReader<DelayRecord>,
+    subscription: Subscription,
+    /// Shared by all connections.
+    db: Arc<Mutex<PgConnection>>,
+    route: Route,
+    filter: DelayRecordFilter,
+    /// The lines of the trips we came across if the filter needs them, along with when we looked
+    /// them up.
+    lines: HashMap<String, (Option<String>, Instant)>,
+    /// Live DelayRecord's up to this fetched_json_id were already sent as historic ones.
+    historic_until: i64,
+}
+
+impl Connection {
+    /// Send the DelayRecord's of the last `historic_seconds` that match the filter. They are
+    /// loaded page by page, and the db connection shared by all connections is only locked while
+    /// loading one, so a slow client doesn't hold it.
+    fn send_historic(&mut self, historic_seconds: u64) -> QueryResult<()> {
+        let since = OffsetDateTime::now_utc() - std::time::Duration::from_secs(historic_seconds);
+        let mut historic = HistoricDelayRecords::since(since);
+        if self.filter.line.is_some() {
+            let lines =
+                load_trip_lines(&mut self.db.lock().expect("Poisoned db connection"), since)?;
+            let now = Instant::now();
+            self.lines.extend(
+                lines
+                    .into_iter()
+                    .map(|(trip_id, line)| (trip_id, (line, now))),
+            );
+        }
+
+        loop {
+            // The lock is released at the end of this statement.
+            let Some(page) =
+                historic.next_page(&mut self.db.lock().expect("Poisoned db connection"))?
+            else {
+                break;
+            };
+            for delay_record in page {
+                let line = self
+                    .lines
+                    .get(&delay_record.trip_id)
+                    .and_then(|(line, _)| line.clone());
+                if !self
+                    .filter
+                    .matches(&delay_record, &self.route, line.as_deref())
+                {
+                    continue;
+                }
+                if send_message(&mut self.websocket, delay_record).is_err() {
+                    return Ok(());
+                }
+                self.subscription.sent();
+            }
+        }
+        self.historic_until = self.historic_until.max(historic.last_fetched_json_id);
+        Ok(())
+    }
+
+    /// The line of a trip, looked up in trip_summaries if we don't know it yet. Trips without a
+    /// line are looked up again after UNKNOWN_LINE_TTL, as their summary might not be there yet.
+    fn line(&mut self, trip_id: &str) -> QueryResult<Option<String>> {
+        use crate::schema::trip_summaries;
+
+        match self.lines.get(trip_id) {
+            Some((Some(line), _)) => return Ok(Some(line.clone())),
+            Some((None, looked_up)) if looked_up.elapsed() < UNKNOWN_LINE_TTL => return Ok(None),
+            _ => {}
+        }
+        // Only lock the shared db connection for the query itself.
+        let line = {
+            let mut db = self.db.lock().expect("Poisoned db connection");
+            trip_summaries::table
+                .find(trip_id)
+                .select(trip_summaries::line)
+                .first::<Option<String>>(&mut *db)
+                .optional()?
+                .flatten()
+        };
+        self.lines
+            .insert(trip_id.to_string(), (line.clone(), Instant::now()));
+        Ok(line)
+    }
+
+    fn matches(&mut self, delay_record: &DelayRecord) -> QueryResult<bool> {
+        let line = match self.filter.line {
+            Some(_) => self.line(&delay_record.trip_id)?,
+            None => None,
+        };
+        Ok(self
+            .filter
+            .matches(delay_record, &self.route, line.as_deref()))
+    }
+
+    /// Apply a message from the client. Empty messages are ignored.
+    fn receive(&mut self, text: &str) -> QueryResult<()> {
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+        match serde_json::from_str::<SubscriptionMessage>(text) {
+            Err(e) => warn!("Ignoring invalid subscription message: {}", e),
+            Ok(SubscriptionMessage { historic, filter }) => {
+                debug!("Subscription changed to {:?}", filter);
+                self.filter = filter;
+                if let Some(historic) = historic {
+                    self.send_historic(historic.min(MAX_HISTORIC_SECONDS))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Forward live DelayRecord's that match the filter, while listening for messages from the
+    /// client. Returns when the client is gone.
+    fn forward_live(&mut self) -> Result<(), Box<dyn Error>> {
+        use std::sync::mpsc::RecvTimeoutError;
+
+        // Reading from the websocket mustn't block sending DelayRecord's.
+        self.websocket
+            .get_ref()
+            .set_read_timeout(Some(std::time::Duration::from_millis(1)))?;
+        loop {
+            match self.rx.recv_timeout(POLL_INTERVAL) {
+                Ok(delay_record) => {
+                    self.subscription.received();
+                    if delay_record.fetched_json_id > self.historic_until
+                        && self.matches(&delay_record)?
+                    {
+                        if send_message(&mut self.websocket, delay_record).is_err() {
+                            return Ok(());
+                        }
+                        self.subscription.sent();
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+            match self.websocket.read() {
+                Ok(Message::Text(text)) => self.receive(&text)?,
+                Ok(Message::Close(_)) => return Ok(()),
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(e))
+                    if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) => {}
+                Err(e) => {
+                    debug!("Websocket is gone: {}", e);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn serve(&mut self, historic_seconds: u64) -> Result<(), Box<dyn Error>> {
+        self.send_historic(historic_seconds)?;
+        debug!("Sent old messages to client, switching to live update now.");
+        self.forward_live()
+    }
+}
 
 /// Open the webserver and publish fetched data via Websockets.
 pub fn websocket_server(
-    db: &mut PgConnection,
+    db: PgConnection,
     bus_read_handle: BusReadHandle<DelayRecord>,
+    health: std::sync::Arc<Health>,
     listen: std::net::IpAddr,
     port: u16,
 ) -> Result<(), Box<dyn Error>> {
-    use diesel::ExpressionMethods;
-    use diesel::QueryDsl;
-    use diesel::RunQueryDsl;
     use std::net::{IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
     let socket_addr: std::net::SocketAddr = match listen {
@@ -30,17 +411,19 @@ pub fn websocket_server(
         error!("Can't bind to {}", socket_addr);
         std::process::exit(1);
     });
+    let db = Arc::new(Mutex::new(db));
     info!("Server started.");
     for stream in server.incoming() {
         use tungstenite::handshake::server::{Request, Response};
 
         // Default is one hour.
         let mut historic_seconds = 3600;
+        let mut filter = DelayRecordFilter::default();
 
         let ws_callback = |request: &Request, response: Response| {
             #[derive(Deserialize, Debug)]
             struct Query {
-                historic: u64,
+                historic: Option<u64>,
             }
 
             if let Some(query_str) = request.uri().query() {
@@ -50,12 +433,30 @@ pub fn websocket_server(
                         error!("{}", e);
                     }
                     Ok(Query { historic }) => {
-                        historic_seconds = std::cmp::min(historic, 3600 * 24 * 31);
+                        if let Some(historic) = historic {
+                            historic_seconds = std::cmp::min(historic, MAX_HISTORIC_SECONDS);
+                        }
+                    }
+                }
+                match serde_qs::from_str(query_str) {
+                    Err(e) => {
+                        error!("{}", e);
+                    }
+                    Ok(query_filter) => {
+                        filter = query_filter;
                     }
                 }
             }
 
             match request.uri().path() {
+                "/api/ws/delays" if !health.caches_are_ready() => {
+                    warn!("Refusing websocket, as the cache tables aren't up to date yet.");
+                    let unavailable = Response::builder()
+                        .status(tungstenite::http::StatusCode::SERVICE_UNAVAILABLE)
+                        .body(Some("The cache tables aren't up to date yet.".into()))
+                        .expect("This should never fail.");
+                    Err(unavailable)
+                }
                 "/api/ws/delays" => {
                     debug!("The request's route is: /api/ws/delays");
                     Ok(response)
@@ -72,7 +473,7 @@ pub fn websocket_server(
         };
         match stream {
             Ok(stream) => {
-                let mut websocket = match tungstenite::accept_hdr(stream, ws_callback) {
+                let websocket = match tungstenite::accept_hdr(stream, ws_callback) {
                     Ok(ws) => ws,
                     Err(e) => {
                         warn!("{}", e);
@@ -80,101 +481,38 @@ pub fn websocket_server(
                     }
                 };
 
-                use crate::models::DelayRecordWithID;
-                use crate::schema::delay_records;
-
-                let mut rx = bus_read_handle.add_rx();
+                // Subscribe before loading the historic DelayRecord's, so we don't miss any
+                // inbetween.
+                let rx = bus_read_handle.add_rx();
+                let subscription = health.subscribe(SubscriberKind::Websocket);
+                let db = db.clone();
 
-                use std::net::TcpStream;
-                use tungstenite::protocol::WebSocket;
-                use tungstenite::Message::Text;
-
-                fn send_message(
-                    websocket: &mut WebSocket<TcpStream>,
-                    msg: DelayRecord,
-                ) -> Result<(), ()> {
-                    match websocket.write(Text(
-                        serde_json::to_string(&msg)
-                            .expect("This shouldn't fail.")
-                            .into(),
-                    )) {
-                        Err(e) => {
-                            warn!(
-                                "{:?}: Couldn't queue message to subscriber: {}",
-                                std::thread::current().id(),
-                                e
-                            );
-                        }
-                        Ok(_) => {
-                            debug!(
-                                "{:?}: Queued message to subscriber successfully.",
-                                std::thread::current().id()
-                            );
-                        }
-                    }
-                    match websocket.flush() {
+                std::thread::spawn(move || {
+                    let route = match crate::route::load_route(
+                        &mut db.lock().expect("Poisoned db connection"),
+                    ) {
+                        Ok(route) => route,
                         Err(e) => {
-                            warn!(
-                                "{:?}: Couldn't send message to subscriber: {}",
-                                std::thread::current().id(),
-                                e
-                            );
-                            Err(())
-                        }
-                        Ok(_) => {
-                            debug!(
-                                "{:?}: Sent message to subscriber successfully.",
-                                std::thread::current().id()
-                            );
-                            Ok(())
-                        }
-                    }
-                }
-
-                {
-                    use std::collections::HashMap;
-                    use std::time::Duration;
-                    use time::OffsetDateTime;
-
-                    let old_delay_records = delay_records::dsl::delay_records
-                        .filter(
-                            delay_records::time
-                                .gt(OffsetDateTime::now_utc()
-                                    - Duration::from_secs(historic_seconds)),
-                        )
-                        .then_order_by(delay_records::time.asc())
-                        .load_iter::<DelayRecordWithID, diesel::pg::PgRowByRowLoadingMode>(db)
-                        .unwrap_or_else(|e| {
-                            panic!("Unable to load data from delay_records: {}", e);
-                        });
-
-                    let mut latest_delay_records: HashMap<String, DelayRecord> = HashMap::new();
-
-                    for delay_record_with_id in old_delay_records {
-                        let delay_record = DelayRecord::from(delay_record_with_id?);
-                        match delay_record.might_be_redundant(&mut latest_delay_records) {
-                            None => {}
-                            Some(None) => {
-                                let _ = send_message(&mut websocket, delay_record);
-                            }
-                            Some(Some(prev_delay_record)) => {
-                                let _ = send_message(&mut websocket, prev_delay_record);
-                                let _ = send_message(&mut websocket, delay_record);
-                            }
+                            error!("Unable to load the route: {}", e);
+                            return;
                         }
-                    }
-                }
-
-                debug!("Sent old messages to client, switching to live update now.");
-
-                std::thread::spawn(move || {
-                    while let Ok(msg) = rx.recv() {
-                        if send_message(&mut websocket, msg).is_err() {
-                            break;
-                        };
+                    };
+                    let mut connection = Connection {
+                        websocket,
+                        rx,
+                        subscription,
+                        db,
+                        route,
+                        filter,
+                        lines: HashMap::new(),
+                        historic_until: 0,
+                    };
+                    if let Err(e) = connection.serve(historic_seconds) {
+                        error!("{}", e);
                     }
 
                     info!("Closing websocket");
+                    let mut websocket = connection.websocket;
                     websocket
                         .close(None)
                         .unwrap_or_else(|_| warn!("Can't close websocket in a normal way."));
@@ -191,3 +529,145 @@ pub fn websocket_server(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Station, TripState};
+
+    fn route() -> Route {
+        Route::new(
+            (1..=5)
+                .map(|id| Station {
+                    id,
+                    name: id.to_string(),
+                    short_name: None,
+                    important: false,
+                    position: Some(id as i32 * 10),
+                    km: None,
+                    latitude: None,
+                    longitude: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn delay_record(previous_station: i64, next_station: i64, delay: i64) -> DelayRecord {
+        DelayRecord {
+            fetched_json_id: 1,
+            trip_id: "RE1_3709_20231115T0900".to_string(),
+            time: OffsetDateTime::from_unix_timestamp(1_700_035_200).unwrap(),
+            previous_station,
+            next_station,
+            percentage_segment: 0.5,
+            delay,
+            arrival_delay: Some(delay),
+            departure_delay: None,
+            has_realtime: true,
+            trip_state: TripState::EnRoute,
+            direction: Some(Direction::Westwards),
+        }
+    }
+
+    #[test]
+    fn filter_matches() {
+        let route = route();
+        let dr = delay_record(2, 3, 120);
+        let matches = |filter: DelayRecordFilter| filter.matches(&dr, &route, Some("RE 1"));
+
+        assert!(matches(DelayRecordFilter::default()));
+        assert!(matches(DelayRecordFilter {
+            trip_ids: vec!["other".to_string(), dr.trip_id.clone()],
+            direction: Some(Direction::Westwards),
+            line: Some("re1".to_string()),
+            min_delay: Some(120),
+            ..Default::default()
+        }));
+        assert!(!matches(DelayRecordFilter {
+            trip_ids: vec!["other".to_string()],
+            ..Default::default()
+        }));
+        assert!(!matches(DelayRecordFilter {
+            direction: Some(Direction::Eastwards),
+            ..Default::default()
+        }));
+        assert!(!matches(DelayRecordFilter {
+            line: Some("RE2".to_string()),
+            ..Default::default()
+        }));
+        assert!(!matches(DelayRecordFilter {
+            min_delay: Some(121),
+            ..Default::default()
+        }));
+        assert!(!DelayRecordFilter {
+            line: Some("RE1".to_string()),
+            ..Default::default()
+        }
+        .matches(&dr, &route, None));
+        assert!(!DelayRecordFilter {
+            min_delay: Some(0),
+            ..Default::default()
+        }
+        .matches(
+            &DelayRecord {
+                has_realtime: false,
+                ..dr.clone()
+            },
+            &route,
+            None
+        ));
+    }
+
+    #[test]
+    fn filter_station_range() {
+        let route = route();
+        let dr = delay_record(2, 3, 0);
+        let matches = |from_station, to_station| {
+            DelayRecordFilter {
+                from_station,
+                to_station,
+                ..Default::default()
+            }
+            .matches(&dr, &route, None)
+        };
+
+        assert!(matches(Some(2), Some(3)));
+        assert!(matches(Some(4), Some(1)));
+        assert!(matches(Some(2), None));
+        assert!(matches(None, Some(3)));
+        assert!(!matches(Some(3), Some(5)));
+        assert!(!matches(None, Some(2)));
+        // Stations that aren't on the route.
+        assert!(!matches(Some(99), Some(3)));
+    }
+
+    #[test]
+    fn parse_subscription() {
+        let filter: DelayRecordFilter =
+            serde_qs::from_str("historic=60&direction=eastwards&min_delay=300&trip_ids[0]=a")
+                .unwrap();
+        assert_eq!(
+            filter,
+            DelayRecordFilter {
+                trip_ids: vec!["a".to_string()],
+                direction: Some(Direction::Eastwards),
+                min_delay: Some(300),
+                ..Default::default()
+            }
+        );
+
+        let msg: SubscriptionMessage =
+            serde_json::from_str(r#"{"historic": 600, "from_station": 2, "line": "RE1"}"#).unwrap();
+        assert_eq!(
+            msg,
+            SubscriptionMessage {
+                historic: Some(600),
+                filter: DelayRecordFilter {
+                    from_station: Some(2),
+                    line: Some("RE1".to_string()),
+                    ..Default::default()
+                }
+            }
+        );
+    }
+}